@@ -1,5 +1,7 @@
 use std::sync::{Arc, Mutex};
 
+use crate::{buffer::bufferstats::BufferStats, tx::isolationlevel::IsolationLevel};
+
 use super::{
     driver::SQLError,
     embedded::{embeddedconnection::EmbeddedConnection, embeddedstatement::EmbeddedStatement},
@@ -11,6 +13,12 @@ pub trait ConnectionControl {
     fn close(&mut self) -> Result<(), SQLError>;
     fn commit(&mut self) -> Result<(), SQLError>;
     fn rollback(&mut self) -> Result<(), SQLError>;
+    fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), SQLError>;
+    fn set_read_only(&mut self, read_only: bool) -> Result<(), SQLError>;
+    fn savepoint(&mut self, name: &str) -> Result<(), SQLError>;
+    fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), SQLError>;
+    fn release_savepoint(&mut self, name: &str) -> Result<(), SQLError>;
+    fn buffer_stats(&mut self) -> Result<BufferStats, SQLError>;
 }
 
 pub enum Connection {
@@ -51,6 +59,48 @@ impl ConnectionControl for Connection {
             Connection::Network(conn) => conn.lock().unwrap().rollback(),
         }
     }
+
+    fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), SQLError> {
+        match self {
+            Connection::Embedded(conn) => conn.lock().unwrap().set_isolation_level(level),
+            Connection::Network(conn) => conn.lock().unwrap().set_isolation_level(level),
+        }
+    }
+
+    fn set_read_only(&mut self, read_only: bool) -> Result<(), SQLError> {
+        match self {
+            Connection::Embedded(conn) => conn.lock().unwrap().set_read_only(read_only),
+            Connection::Network(conn) => conn.lock().unwrap().set_read_only(read_only),
+        }
+    }
+
+    fn savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        match self {
+            Connection::Embedded(conn) => conn.lock().unwrap().savepoint(name),
+            Connection::Network(conn) => conn.lock().unwrap().savepoint(name),
+        }
+    }
+
+    fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        match self {
+            Connection::Embedded(conn) => conn.lock().unwrap().rollback_to_savepoint(name),
+            Connection::Network(conn) => conn.lock().unwrap().rollback_to_savepoint(name),
+        }
+    }
+
+    fn release_savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        match self {
+            Connection::Embedded(conn) => conn.lock().unwrap().release_savepoint(name),
+            Connection::Network(conn) => conn.lock().unwrap().release_savepoint(name),
+        }
+    }
+
+    fn buffer_stats(&mut self) -> Result<BufferStats, SQLError> {
+        match self {
+            Connection::Embedded(conn) => conn.lock().unwrap().buffer_stats(),
+            Connection::Network(conn) => conn.lock().unwrap().buffer_stats(),
+        }
+    }
 }
 
 impl Connection {
@@ -2,15 +2,17 @@ use std::sync::{Arc, Mutex};
 
 use crate::{
     api::{connection::ConnectionControl, driver::SQLError},
+    buffer::bufferstats::BufferStats,
     plan::planner::Planner,
     server::simpledb::SimpleDB,
-    tx::transaction::Transaction,
+    tx::{isolationlevel::IsolationLevel, transaction::Transaction},
 };
 
 pub struct EmbeddedConnection {
     db: SimpleDB,
     current_tx: Arc<Mutex<Transaction>>,
     planner: Option<Arc<Mutex<Planner>>>,
+    read_only: bool,
 }
 
 impl EmbeddedConnection {
@@ -21,6 +23,7 @@ impl EmbeddedConnection {
             db,
             current_tx,
             planner,
+            read_only: false,
         })
     }
 
@@ -31,6 +34,18 @@ impl EmbeddedConnection {
     pub(in crate::api) fn planner(&self) -> Option<Arc<Mutex<Planner>>> {
         self.planner.clone()
     }
+
+    fn renew_transaction(&mut self) -> Result<(), SQLError> {
+        let level = self.current_tx.lock().unwrap().isolation_level();
+        let mut tx = if self.read_only {
+            self.db.new_read_only_tx()?
+        } else {
+            self.db.new_tx()?
+        };
+        tx.set_isolation_level(level);
+        self.current_tx = Arc::new(Mutex::new(tx));
+        Ok(())
+    }
 }
 
 impl ConnectionControl for EmbeddedConnection {
@@ -40,13 +55,45 @@ impl ConnectionControl for EmbeddedConnection {
 
     fn commit(&mut self) -> Result<(), SQLError> {
         self.current_tx.lock().unwrap().commit()?;
-        self.current_tx = Arc::new(Mutex::new(self.db.new_tx()?));
-        Ok(())
+        self.renew_transaction()
     }
 
     fn rollback(&mut self) -> Result<(), SQLError> {
         self.current_tx.lock().unwrap().rollback()?;
-        self.current_tx = Arc::new(Mutex::new(self.db.new_tx()?));
+        self.renew_transaction()
+    }
+
+    fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), SQLError> {
+        self.current_tx.lock().unwrap().set_isolation_level(level);
         Ok(())
     }
+
+    fn set_read_only(&mut self, read_only: bool) -> Result<(), SQLError> {
+        if read_only == self.read_only {
+            return Ok(());
+        }
+        self.current_tx.lock().unwrap().commit()?;
+        self.read_only = read_only;
+        self.renew_transaction()
+    }
+
+    fn savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        Ok(self.current_tx.lock().unwrap().savepoint(name)?)
+    }
+
+    fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        Ok(self
+            .current_tx
+            .lock()
+            .unwrap()
+            .rollback_to_savepoint(name)?)
+    }
+
+    fn release_savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        Ok(self.current_tx.lock().unwrap().release_savepoint(name)?)
+    }
+
+    fn buffer_stats(&mut self) -> Result<BufferStats, SQLError> {
+        Ok(self.current_tx.lock().unwrap().buffer_stats())
+    }
 }
@@ -5,17 +5,27 @@ use crate::{
         connection::Connection,
         driver::{DriverControl, SQLError},
     },
-    server::simpledb::SimpleDB,
+    server::{dbconfig::DbConfig, simpledb::SimpleDB},
 };
 
 use super::embeddedconnection::EmbeddedConnection;
 
-pub struct EmbeddedDriver {}
+#[allow(dead_code)]
+pub struct EmbeddedDriver {
+    config: Option<DbConfig>,
+}
 
 impl EmbeddedDriver {
     #[allow(dead_code)]
     pub fn new() -> EmbeddedDriver {
-        EmbeddedDriver {}
+        EmbeddedDriver { config: None }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_config(config: DbConfig) -> EmbeddedDriver {
+        EmbeddedDriver {
+            config: Some(config),
+        }
     }
 }
 
@@ -25,7 +35,10 @@ impl DriverControl for EmbeddedDriver {
         if let Some(idx) = dbname.rfind(':') {
             dbname = &dbname[idx + 1..]
         }
-        let db = SimpleDB::new(dbname)?;
+        let db = match &self.config {
+            Some(config) => SimpleDB::with_config(dbname, config)?,
+            None => SimpleDB::new(dbname)?,
+        };
         Ok(Arc::new(Mutex::new(EmbeddedConnection::new(db)?)).into())
     }
 }
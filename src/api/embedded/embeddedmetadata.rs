@@ -37,7 +37,13 @@ impl MetaDataControl for EmbeddedMetaData {
         let fldtype = self.sch.type_(&fldname);
         let fldlength = match fldtype {
             Type::Integer => 6,
+            Type::BigInt => 20,
+            Type::Double => 24,
             Type::Varchar => self.sch.length(&fldname),
+            Type::Boolean => 5,
+            Type::Date => 10,
+            Type::Timestamp => 26,
+            Type::Text | Type::Blob => 20,
         };
         Ok(cmp::max(fldname.len(), fldlength) + 1)
     }
@@ -6,7 +6,10 @@ use crate::{
         resultset::ResultSetControl,
     },
     plan::plan::{Plan, PlanControl},
-    query::scan::{Scan, ScanControl},
+    query::{
+        constant::Constant,
+        scan::{Scan, ScanControl},
+    },
     record::schema::Schema,
     tx::transaction::TransactionError,
 };
@@ -17,6 +20,7 @@ pub struct EmbeddedResultSet {
     s: Scan,
     sch: Arc<Schema>,
     conn: Arc<Mutex<EmbeddedConnection>>,
+    was_null: bool,
 }
 
 impl EmbeddedResultSet {
@@ -28,8 +32,27 @@ impl EmbeddedResultSet {
             s: plan.open()?,
             sch: plan.schema(),
             conn,
+            was_null: false,
         })
     }
+
+    fn get<T>(
+        &mut self,
+        fldname: &str,
+        default: T,
+        convert: impl FnOnce(&Constant) -> Option<T>,
+    ) -> Result<T, SQLError> {
+        let fldname = fldname.to_lowercase();
+        if let Ok(v) = self.s.get_val(&fldname) {
+            self.was_null = v.is_null();
+            if v.is_null() {
+                return Ok(default);
+            }
+            return convert(&v).ok_or(TransactionError::TypeMismatch(fldname).into());
+        }
+        self.conn.lock().unwrap().rollback()?;
+        Err(SQLError::General)
+    }
 }
 
 impl ResultSetControl for EmbeddedResultSet {
@@ -42,21 +65,29 @@ impl ResultSetControl for EmbeddedResultSet {
     }
 
     fn get_int(&mut self, fldname: &str) -> Result<i32, SQLError> {
-        let fldname = fldname.to_lowercase();
-        if let Ok(v) = self.s.get_int(&fldname) {
-            return Ok(v);
-        }
-        self.conn.lock().unwrap().rollback()?;
-        Err(SQLError::General)
+        self.get(fldname, 0, Constant::as_int)
     }
 
     fn get_string(&mut self, fldname: &str) -> Result<String, SQLError> {
-        let fldname = fldname.to_lowercase();
-        if let Ok(v) = self.s.get_string(&fldname) {
-            return Ok(v);
-        }
-        self.conn.lock().unwrap().rollback()?;
-        Err(SQLError::General)
+        self.get(fldname, String::new(), |v| {
+            Some(v.as_string().unwrap_or_else(|| v.to_string()))
+        })
+    }
+
+    fn get_long(&mut self, fldname: &str) -> Result<i64, SQLError> {
+        self.get(fldname, 0, Constant::as_long)
+    }
+
+    fn get_double(&mut self, fldname: &str) -> Result<f64, SQLError> {
+        self.get(fldname, 0.0, Constant::as_double)
+    }
+
+    fn get_boolean(&mut self, fldname: &str) -> Result<bool, SQLError> {
+        self.get(fldname, false, Constant::as_bool)
+    }
+
+    fn was_null(&self) -> bool {
+        self.was_null
     }
 
     fn get_meta_data(&self) -> MetaData {
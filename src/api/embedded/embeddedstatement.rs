@@ -38,9 +38,15 @@ impl StatementControl for EmbeddedStatement {
         let planner = self.conn.lock().unwrap().planner();
         if let Some(planner) = planner {
             let result = planner.lock().unwrap().execute_update(cmd, tx);
-            if let Ok(result) = result {
-                if self.conn.lock().unwrap().commit().is_ok() {
-                    return Ok(result);
+            match result {
+                Ok(result) => {
+                    if self.conn.lock().unwrap().commit().is_ok() {
+                        return Ok(result);
+                    }
+                }
+                Err(e) => {
+                    self.conn.lock().unwrap().rollback()?;
+                    return Err(e.into());
                 }
             }
         }
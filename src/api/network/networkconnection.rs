@@ -3,11 +3,16 @@ use std::future::Future;
 use tokio::runtime::Runtime;
 use tonic::transport::{Channel, Endpoint};
 
-use crate::api::{connection::ConnectionControl, driver::SQLError};
+use crate::{
+    api::{connection::ConnectionControl, driver::SQLError},
+    buffer::bufferstats::BufferStats,
+    tx::isolationlevel::IsolationLevel,
+};
 
 use super::simpledb::{
     connection_client::ConnectionClient, CloseConnectionRequest, CommitRequest,
-    RollbackRequest,
+    GetBufferStatsRequest, ReleaseSavepointRequest, RollbackRequest, RollbackToSavepointRequest,
+    SavepointRequest, SetIsolationLevelRequest, SetReadOnlyRequest,
 };
 
 pub struct NetworkConnection {
@@ -60,4 +65,69 @@ impl ConnectionControl for NetworkConnection {
         self.rt.block_on(self.client.rollback(request))?;
         Ok(())
     }
+
+    fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), SQLError> {
+        let level = match level {
+            IsolationLevel::ReadUncommitted => 1,
+            IsolationLevel::ReadCommitted => 2,
+            IsolationLevel::RepeatableRead => 4,
+            IsolationLevel::Serializable => 8,
+        };
+        let request = tonic::Request::new(SetIsolationLevelRequest { level });
+        self.rt.block_on(self.client.set_isolation_level(request))?;
+        Ok(())
+    }
+
+    fn set_read_only(&mut self, read_only: bool) -> Result<(), SQLError> {
+        let request = tonic::Request::new(SetReadOnlyRequest { read_only });
+        self.rt.block_on(self.client.set_read_only(request))?;
+        Ok(())
+    }
+
+    fn savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        let request = tonic::Request::new(SavepointRequest {
+            name: name.to_string(),
+        });
+        self.rt.block_on(self.client.savepoint(request))?;
+        Ok(())
+    }
+
+    fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        let request = tonic::Request::new(RollbackToSavepointRequest {
+            name: name.to_string(),
+        });
+        self.rt
+            .block_on(self.client.rollback_to_savepoint(request))?;
+        Ok(())
+    }
+
+    fn release_savepoint(&mut self, name: &str) -> Result<(), SQLError> {
+        let request = tonic::Request::new(ReleaseSavepointRequest {
+            name: name.to_string(),
+        });
+        self.rt.block_on(self.client.release_savepoint(request))?;
+        Ok(())
+    }
+
+    fn buffer_stats(&mut self) -> Result<BufferStats, SQLError> {
+        let request = tonic::Request::new(GetBufferStatsRequest {});
+        let reply = self
+            .rt
+            .block_on(self.client.get_buffer_stats(request))?
+            .into_inner();
+        let resident = reply
+            .resident
+            .into_iter()
+            .map(|(filename, count)| (filename, count as usize))
+            .collect();
+        Ok(BufferStats::new(
+            reply.hits as usize,
+            reply.misses as usize,
+            reply.evictions as usize,
+            reply.dirty as usize,
+            reply.pin_waits as usize,
+            reply.pin_timeouts as usize,
+            resident,
+        ))
+    }
 }
@@ -62,11 +62,7 @@ impl MetaDataControl for NetworkMetaData {
             .lock()
             .unwrap()
             .run(self.client.get_column_type(request))?;
-        match response.into_inner().r#type {
-            4 => Ok(Type::Integer),
-            12 => Ok(Type::Varchar),
-            _ => Err(SQLError::General),
-        }
+        Type::from_code(response.into_inner().r#type).ok_or(SQLError::General)
     }
 
     fn get_column_display_size(&mut self, column: usize) -> Result<usize, SQLError> {
@@ -8,8 +8,8 @@ use super::{
     networkconnection::NetworkConnection,
     networkmetadata::NetworkMetaData,
     simpledb::{
-        result_set_client::ResultSetClient, CloseResultSetRequest, GetIntRequest, GetStringRequest,
-        NextRequest,
+        result_set_client::ResultSetClient, CloseResultSetRequest, GetBooleanRequest,
+        GetDoubleRequest, GetIntRequest, GetLongRequest, GetStringRequest, NextRequest,
     },
 };
 
@@ -17,12 +17,18 @@ pub struct NetworkResultSet {
     conn: Arc<Mutex<NetworkConnection>>,
     client: ResultSetClient<Channel>,
     id: u64,
+    was_null: bool,
 }
 
 impl NetworkResultSet {
     pub fn new(conn: Arc<Mutex<NetworkConnection>>, id: u64) -> NetworkResultSet {
         let client = ResultSetClient::new(conn.lock().unwrap().channel());
-        NetworkResultSet { conn, client, id }
+        NetworkResultSet {
+            conn,
+            client,
+            id,
+            was_null: false,
+        }
     }
 }
 
@@ -42,8 +48,10 @@ impl ResultSetControl for NetworkResultSet {
             .conn
             .lock()
             .unwrap()
-            .run(self.client.get_int(request))?;
-        Ok(response.into_inner().value)
+            .run(self.client.get_int(request))?
+            .into_inner();
+        self.was_null = response.was_null;
+        Ok(response.value)
     }
 
     fn get_string(&mut self, fldname: &str) -> Result<String, SQLError> {
@@ -55,8 +63,59 @@ impl ResultSetControl for NetworkResultSet {
             .conn
             .lock()
             .unwrap()
-            .run(self.client.get_string(request))?;
-        Ok(response.into_inner().value)
+            .run(self.client.get_string(request))?
+            .into_inner();
+        self.was_null = response.was_null;
+        Ok(response.value)
+    }
+
+    fn get_long(&mut self, fldname: &str) -> Result<i64, SQLError> {
+        let request = tonic::Request::new(GetLongRequest {
+            id: self.id,
+            name: fldname.to_string(),
+        });
+        let response = self
+            .conn
+            .lock()
+            .unwrap()
+            .run(self.client.get_long(request))?
+            .into_inner();
+        self.was_null = response.was_null;
+        Ok(response.value)
+    }
+
+    fn get_double(&mut self, fldname: &str) -> Result<f64, SQLError> {
+        let request = tonic::Request::new(GetDoubleRequest {
+            id: self.id,
+            name: fldname.to_string(),
+        });
+        let response = self
+            .conn
+            .lock()
+            .unwrap()
+            .run(self.client.get_double(request))?
+            .into_inner();
+        self.was_null = response.was_null;
+        Ok(response.value)
+    }
+
+    fn get_boolean(&mut self, fldname: &str) -> Result<bool, SQLError> {
+        let request = tonic::Request::new(GetBooleanRequest {
+            id: self.id,
+            name: fldname.to_string(),
+        });
+        let response = self
+            .conn
+            .lock()
+            .unwrap()
+            .run(self.client.get_boolean(request))?
+            .into_inner();
+        self.was_null = response.was_null;
+        Ok(response.value)
+    }
+
+    fn was_null(&self) -> bool {
+        self.was_null
     }
 
     fn get_meta_data(&self) -> MetaData {
@@ -6,12 +6,16 @@ use crate::api::connection::ConnectionControl;
 use crate::api::driver::SQLError;
 use crate::api::embedded::embeddedconnection::EmbeddedConnection;
 use crate::server::simpledb::SimpleDB;
+use crate::tx::isolationlevel::IsolationLevel;
 
 use super::remotestatement::RemoteStatement;
 use super::simpledb::connection_server::Connection;
 use super::simpledb::{
-    CloseConnectionReply, CloseConnectionRequest, CommitReply, CommitRequest, RollbackReply,
-    RollbackRequest,
+    CloseConnectionReply, CloseConnectionRequest, CommitReply, CommitRequest, GetBufferStatsReply,
+    GetBufferStatsRequest, ReleaseSavepointReply, ReleaseSavepointRequest, RollbackReply,
+    RollbackRequest, RollbackToSavepointReply, RollbackToSavepointRequest, SavepointReply,
+    SavepointRequest, SetIsolationLevelReply, SetIsolationLevelRequest, SetReadOnlyReply,
+    SetReadOnlyRequest,
 };
 
 pub struct RemoteConnection {
@@ -62,4 +66,106 @@ impl Connection for RemoteConnection {
         let reply = RollbackReply {};
         Ok(Response::new(reply))
     }
+
+    async fn set_isolation_level(
+        &self,
+        request: Request<SetIsolationLevelRequest>,
+    ) -> Result<Response<SetIsolationLevelReply>, Status> {
+        let level = match request.into_inner().level {
+            1 => IsolationLevel::ReadUncommitted,
+            2 => IsolationLevel::ReadCommitted,
+            4 => IsolationLevel::RepeatableRead,
+            8 => IsolationLevel::Serializable,
+            _ => return Err(Status::invalid_argument("unknown isolation level")),
+        };
+        if self
+            .conn
+            .lock()
+            .unwrap()
+            .set_isolation_level(level)
+            .is_err()
+        {
+            return Err(Status::internal("failed to set isolation level"));
+        }
+        let reply = SetIsolationLevelReply {};
+        Ok(Response::new(reply))
+    }
+
+    async fn set_read_only(
+        &self,
+        request: Request<SetReadOnlyRequest>,
+    ) -> Result<Response<SetReadOnlyReply>, Status> {
+        let read_only = request.into_inner().read_only;
+        if self.conn.lock().unwrap().set_read_only(read_only).is_err() {
+            return Err(Status::internal("failed to set read only"));
+        }
+        let reply = SetReadOnlyReply {};
+        Ok(Response::new(reply))
+    }
+
+    async fn savepoint(
+        &self,
+        request: Request<SavepointRequest>,
+    ) -> Result<Response<SavepointReply>, Status> {
+        let name = request.into_inner().name;
+        if self.conn.lock().unwrap().savepoint(&name).is_err() {
+            return Err(Status::internal("failed to set savepoint"));
+        }
+        let reply = SavepointReply {};
+        Ok(Response::new(reply))
+    }
+
+    async fn rollback_to_savepoint(
+        &self,
+        request: Request<RollbackToSavepointRequest>,
+    ) -> Result<Response<RollbackToSavepointReply>, Status> {
+        let name = request.into_inner().name;
+        if self
+            .conn
+            .lock()
+            .unwrap()
+            .rollback_to_savepoint(&name)
+            .is_err()
+        {
+            return Err(Status::internal("failed to rollback to savepoint"));
+        }
+        let reply = RollbackToSavepointReply {};
+        Ok(Response::new(reply))
+    }
+
+    async fn release_savepoint(
+        &self,
+        request: Request<ReleaseSavepointRequest>,
+    ) -> Result<Response<ReleaseSavepointReply>, Status> {
+        let name = request.into_inner().name;
+        if self.conn.lock().unwrap().release_savepoint(&name).is_err() {
+            return Err(Status::internal("failed to release savepoint"));
+        }
+        let reply = ReleaseSavepointReply {};
+        Ok(Response::new(reply))
+    }
+
+    async fn get_buffer_stats(
+        &self,
+        _: Request<GetBufferStatsRequest>,
+    ) -> Result<Response<GetBufferStatsReply>, Status> {
+        let stats = match self.conn.lock().unwrap().buffer_stats() {
+            Ok(stats) => stats,
+            Err(_) => return Err(Status::internal("failed to get buffer stats")),
+        };
+        let reply = GetBufferStatsReply {
+            hits: stats.hits() as u64,
+            misses: stats.misses() as u64,
+            evictions: stats.evictions() as u64,
+            dirty: stats.dirty() as u64,
+            pin_waits: stats.pin_waits() as u64,
+            pin_timeouts: stats.pin_timeouts() as u64,
+            resident: stats
+                .resident()
+                .iter()
+                .map(|(filename, count)| (filename.clone(), *count as u64))
+                .collect(),
+        };
+        Ok(Response::new(reply))
+    }
 }
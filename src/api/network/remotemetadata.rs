@@ -5,12 +5,9 @@ use std::{
 
 use tonic::{Request, Response, Status};
 
-use crate::{
-    api::{
-        embedded::embeddedresultset::EmbeddedResultSet, metadata::MetaDataControl,
-        resultset::ResultSetControl,
-    },
-    record::schema::Type,
+use crate::api::{
+    embedded::embeddedresultset::EmbeddedResultSet, metadata::MetaDataControl,
+    resultset::ResultSetControl,
 };
 
 use super::simpledb::{
@@ -78,11 +75,9 @@ impl MetaData for RemoteMetaData {
         if let Some(rs) = rs {
             let type_ = rs.get_meta_data().get_column_type(request.index as usize);
             if let Ok(type_) = type_ {
-                let type_ = match type_ {
-                    Type::Integer => 4,
-                    Type::Varchar => 12,
+                let reply = GetColumnTypeReply {
+                    r#type: type_ as i32,
                 };
-                let reply = GetColumnTypeReply { r#type: type_ };
                 return Ok(Response::new(reply));
             }
         }
@@ -8,8 +8,9 @@ use tonic::{Request, Response, Status};
 use crate::api::{embedded::embeddedresultset::EmbeddedResultSet, resultset::ResultSetControl};
 
 use super::simpledb::{
-    result_set_server::ResultSet, CloseResultSetReply, CloseResultSetRequest, GetIntReply,
-    GetIntRequest, GetStringReply, GetStringRequest, NextReply, NextRequest,
+    result_set_server::ResultSet, CloseResultSetReply, CloseResultSetRequest, GetBooleanReply,
+    GetBooleanRequest, GetDoubleReply, GetDoubleRequest, GetIntReply, GetIntRequest, GetLongReply,
+    GetLongRequest, GetStringReply, GetStringRequest, NextReply, NextRequest,
 };
 
 pub struct RemoteResultSet {
@@ -47,7 +48,10 @@ impl ResultSet for RemoteResultSet {
         let rs = rss.get_mut(&request.id);
         if let Some(rs) = rs {
             if let Ok(value) = rs.get_int(&request.name) {
-                let reply = GetIntReply { value };
+                let reply = GetIntReply {
+                    value,
+                    was_null: rs.was_null(),
+                };
                 return Ok(Response::new(reply));
             }
         }
@@ -63,13 +67,73 @@ impl ResultSet for RemoteResultSet {
         let rs = rss.get_mut(&request.id);
         if let Some(rs) = rs {
             if let Ok(value) = rs.get_string(&request.name) {
-                let reply = GetStringReply { value };
+                let reply = GetStringReply {
+                    value,
+                    was_null: rs.was_null(),
+                };
                 return Ok(Response::new(reply));
             }
         }
         Err(Status::internal("failed to get the string"))
     }
 
+    async fn get_long(
+        &self,
+        request: Request<GetLongRequest>,
+    ) -> Result<Response<GetLongReply>, Status> {
+        let request = request.into_inner();
+        let mut rss = self.rss.lock().unwrap();
+        let rs = rss.get_mut(&request.id);
+        if let Some(rs) = rs {
+            if let Ok(value) = rs.get_long(&request.name) {
+                let reply = GetLongReply {
+                    value,
+                    was_null: rs.was_null(),
+                };
+                return Ok(Response::new(reply));
+            }
+        }
+        Err(Status::internal("failed to get the long"))
+    }
+
+    async fn get_double(
+        &self,
+        request: Request<GetDoubleRequest>,
+    ) -> Result<Response<GetDoubleReply>, Status> {
+        let request = request.into_inner();
+        let mut rss = self.rss.lock().unwrap();
+        let rs = rss.get_mut(&request.id);
+        if let Some(rs) = rs {
+            if let Ok(value) = rs.get_double(&request.name) {
+                let reply = GetDoubleReply {
+                    value,
+                    was_null: rs.was_null(),
+                };
+                return Ok(Response::new(reply));
+            }
+        }
+        Err(Status::internal("failed to get the double"))
+    }
+
+    async fn get_boolean(
+        &self,
+        request: Request<GetBooleanRequest>,
+    ) -> Result<Response<GetBooleanReply>, Status> {
+        let request = request.into_inner();
+        let mut rss = self.rss.lock().unwrap();
+        let rs = rss.get_mut(&request.id);
+        if let Some(rs) = rs {
+            if let Ok(value) = rs.get_boolean(&request.name) {
+                let reply = GetBooleanReply {
+                    value,
+                    was_null: rs.was_null(),
+                };
+                return Ok(Response::new(reply));
+            }
+        }
+        Err(Status::internal("failed to get the boolean"))
+    }
+
     async fn close(
         &self,
         request: Request<CloseResultSetRequest>,
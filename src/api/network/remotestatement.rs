@@ -66,12 +66,17 @@ impl Statement for RemoteStatement {
             .lock()
             .unwrap()
             .execute_update(&request.into_inner().command);
-        if let Ok(count) = count {
-            let reply = ExecuteUpdateReply {
-                count: count as u64,
-            };
-            return Ok(Response::new(reply));
+        match count {
+            Ok(count) => {
+                let reply = ExecuteUpdateReply {
+                    count: count as u64,
+                };
+                Ok(Response::new(reply))
+            }
+            Err(e) => Err(Status::internal(format!(
+                "failed to execute the update: {:?}",
+                e
+            ))),
         }
-        Err(Status::internal("failed to execute the update"))
     }
 }
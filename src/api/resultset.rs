@@ -10,6 +10,10 @@ pub trait ResultSetControl {
     fn next(&mut self) -> Result<bool, SQLError>;
     fn get_int(&mut self, fldname: &str) -> Result<i32, SQLError>;
     fn get_string(&mut self, fldname: &str) -> Result<String, SQLError>;
+    fn get_long(&mut self, fldname: &str) -> Result<i64, SQLError>;
+    fn get_double(&mut self, fldname: &str) -> Result<f64, SQLError>;
+    fn get_boolean(&mut self, fldname: &str) -> Result<bool, SQLError>;
+    fn was_null(&self) -> bool;
     fn get_meta_data(&self) -> MetaData;
     fn close(&mut self) -> Result<(), SQLError>;
 }
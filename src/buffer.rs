@@ -1,5 +1,13 @@
+pub mod backgroundwriter;
+pub mod backgroundwritertest;
 pub mod buffer;
 pub mod bufferfiletest;
 pub mod buffermgr;
 pub mod buffermgrtest;
+pub mod bufferstats;
 pub mod buffertest;
+pub mod clockpolicy;
+pub mod lrupolicy;
+pub mod naivepolicy;
+pub mod replacementpolicy;
+pub mod replacementpolicytest;
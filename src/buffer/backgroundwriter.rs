@@ -0,0 +1,50 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use super::buffermgr::BufferMgr;
+
+pub struct BackgroundWriter {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl BackgroundWriter {
+    const MAX_WRITES: usize = 4;
+
+    pub fn new(bm: Arc<Mutex<BufferMgr>>, interval: Duration) -> BackgroundWriter {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let stop_c = stop.clone();
+        let handle = thread::spawn(move || loop {
+            let (lock, cvar) = &*stop_c;
+            let stopped = lock.lock().unwrap();
+            let (stopped, _) = cvar
+                .wait_timeout_while(stopped, interval, |stopped| !*stopped)
+                .unwrap();
+            if *stopped {
+                break;
+            }
+            drop(stopped);
+            if BufferMgr::flush_unpinned(&bm, BackgroundWriter::MAX_WRITES).is_err() {
+                break;
+            }
+        });
+        BackgroundWriter {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for BackgroundWriter {
+    fn drop(&mut self) {
+        let (lock, cvar) = &*self.stop;
+        *lock.lock().unwrap() = true;
+        cvar.notify_all();
+        if let Some(handle) = self.handle.take() {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, thread, time::Duration};
+
+    use crate::{
+        buffer::{buffermgr::BufferMgr, lrupolicy::LruPolicy},
+        file::{blockid::BlockId, durability::Durability, page::Page},
+        server::simpledb::SimpleDB,
+    };
+
+    #[test]
+    fn backgroundwritertest() {
+        let mut db = SimpleDB::with_params("backgroundwritertest", 400, 8, Durability::NoSync)
+            .unwrap()
+            .with_background_writer(Duration::from_millis(10));
+        let fm = db.file_mgr();
+        let lm = db.log_mgr();
+        let bm = db.buffer_mgr();
+        let flushes = lm.lock().unwrap().flush_count();
+
+        let blk = BlockId::new("testfile", 1);
+        let mut tx = db.new_tx().unwrap();
+        tx.pin(&blk).unwrap();
+        tx.set_int(&blk, 80, 42, true).unwrap();
+        tx.unpin(&blk).unwrap();
+
+        let mut waited = 0;
+        while bm.lock().unwrap().stats().dirty() > 0 && waited < 200 {
+            thread::sleep(Duration::from_millis(10));
+            waited += 1;
+        }
+        assert_eq!(0, bm.lock().unwrap().stats().dirty());
+        assert!(lm.lock().unwrap().flush_count() > flushes);
+
+        let mut p = Page::new(fm.page_size());
+        fm.read(&blk, &mut p).unwrap();
+        assert_eq!(42, p.get_int(80));
+        tx.commit().unwrap();
+
+        fs::remove_dir_all("backgroundwritertest").unwrap();
+    }
+
+    #[test]
+    fn coldvictimtest() {
+        let mut db = SimpleDB::with_params("coldvictimtest", 400, 3, Durability::NoSync)
+            .unwrap()
+            .with_replacement_policy(LruPolicy::new().into());
+        let fm = db.file_mgr();
+        let m = db.buffer_mgr();
+
+        let mut bm = m.lock().unwrap();
+        let idx: Vec<usize> = (0..3)
+            .map(|i| {
+                let idx = bm.try_pin(&BlockId::new("testfile", i)).unwrap().unwrap();
+                let buff = bm.buffer(idx);
+                buff.contents().set_int(80, 100 + i);
+                buff.set_modified(1, None);
+                idx
+            })
+            .collect();
+        bm.unpin(idx[1]);
+        bm.unpin(idx[0]);
+        bm.unpin(idx[2]);
+        drop(bm);
+
+        assert_eq!(1, BufferMgr::flush_unpinned(&m, 1).unwrap());
+        let mut bm = m.lock().unwrap();
+        assert_eq!(None, bm.buffer(idx[1]).modifying_tx());
+        assert_eq!(Some(1), bm.buffer(idx[0]).modifying_tx());
+        assert_eq!(2, bm.stats().dirty());
+        drop(bm);
+
+        let mut p = Page::new(fm.page_size());
+        fm.read(&BlockId::new("testfile", 1), &mut p).unwrap();
+        assert_eq!(101, p.get_int(80));
+
+        fs::remove_dir_all("coldvictimtest").unwrap();
+    }
+}
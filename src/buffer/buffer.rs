@@ -16,11 +16,13 @@ pub struct Buffer {
     pins: i32,
     txnum: Option<usize>,
     lsn: Option<usize>,
+    version: usize,
+    io: Arc<Mutex<()>>,
 }
 
 impl Buffer {
     pub fn new(fm: Arc<FileMgr>, lm: Arc<Mutex<LogMgr>>) -> Buffer {
-        let blocksize = fm.block_size();
+        let blocksize = fm.page_size();
         Buffer {
             fm,
             lm,
@@ -29,6 +31,8 @@ impl Buffer {
             pins: 0,
             txnum: None,
             lsn: None,
+            version: 0,
+            io: Arc::new(Mutex::new(())),
         }
     }
 
@@ -42,6 +46,7 @@ impl Buffer {
 
     pub fn set_modified(&mut self, txnum: usize, lsn: Option<usize>) {
         self.txnum = Some(txnum);
+        self.version = self.version.wrapping_add(1);
         if lsn.is_some() {
             self.lsn = lsn;
         }
@@ -55,16 +60,37 @@ impl Buffer {
         self.txnum
     }
 
+    pub(in crate::buffer) fn lsn(&self) -> Option<usize> {
+        self.lsn
+    }
+
+    pub(in crate::buffer) fn version(&self) -> usize {
+        self.version
+    }
+
+    pub(in crate::buffer) fn io(&self) -> Arc<Mutex<()>> {
+        self.io.clone()
+    }
+
+    pub(in crate::buffer) fn written(&mut self, blk: &BlockId, version: usize) {
+        if self.blk.as_ref() == Some(blk) && self.version == version {
+            self.txnum = None;
+        }
+    }
+
     pub(in crate::buffer) fn assign_to_block(&mut self, b: BlockId) -> Result<(), Error> {
         self.flush()?;
-        self.blk = Some(b.clone());
+        self.blk = None;
         self.fm.read(&b, &mut self.contents)?;
+        self.blk = Some(b);
         self.pins = 0;
         Ok(())
     }
 
     pub(in crate::buffer) fn flush(&mut self) -> Result<(), Error> {
         if self.txnum.is_some() {
+            let io = self.io.clone();
+            let _io = io.lock().unwrap();
             if let Some(lsn) = self.lsn {
                 self.lm.lock().unwrap().flush(lsn)?;
             }
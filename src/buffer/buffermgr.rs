@@ -1,21 +1,27 @@
 use std::{
+    collections::HashMap,
     io::Error,
-    sync::{Arc, Mutex},
-    thread::{current, park_timeout},
+    sync::{Arc, Condvar, Mutex},
     time::{Duration, SystemTime, SystemTimeError},
 };
 
 use crate::{
-    file::{blockid::BlockId, filemgr::FileMgr},
+    file::{blockid::BlockId, filemgr::FileMgr, page::Page},
     log::logmgr::LogMgr,
 };
 
-use super::buffer::Buffer;
+use super::{
+    buffer::Buffer,
+    bufferstats::BufferStats,
+    naivepolicy::NaivePolicy,
+    replacementpolicy::{ReplacementPolicy, ReplacementPolicyControl},
+};
 
 #[derive(Debug)]
 pub enum AbortError {
     Time(SystemTimeError),
     IO(Error),
+    Deadlock(usize),
     General,
 }
 
@@ -32,9 +38,19 @@ impl From<Error> for AbortError {
 }
 
 pub struct BufferMgr {
+    fm: Arc<FileMgr>,
+    lm: Arc<Mutex<LogMgr>>,
     bufferpool: Vec<Buffer>,
+    blocks: HashMap<BlockId, usize>,
+    policy: ReplacementPolicy,
     num_available: usize,
     max_time: u128,
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+    pin_waits: usize,
+    pin_timeouts: usize,
+    unpinned: Arc<Condvar>,
 }
 
 pub fn waiting_too_long(starttime: SystemTime, max_time: u128) -> Result<bool, SystemTimeError> {
@@ -51,9 +67,19 @@ impl BufferMgr {
             bufferpool.push(Buffer::new(fm.clone(), lm.clone()));
         }
         BufferMgr {
+            fm,
+            lm,
             bufferpool,
+            blocks: HashMap::new(),
+            policy: NaivePolicy::new().into(),
             num_available,
             max_time: BufferMgr::MAX_TIME,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            pin_waits: 0,
+            pin_timeouts: 0,
+            unpinned: Arc::new(Condvar::new()),
         }
     }
 
@@ -72,25 +98,79 @@ impl BufferMgr {
         Ok(())
     }
 
+    pub fn flush_modified(&mut self) -> Result<(), Error> {
+        for buff in self.bufferpool.iter_mut() {
+            buff.flush()?;
+        }
+        Ok(())
+    }
+
+    pub fn flush_unpinned(bm: &Arc<Mutex<BufferMgr>>, max: usize) -> Result<usize, Error> {
+        let mut written = 0;
+        while written < max {
+            let mut guard = bm.lock().unwrap();
+            let victim = guard
+                .policy
+                .cold_buffers(&guard.bufferpool)
+                .into_iter()
+                .find(|idx| guard.bufferpool[*idx].modifying_tx().is_some());
+            let Some(idx) = victim else {
+                break;
+            };
+            let (fm, lm) = (guard.fm.clone(), guard.lm.clone());
+            let buff = &mut guard.bufferpool[idx];
+            let Some(blk) = buff.block().clone() else {
+                break;
+            };
+            let io = buff.io();
+            let io_guard = io.lock().unwrap();
+            let (lsn, version) = (buff.lsn(), buff.version());
+            let mut page = Page::with_vec(buff.contents().contents().clone());
+            drop(guard);
+            if let Some(lsn) = lsn {
+                lm.lock().unwrap().flush(lsn)?;
+            }
+            fm.write(&blk, &mut page)?;
+            drop(io_guard);
+            bm.lock().unwrap().bufferpool[idx].written(&blk, version);
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    pub fn sync(&self) -> Result<(), Error> {
+        self.fm.sync_all()
+    }
+
     pub fn unpin(&mut self, idx: usize) {
         let buff = &mut self.bufferpool[idx];
         buff.unpin();
         if !buff.is_pinned() {
             self.num_available += 1;
-            current().unpark();
+            self.policy.unpinned(idx);
+            self.unpinned.notify_all();
         }
     }
 
-    pub fn pin(&mut self, blk: &BlockId) -> Result<usize, AbortError> {
+    pub fn pin(bm: &Arc<Mutex<BufferMgr>>, blk: &BlockId) -> Result<usize, AbortError> {
         let timestamp = SystemTime::now();
-        let mut idx = self.try_to_pin(blk)?;
-        while idx.is_none() && !waiting_too_long(timestamp, self.max_time)? {
-            park_timeout(Duration::from_millis(self.max_time as u64));
-            idx = self.try_to_pin(blk)?;
-        }
-        match idx {
-            Some(idx) => Ok(idx),
-            None => Err(AbortError::General),
+        let mut guard = bm.lock().unwrap();
+        let unpinned = guard.unpinned.clone();
+        let mut waited = false;
+        loop {
+            if let Some(idx) = guard.try_pin(blk)? {
+                return Ok(idx);
+            }
+            if waiting_too_long(timestamp, guard.max_time)? {
+                guard.pin_timeouts += 1;
+                return Err(AbortError::General);
+            }
+            if !waited {
+                guard.pin_waits += 1;
+                waited = true;
+            }
+            let timeout = Duration::from_millis(guard.max_time as u64);
+            guard = unpinned.wait_timeout(guard, timeout).unwrap().0;
         }
     }
 
@@ -98,44 +178,72 @@ impl BufferMgr {
         &mut self.bufferpool[idx]
     }
 
-    fn try_to_pin(&mut self, blk: &BlockId) -> Result<Option<usize>, Error> {
-        let mut idx = self.find_existing_buffer(blk);
-        if idx.is_none() {
-            idx = self.choose_unpinned_buffer();
-            if let Some(i) = idx {
-                self.bufferpool[i].assign_to_block(blk.clone())?;
-            } else {
-                return Ok(None);
-            }
-        }
-        if let Some(i) = idx {
-            if !self.bufferpool[i].is_pinned() {
-                self.num_available -= 1;
+    pub fn try_pin(&mut self, blk: &BlockId) -> Result<Option<usize>, Error> {
+        let idx = match self.blocks.get(blk) {
+            Some(i) => {
+                self.hits += 1;
+                *i
             }
-            self.bufferpool[i].pin();
+            None => match self.policy.choose_unpinned_buffer(&self.bufferpool) {
+                Some(i) => {
+                    let old = self.bufferpool[i].block().clone();
+                    let assigned = self.bufferpool[i].assign_to_block(blk.clone());
+                    if let Some(old) = old {
+                        if self.bufferpool[i].block().as_ref() != Some(&old) {
+                            self.blocks.remove(&old);
+                            self.evictions += 1;
+                        }
+                    }
+                    assigned?;
+                    self.blocks.insert(blk.clone(), i);
+                    self.misses += 1;
+                    i
+                }
+                None => return Ok(None),
+            },
+        };
+        if !self.bufferpool[idx].is_pinned() {
+            self.num_available -= 1;
         }
-        Ok(idx)
+        self.bufferpool[idx].pin();
+        self.policy.pinned(idx);
+        Ok(Some(idx))
     }
 
-    fn find_existing_buffer(&mut self, blk: &BlockId) -> Option<usize> {
-        for (i, buffer) in self.bufferpool.iter().enumerate() {
-            let o = buffer.block();
-            if let Some(b) = o {
-                if b == blk {
-                    return Some(i);
-                }
-            }
-        }
-        None
+    #[allow(dead_code)]
+    pub fn hits(&self) -> usize {
+        self.hits
     }
 
-    fn choose_unpinned_buffer(&mut self) -> Option<usize> {
-        for (i, buffer) in self.bufferpool.iter().enumerate() {
-            if !buffer.is_pinned() {
-                return Some(i);
-            }
+    #[allow(dead_code)]
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn stats(&self) -> BufferStats {
+        let mut resident = HashMap::new();
+        for blk in self.blocks.keys() {
+            *resident.entry(blk.file_name().to_string()).or_insert(0) += 1;
         }
-        None
+        let dirty = self
+            .bufferpool
+            .iter()
+            .filter(|buff| buff.modifying_tx().is_some())
+            .count();
+        BufferStats::new(
+            self.hits,
+            self.misses,
+            self.evictions,
+            dirty,
+            self.pin_waits,
+            self.pin_timeouts,
+            resident,
+        )
+    }
+
+    #[allow(dead_code)]
+    pub fn set_replacement_policy(&mut self, policy: ReplacementPolicy) {
+        self.policy = policy;
     }
 
     #[allow(dead_code)]
@@ -1,31 +1,38 @@
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, fs};
+    use std::{
+        collections::HashMap,
+        fs, thread,
+        time::{Duration, SystemTime},
+    };
 
-    use crate::{file::blockid::BlockId, server::simpledb::SimpleDB};
+    use crate::{
+        buffer::buffermgr::BufferMgr,
+        file::{blockid::BlockId, durability::Durability},
+        server::simpledb::SimpleDB,
+    };
 
     #[test]
     fn buffermgrtest() {
-        let mut db = SimpleDB::with_params("buffermgrtest", 400, 3).unwrap();
+        let mut db = SimpleDB::with_params("buffermgrtest", 400, 3, Durability::NoSync).unwrap();
         let m = db.buffer_mgr();
         let mut bm = m.lock().unwrap();
-        bm.set_max_time(1);
 
         let mut buff = Vec::with_capacity(6);
-        buff.push(bm.pin(&BlockId::new("testfile", 0)).unwrap());
-        buff.push(bm.pin(&BlockId::new("testfile", 1)).unwrap());
-        buff.push(bm.pin(&BlockId::new("testfile", 2)).unwrap());
+        buff.push(bm.try_pin(&BlockId::new("testfile", 0)).unwrap().unwrap());
+        buff.push(bm.try_pin(&BlockId::new("testfile", 1)).unwrap().unwrap());
+        buff.push(bm.try_pin(&BlockId::new("testfile", 2)).unwrap().unwrap());
         bm.unpin(buff[1]);
         buff[1] = 10;
-        buff.push(bm.pin(&BlockId::new("testfile", 0)).unwrap());
-        buff.push(bm.pin(&BlockId::new("testfile", 1)).unwrap());
+        buff.push(bm.try_pin(&BlockId::new("testfile", 0)).unwrap().unwrap());
+        buff.push(bm.try_pin(&BlockId::new("testfile", 1)).unwrap().unwrap());
         assert_eq!(0, bm.available());
 
-        assert!(bm.pin(&BlockId::new("testfile", 3)).is_err());
+        assert!(bm.try_pin(&BlockId::new("testfile", 3)).unwrap().is_none());
 
         bm.unpin(buff[2]);
         buff[2] = 10;
-        buff.push(bm.pin(&BlockId::new("testfile", 3)).unwrap());
+        buff.push(bm.try_pin(&BlockId::new("testfile", 3)).unwrap().unwrap());
 
         let exp = HashMap::from([
             (0, BlockId::new("testfile", 0)),
@@ -44,4 +51,64 @@ mod tests {
 
         fs::remove_dir_all("buffermgrtest").unwrap();
     }
+
+    #[test]
+    fn pinwaittest() {
+        let mut db = SimpleDB::with_params("pinwaittest", 400, 1, Durability::NoSync).unwrap();
+        let m = db.buffer_mgr();
+        let idx = BufferMgr::pin(&m, &BlockId::new("testfile", 0)).unwrap();
+
+        let m_b = m.clone();
+        let handler = thread::spawn(move || {
+            let timestamp = SystemTime::now();
+            let idx = BufferMgr::pin(&m_b, &BlockId::new("testfile", 1)).unwrap();
+            (idx, timestamp.elapsed().unwrap())
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        m.lock().unwrap().unpin(idx);
+        let (idx, elapsed) = handler.join().unwrap();
+        assert!(elapsed < Duration::from_secs(1));
+        let mut bm = m.lock().unwrap();
+        assert_eq!(
+            &BlockId::new("testfile", 1),
+            bm.buffer(idx).block().as_ref().unwrap()
+        );
+        let stats = bm.stats();
+        assert_eq!(1, stats.pin_waits());
+        assert_eq!(0, stats.pin_timeouts());
+        assert_eq!(1, stats.evictions());
+        drop(bm);
+
+        fs::remove_dir_all("pinwaittest").unwrap();
+    }
+
+    #[test]
+    fn evictfailtest() {
+        let mut db = SimpleDB::with_params("evictfailtest", 400, 1, Durability::NoSync).unwrap();
+        let m = db.buffer_mgr();
+        let mut bm = m.lock().unwrap();
+
+        let idx = bm.try_pin(&BlockId::new("testfile", 0)).unwrap().unwrap();
+        let buff = bm.buffer(idx);
+        buff.contents().set_int(80, 1234);
+        buff.set_modified(1, None);
+        bm.unpin(idx);
+
+        fs::remove_file("evictfailtest/testfile").unwrap();
+        fs::create_dir("evictfailtest/testfile").unwrap();
+        assert!(bm.try_pin(&BlockId::new("otherfile", 0)).is_err());
+        let stats = bm.stats();
+        assert_eq!(Some(&1), stats.resident().get("testfile"));
+        assert_eq!(None, stats.resident().get("otherfile"));
+        assert_eq!(0, stats.evictions());
+
+        fs::remove_dir("evictfailtest/testfile").unwrap();
+        let idx = bm.try_pin(&BlockId::new("testfile", 0)).unwrap().unwrap();
+        assert_eq!(1234, bm.buffer(idx).contents().get_int(80));
+        assert_eq!(1, bm.hits());
+        drop(bm);
+
+        fs::remove_dir_all("evictfailtest").unwrap();
+    }
 }
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+    dirty: usize,
+    pin_waits: usize,
+    pin_timeouts: usize,
+    resident: HashMap<String, usize>,
+}
+
+impl BufferStats {
+    pub fn new(
+        hits: usize,
+        misses: usize,
+        evictions: usize,
+        dirty: usize,
+        pin_waits: usize,
+        pin_timeouts: usize,
+        resident: HashMap<String, usize>,
+    ) -> BufferStats {
+        BufferStats {
+            hits,
+            misses,
+            evictions,
+            dirty,
+            pin_waits,
+            pin_timeouts,
+            resident,
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn evictions(&self) -> usize {
+        self.evictions
+    }
+
+    pub fn dirty(&self) -> usize {
+        self.dirty
+    }
+
+    pub fn pin_waits(&self) -> usize {
+        self.pin_waits
+    }
+
+    pub fn pin_timeouts(&self) -> usize {
+        self.pin_timeouts
+    }
+
+    pub fn resident(&self) -> &HashMap<String, usize> {
+        &self.resident
+    }
+}
@@ -2,15 +2,18 @@
 mod tests {
     use std::fs;
 
-    use crate::{file::blockid::BlockId, server::simpledb::SimpleDB};
+    use crate::{
+        file::{blockid::BlockId, durability::Durability},
+        server::simpledb::SimpleDB,
+    };
 
     #[test]
     fn buffertest() {
-        let mut db = SimpleDB::with_params("buffertest", 400, 3).unwrap();
+        let mut db = SimpleDB::with_params("buffertest", 400, 3, Durability::NoSync).unwrap();
         let m = db.buffer_mgr();
         let mut bm = m.lock().unwrap();
 
-        let idx1 = bm.pin(&BlockId::new("testfile", 1)).unwrap();
+        let idx1 = bm.try_pin(&BlockId::new("testfile", 1)).unwrap().unwrap();
         let buff1 = bm.buffer(idx1);
         let p = buff1.contents();
         let n = p.get_int(80);
@@ -19,12 +22,12 @@ mod tests {
         assert_eq!(1, n + 1);
         bm.unpin(idx1);
 
-        let mut idx2 = bm.pin(&BlockId::new("testfile", 2)).unwrap();
-        bm.pin(&BlockId::new("testfile", 3)).unwrap();
-        bm.pin(&BlockId::new("testfile", 4)).unwrap();
+        let mut idx2 = bm.try_pin(&BlockId::new("testfile", 2)).unwrap().unwrap();
+        bm.try_pin(&BlockId::new("testfile", 3)).unwrap().unwrap();
+        bm.try_pin(&BlockId::new("testfile", 4)).unwrap().unwrap();
 
         bm.unpin(idx2);
-        idx2 = bm.pin(&BlockId::new("testfile", 1)).unwrap();
+        idx2 = bm.try_pin(&BlockId::new("testfile", 1)).unwrap().unwrap();
         let buff2 = bm.buffer(idx2);
         let p2 = buff2.contents();
         p2.set_int(80, 9999);
@@ -0,0 +1,52 @@
+use super::{buffer::Buffer, replacementpolicy::ReplacementPolicyControl};
+
+pub struct ClockPolicy {
+    hand: usize,
+    referenced: Vec<bool>,
+}
+
+#[allow(dead_code)]
+impl ClockPolicy {
+    pub fn new() -> ClockPolicy {
+        ClockPolicy {
+            hand: 0,
+            referenced: Vec::new(),
+        }
+    }
+}
+
+impl ReplacementPolicyControl for ClockPolicy {
+    fn pinned(&mut self, idx: usize) {
+        if self.referenced.len() <= idx {
+            self.referenced.resize(idx + 1, false);
+        }
+        self.referenced[idx] = true;
+    }
+
+    fn unpinned(&mut self, _: usize) {}
+
+    fn choose_unpinned_buffer(&mut self, bufferpool: &[Buffer]) -> Option<usize> {
+        self.referenced.resize(bufferpool.len(), false);
+        for _ in 0..2 * bufferpool.len() {
+            let idx = self.hand;
+            self.hand = (self.hand + 1) % bufferpool.len();
+            if bufferpool[idx].is_pinned() {
+                continue;
+            }
+            if !self.referenced[idx] {
+                return Some(idx);
+            }
+            self.referenced[idx] = false;
+        }
+        None
+    }
+
+    fn cold_buffers(&self, bufferpool: &[Buffer]) -> Vec<usize> {
+        let mut cold: Vec<usize> = (0..bufferpool.len())
+            .map(|i| (self.hand + i) % bufferpool.len())
+            .filter(|i| !bufferpool[*i].is_pinned())
+            .collect();
+        cold.sort_by_key(|i| self.referenced.get(*i).copied().unwrap_or(false));
+        cold
+    }
+}
@@ -0,0 +1,45 @@
+use super::{buffer::Buffer, replacementpolicy::ReplacementPolicyControl};
+
+pub struct LruPolicy {
+    clock: usize,
+    last_unpinned: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl LruPolicy {
+    pub fn new() -> LruPolicy {
+        LruPolicy {
+            clock: 0,
+            last_unpinned: Vec::new(),
+        }
+    }
+}
+
+impl ReplacementPolicyControl for LruPolicy {
+    fn pinned(&mut self, _: usize) {}
+
+    fn unpinned(&mut self, idx: usize) {
+        if self.last_unpinned.len() <= idx {
+            self.last_unpinned.resize(idx + 1, 0);
+        }
+        self.clock += 1;
+        self.last_unpinned[idx] = self.clock;
+    }
+
+    fn choose_unpinned_buffer(&mut self, bufferpool: &[Buffer]) -> Option<usize> {
+        bufferpool
+            .iter()
+            .enumerate()
+            .filter(|(_, buff)| !buff.is_pinned())
+            .min_by_key(|(i, _)| self.last_unpinned.get(*i).copied().unwrap_or(0))
+            .map(|(i, _)| i)
+    }
+
+    fn cold_buffers(&self, bufferpool: &[Buffer]) -> Vec<usize> {
+        let mut cold: Vec<usize> = (0..bufferpool.len())
+            .filter(|i| !bufferpool[*i].is_pinned())
+            .collect();
+        cold.sort_by_key(|i| self.last_unpinned.get(*i).copied().unwrap_or(0));
+        cold
+    }
+}
@@ -0,0 +1,25 @@
+use super::{buffer::Buffer, replacementpolicy::ReplacementPolicyControl};
+
+pub struct NaivePolicy {}
+
+impl NaivePolicy {
+    pub fn new() -> NaivePolicy {
+        NaivePolicy {}
+    }
+}
+
+impl ReplacementPolicyControl for NaivePolicy {
+    fn pinned(&mut self, _: usize) {}
+
+    fn unpinned(&mut self, _: usize) {}
+
+    fn choose_unpinned_buffer(&mut self, bufferpool: &[Buffer]) -> Option<usize> {
+        bufferpool.iter().position(|buff| !buff.is_pinned())
+    }
+
+    fn cold_buffers(&self, bufferpool: &[Buffer]) -> Vec<usize> {
+        (0..bufferpool.len())
+            .filter(|i| !bufferpool[*i].is_pinned())
+            .collect()
+    }
+}
@@ -0,0 +1,21 @@
+use enum_dispatch::enum_dispatch;
+
+use super::{
+    buffer::Buffer, clockpolicy::ClockPolicy, lrupolicy::LruPolicy, naivepolicy::NaivePolicy,
+};
+
+#[enum_dispatch(ReplacementPolicy)]
+pub trait ReplacementPolicyControl {
+    fn pinned(&mut self, idx: usize);
+    fn unpinned(&mut self, idx: usize);
+    fn choose_unpinned_buffer(&mut self, bufferpool: &[Buffer]) -> Option<usize>;
+    fn cold_buffers(&self, bufferpool: &[Buffer]) -> Vec<usize>;
+}
+
+#[allow(dead_code)]
+#[enum_dispatch]
+pub enum ReplacementPolicy {
+    Naive(NaivePolicy),
+    Lru(LruPolicy),
+    Clock(ClockPolicy),
+}
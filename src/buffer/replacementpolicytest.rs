@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        buffer::{
+            buffermgr::BufferMgr, clockpolicy::ClockPolicy, lrupolicy::LruPolicy,
+            naivepolicy::NaivePolicy, replacementpolicy::ReplacementPolicy,
+        },
+        file::{blockid::BlockId, durability::Durability},
+        server::simpledb::SimpleDB,
+    };
+
+    #[test]
+    fn naivepolicytest() {
+        let evicted = evicted_blocks("naivepolicytest", NaivePolicy::new().into());
+        assert_eq!(vec![0, 3], evicted);
+    }
+
+    #[test]
+    fn lrupolicytest() {
+        let evicted = evicted_blocks("lrupolicytest", LruPolicy::new().into());
+        assert_eq!(vec![1, 0], evicted);
+    }
+
+    #[test]
+    fn clockpolicytest() {
+        let evicted = evicted_blocks("clockpolicytest", ClockPolicy::new().into());
+        assert_eq!(vec![0, 1], evicted);
+    }
+
+    #[test]
+    fn hitmisstest() {
+        let mut db = SimpleDB::with_params("hitmisstest", 400, 3, Durability::NoSync)
+            .unwrap()
+            .with_replacement_policy(LruPolicy::new().into());
+        let m = db.buffer_mgr();
+        let mut bm = m.lock().unwrap();
+        for i in 0..3 {
+            let idx = bm.try_pin(&BlockId::new("testfile", i)).unwrap().unwrap();
+            bm.unpin(idx);
+        }
+        assert_eq!((0, 3), (bm.hits(), bm.misses()));
+        for i in 0..3 {
+            let idx = bm.try_pin(&BlockId::new("testfile", i)).unwrap().unwrap();
+            bm.unpin(idx);
+        }
+        assert_eq!((3, 3), (bm.hits(), bm.misses()));
+        let idx = bm.try_pin(&BlockId::new("testfile", 3)).unwrap().unwrap();
+        bm.unpin(idx);
+        let idx = bm.try_pin(&BlockId::new("testfile", 0)).unwrap().unwrap();
+        bm.unpin(idx);
+        assert_eq!((3, 5), (bm.hits(), bm.misses()));
+        drop(bm);
+
+        fs::remove_dir_all("hitmisstest").unwrap();
+    }
+
+    fn evicted_blocks(dirname: &str, policy: ReplacementPolicy) -> Vec<i32> {
+        let mut db = SimpleDB::with_params(dirname, 400, 3, Durability::NoSync)
+            .unwrap()
+            .with_replacement_policy(policy);
+        let m = db.buffer_mgr();
+        let mut bm = m.lock().unwrap();
+
+        let idx: Vec<usize> = (0..3)
+            .map(|i| bm.try_pin(&BlockId::new("testfile", i)).unwrap().unwrap())
+            .collect();
+        bm.unpin(idx[1]);
+        bm.unpin(idx[0]);
+        bm.unpin(idx[2]);
+
+        let mut evicted = vec![pin_new_block(&mut bm, 3)];
+        let idx = bm.try_pin(&BlockId::new("testfile", 2)).unwrap().unwrap();
+        bm.unpin(idx);
+        evicted.push(pin_new_block(&mut bm, 4));
+        drop(bm);
+
+        fs::remove_dir_all(dirname).unwrap();
+        evicted
+    }
+
+    fn pin_new_block(bm: &mut BufferMgr, blknum: i32) -> i32 {
+        let old: Vec<Option<BlockId>> = (0..3).map(|i| bm.buffer(i).block().clone()).collect();
+        let idx = bm
+            .try_pin(&BlockId::new("testfile", blknum))
+            .unwrap()
+            .unwrap();
+        bm.unpin(idx);
+        old[idx].as_ref().unwrap().number()
+    }
+}
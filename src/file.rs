@@ -1,4 +1,6 @@
 pub mod blockid;
+pub mod corruptpageerror;
+pub mod durability;
 pub mod filemgr;
 pub mod filetest;
 pub mod page;
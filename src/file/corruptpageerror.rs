@@ -0,0 +1,30 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+use super::blockid::BlockId;
+
+#[derive(Debug)]
+pub struct CorruptPageError {
+    blk: BlockId,
+}
+
+impl Display for CorruptPageError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "checksum mismatch in {}", self.blk)
+    }
+}
+
+impl Error for CorruptPageError {}
+
+impl CorruptPageError {
+    pub fn new(blk: BlockId) -> CorruptPageError {
+        CorruptPageError { blk }
+    }
+
+    #[allow(dead_code)]
+    pub fn block(&self) -> &BlockId {
+        &self.blk
+    }
+}
@@ -0,0 +1,6 @@
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Durability {
+    Fsync,
+    NoSync,
+}
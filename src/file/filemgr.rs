@@ -1,24 +1,58 @@
+use std::collections::HashSet;
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, SeekFrom, Write};
 use std::path::Path;
-use std::{io::Error, io::Seek};
+use std::sync::Mutex;
+use std::{io::Error, io::ErrorKind, io::Seek};
 
 use super::blockid::BlockId;
+use super::corruptpageerror::CorruptPageError;
+use super::durability::Durability;
 use super::page::Page;
 
 pub struct FileMgr {
     db_directory: String,
     blocksize: usize,
     is_new: bool,
+    durability: Durability,
+    unsynced: Mutex<HashSet<String>>,
+    reserved_tx_num: Mutex<usize>,
+}
+
+fn checksum(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for b in bytes {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
 }
 
 impl FileMgr {
-    pub fn new(db_directory: &str, blocksize: usize) -> Result<FileMgr, Error> {
+    const HEADER_SIZE: usize = 4;
+    const INFO_FILE: &'static str = "simpledb.info";
+    const FORMAT_VERSION: usize = 4;
+    const TX_NUM_RESERVE: usize = 1000;
+
+    pub fn new(
+        db_directory: &str,
+        blocksize: usize,
+        durability: Durability,
+    ) -> Result<FileMgr, Error> {
         let is_new = !Path::new(db_directory).exists();
         let fm = FileMgr {
             db_directory: db_directory.to_string(),
             blocksize,
             is_new,
+            durability,
+            unsynced: Mutex::new(HashSet::new()),
+            reserved_tx_num: Mutex::new(0),
         };
         if fm.is_new {
             fs::create_dir_all(db_directory)?;
@@ -30,38 +64,68 @@ impl FileMgr {
                 fs::remove_file(path)?;
             }
         }
+        fm.check_info()?;
         Ok(fm)
     }
 
     pub fn read(&self, blk: &BlockId, p: &mut Page) -> Result<(), Error> {
         let mut f = self.get_file(blk.file_name())?;
-        let pos = (blk.number() as usize * self.blocksize) as u64;
+        let pos = self.position(blk);
+        let mut b = vec![0; self.blocksize];
+        if f.metadata()?.len() < pos + b.len() as u64 {
+            p.contents().fill(0);
+            return Ok(());
+        }
         f.seek(SeekFrom::Start(pos))?;
-        if f.metadata()?.len() >= pos + p.contents().len() as u64 {
-            f.read_exact(p.contents())?;
+        f.read_exact(&mut b)?;
+        let mut header = [0; FileMgr::HEADER_SIZE];
+        header.copy_from_slice(&b[..FileMgr::HEADER_SIZE]);
+        let contents = &b[FileMgr::HEADER_SIZE..];
+        let unwritten = u32::from_be_bytes(header) == 0 && contents.iter().all(|b| *b == 0);
+        if !unwritten && u32::from_be_bytes(header) != checksum(contents) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                CorruptPageError::new(blk.clone()),
+            ));
         }
+        p.contents().copy_from_slice(contents);
         Ok(())
     }
 
     pub fn write(&self, blk: &BlockId, p: &mut Page) -> Result<(), Error> {
         let mut f = self.get_file(blk.file_name())?;
-        f.seek(SeekFrom::Start(
-            (blk.number() as usize * self.blocksize) as u64,
-        ))?;
-        f.write_all(p.contents())?;
+        f.seek(SeekFrom::Start(self.position(blk)))?;
+        f.write_all(&FileMgr::block_image(p.contents()))?;
+        self.mark_unsynced(blk.file_name());
+        Ok(())
+    }
+
+    pub fn sync(&self, filename: &str) -> Result<(), Error> {
+        if self.durability == Durability::NoSync {
+            return Ok(());
+        }
+        self.get_file(filename)?.sync_data()?;
+        self.unsynced.lock().unwrap().remove(filename);
+        Ok(())
+    }
+
+    pub fn sync_all(&self) -> Result<(), Error> {
+        let filenames: Vec<String> = self.unsynced.lock().unwrap().drain().collect();
+        for filename in filenames {
+            self.get_file(&filename)?.sync_data()?;
+        }
         Ok(())
     }
 
     pub fn append(&self, filename: &str) -> Result<BlockId, Error> {
         let newblknum = self.length(filename)?;
         let blk = BlockId::new(filename, newblknum as i32);
-        let b: Vec<u8> = vec![0; self.blocksize];
+        let b: Vec<u8> = vec![0; self.page_size()];
 
         let mut f = self.get_file(blk.file_name())?;
-        f.seek(SeekFrom::Start(
-            (blk.number() as usize * self.blocksize) as u64,
-        ))?;
-        f.write_all(&b)?;
+        f.seek(SeekFrom::Start(self.position(&blk)))?;
+        f.write_all(&FileMgr::block_image(&b))?;
+        self.mark_unsynced(blk.file_name());
 
         Ok(blk)
     }
@@ -72,6 +136,44 @@ impl FileMgr {
         Ok(metadata.len() as usize / self.blocksize)
     }
 
+    pub fn file_names(&self) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&self.db_directory)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                if let Some(name) = entry.file_name().to_str() {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    pub fn remove(&self, filename: &str) -> Result<(), Error> {
+        self.unsynced.lock().unwrap().remove(filename);
+        fs::remove_file(Path::new(&self.db_directory).join(filename))
+    }
+
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), Error> {
+        let dir = Path::new(&self.db_directory);
+        self.unsynced.lock().unwrap().remove(from);
+        fs::rename(dir.join(from), dir.join(to))?;
+        self.sync_dir(dir)
+    }
+
+    pub fn archive(&self, filename: &str, dirname: &str) -> Result<(), Error> {
+        let dir = Path::new(dirname);
+        fs::create_dir_all(dir)?;
+        let tmp = dir.join(format!("{}.tmp", filename));
+        fs::copy(Path::new(&self.db_directory).join(filename), &tmp)?;
+        if self.durability == Durability::Fsync {
+            File::open(&tmp)?.sync_all()?;
+        }
+        fs::rename(tmp, dir.join(filename))?;
+        self.sync_dir(dir)?;
+        self.remove(filename)
+    }
+
     pub fn is_new(&self) -> bool {
         self.is_new
     }
@@ -80,6 +182,117 @@ impl FileMgr {
         self.blocksize
     }
 
+    pub fn page_size(&self) -> usize {
+        self.blocksize - FileMgr::HEADER_SIZE
+    }
+
+    pub fn reserved_tx_num(&self) -> usize {
+        *self.reserved_tx_num.lock().unwrap()
+    }
+
+    pub fn reserve_tx_num(&self, txnum: usize) -> Result<(), Error> {
+        let mut reserved = self.reserved_tx_num.lock().unwrap();
+        if txnum >= *reserved {
+            self.write_info(txnum + FileMgr::TX_NUM_RESERVE)?;
+            *reserved = txnum + FileMgr::TX_NUM_RESERVE;
+        }
+        Ok(())
+    }
+
+    fn position(&self, blk: &BlockId) -> u64 {
+        (blk.number() as usize * self.blocksize) as u64
+    }
+
+    fn block_image(contents: &[u8]) -> Vec<u8> {
+        let mut b = Vec::with_capacity(FileMgr::HEADER_SIZE + contents.len());
+        b.extend_from_slice(&checksum(contents).to_be_bytes());
+        b.extend_from_slice(contents);
+        b
+    }
+
+    fn check_info(&self) -> Result<(), Error> {
+        let path = Path::new(&self.db_directory).join(FileMgr::INFO_FILE);
+        if !path.exists() {
+            if self.file_names()?.iter().any(|name| name.ends_with(".tbl")) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("missing {}", FileMgr::INFO_FILE),
+                ));
+            }
+            return self.write_info(0);
+        }
+        let info = fs::read_to_string(path)?;
+        let saved = |name: &str| {
+            info.lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(key, _)| key.trim() == name)
+                .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        };
+        match saved("block_size") {
+            Some(saved) if saved == self.blocksize => {}
+            Some(saved) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "block size mismatch: database uses {}, configured {}",
+                        saved, self.blocksize
+                    ),
+                ))
+            }
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("missing block size in {}", FileMgr::INFO_FILE),
+                ))
+            }
+        }
+        match saved("format_version") {
+            Some(FileMgr::FORMAT_VERSION) => {}
+            saved => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "unsupported format version: database uses {}, expected {}",
+                        saved.unwrap_or(1),
+                        FileMgr::FORMAT_VERSION
+                    ),
+                ))
+            }
+        }
+        *self.reserved_tx_num.lock().unwrap() = saved("tx_reserved").unwrap_or(0);
+        Ok(())
+    }
+
+    fn write_info(&self, reserved_tx_num: usize) -> Result<(), Error> {
+        let dir = Path::new(&self.db_directory);
+        let tmp = dir.join(format!("{}.tmp", FileMgr::INFO_FILE));
+        let mut f = File::create(&tmp)?;
+        write!(
+            f,
+            "block_size = {}\nformat_version = {}\ntx_reserved = {}\n",
+            self.blocksize,
+            FileMgr::FORMAT_VERSION,
+            reserved_tx_num
+        )?;
+        if self.durability == Durability::Fsync {
+            f.sync_all()?;
+        }
+        fs::rename(tmp, dir.join(FileMgr::INFO_FILE))
+    }
+
+    fn sync_dir(&self, dir: &Path) -> Result<(), Error> {
+        if self.durability == Durability::Fsync {
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    fn mark_unsynced(&self, filename: &str) {
+        if self.durability == Durability::Fsync && !filename.starts_with("temp") {
+            self.unsynced.lock().unwrap().insert(filename.to_string());
+        }
+    }
+
     fn get_file(&self, filename: &str) -> Result<File, Error> {
         let filename = Path::new(&self.db_directory).join(filename);
         let file = OpenOptions::new()
@@ -1,27 +1,33 @@
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::{
+        fs::{self, OpenOptions},
+        io::{Seek, SeekFrom, Write},
+    };
 
     use crate::{
-        file::{blockid::BlockId, page::Page},
+        file::{
+            blockid::BlockId, corruptpageerror::CorruptPageError, durability::Durability,
+            page::Page,
+        },
         server::simpledb::SimpleDB,
     };
 
     #[test]
     fn filetest() {
-        let db = SimpleDB::with_params("filetest", 400, 8).unwrap();
+        let db = SimpleDB::with_params("filetest", 400, 8, Durability::NoSync).unwrap();
         let fm = db.file_mgr();
 
         let blk = BlockId::new("testfile", 2);
-        let mut p1 = Page::new(fm.block_size());
+        let mut p1 = Page::new(fm.page_size());
         let pos1 = 88;
         p1.set_string(pos1, "abcdefghijklm");
-        let size = Page::max_length("abcdefghijklm".len());
+        let size = Page::bytes_length("abcdefghijklm".len());
         let pos2 = pos1 + size;
         p1.set_int(pos2, 345);
         fm.write(&blk, &mut p1).unwrap();
 
-        let mut p2 = Page::new(fm.block_size());
+        let mut p2 = Page::new(fm.page_size());
         fm.read(&blk, &mut p2).unwrap();
 
         assert_eq!(105, pos2);
@@ -31,4 +37,63 @@ mod tests {
 
         fs::remove_dir_all("filetest").unwrap();
     }
+
+    #[test]
+    fn filesynctest() {
+        let db = SimpleDB::with_params("filesynctest", 400, 8, Durability::Fsync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+        let mut tx = db.new_tx().unwrap();
+        tx.pin(&blk).unwrap();
+        tx.set_int(&blk, 80, 7, true).unwrap();
+        tx.set_string(&blk, 40, "seven", true).unwrap();
+        tx.commit().unwrap();
+        db.checkpoint().unwrap();
+        drop(db);
+
+        let db = SimpleDB::with_params("filesynctest", 400, 8, Durability::Fsync).unwrap();
+        let fm = db.file_mgr();
+        let mut p = Page::new(fm.page_size());
+        fm.read(&blk, &mut p).unwrap();
+        assert_eq!(7, p.get_int(80));
+        assert_eq!("seven", p.get_string(40).unwrap());
+        fm.sync_all().unwrap();
+
+        fs::remove_dir_all("filesynctest").unwrap();
+    }
+
+    #[test]
+    fn checksumtest() {
+        let db = SimpleDB::with_params("checksumtest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+
+        let blk = BlockId::new("testfile", 1);
+        let mut p1 = Page::new(fm.page_size());
+        p1.set_string(88, "abcdefghijklm");
+        fm.write(&blk, &mut p1).unwrap();
+
+        let mut p2 = Page::new(fm.page_size());
+        fm.read(&BlockId::new("testfile", 0), &mut p2).unwrap();
+        assert_eq!(0, p2.get_int(88));
+        fm.read(&blk, &mut p2).unwrap();
+        assert_eq!("abcdefghijklm", p2.get_string(88).unwrap());
+
+        let mut f = OpenOptions::new()
+            .write(true)
+            .open("checksumtest/testfile")
+            .unwrap();
+        let len = f.metadata().unwrap().len();
+        assert_eq!(2 * 400, len);
+        f.seek(SeekFrom::Start(len - 100)).unwrap();
+        f.write_all(&[0xff; 50]).unwrap();
+        drop(f);
+
+        let e = fm.read(&blk, &mut p2).unwrap_err();
+        let corrupt = e
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<CorruptPageError>())
+            .unwrap();
+        assert_eq!(&blk, corrupt.block());
+
+        fs::remove_dir_all("checksumtest").unwrap();
+    }
 }
@@ -25,6 +25,40 @@ impl Page {
         self.bb.as_mut_slice()[offset..offset + 4].copy_from_slice(&n.to_be_bytes());
     }
 
+    pub fn get_long(&self, offset: usize) -> i64 {
+        let mut buf: [u8; 8] = [0; 8];
+        buf.copy_from_slice(&self.bb[offset..offset + 8]);
+        i64::from_be_bytes(buf)
+    }
+
+    pub fn set_long(&mut self, offset: usize, n: i64) {
+        self.bb.as_mut_slice()[offset..offset + 8].copy_from_slice(&n.to_be_bytes());
+    }
+
+    pub fn get_double(&self, offset: usize) -> f64 {
+        f64::from_bits(self.get_long(offset) as u64)
+    }
+
+    pub fn set_double(&mut self, offset: usize, d: f64) {
+        self.set_long(offset, d.to_bits() as i64)
+    }
+
+    pub fn get_bool(&self, offset: usize) -> bool {
+        self.bb[offset] != 0
+    }
+
+    pub fn set_bool(&mut self, offset: usize, b: bool) {
+        self.bb[offset] = b as u8;
+    }
+
+    pub fn get_raw(&self, offset: usize, len: usize) -> &[u8] {
+        &self.bb[offset..offset + len]
+    }
+
+    pub fn set_raw(&mut self, offset: usize, b: &[u8]) {
+        self.bb.as_mut_slice()[offset..offset + b.len()].copy_from_slice(b);
+    }
+
     pub fn get_bytes(&self, offset: usize) -> &[u8] {
         let len = self.get_int(offset) as usize;
         &self.bb[offset + 4..offset + 4 + len]
@@ -46,11 +80,15 @@ impl Page {
     }
 
     pub fn max_length(strlen: usize) -> usize {
-        let bytes_per_char = 1;
-        4 + strlen * bytes_per_char
+        let bytes_per_char = 4;
+        Page::bytes_length(strlen * bytes_per_char)
+    }
+
+    pub fn bytes_length(len: usize) -> usize {
+        4 + len
     }
 
-    pub(in crate) fn contents(&mut self) -> &mut Vec<u8> {
+    pub(crate) fn contents(&mut self) -> &mut Vec<u8> {
         &mut self.bb
     }
 }
@@ -1,6 +1,7 @@
 pub mod btree;
 pub mod hash;
 pub mod index;
+pub mod indexkeytest;
 pub mod indexretrievaltest;
 pub mod indexupdatetest;
 pub mod planner;
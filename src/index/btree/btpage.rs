@@ -45,7 +45,7 @@ impl BTPage {
     }
 
     pub fn is_full(&self) -> Result<bool, TransactionError> {
-        Ok(self.slotpos(self.get_num_recs()? + 1) >= self.tx.lock().unwrap().block_size())
+        Ok(self.slotpos(self.get_num_recs()? + 1) >= self.tx.lock().unwrap().page_size())
     }
 
     pub fn split(&self, splitpos: usize, flag: i32) -> Result<BlockId, TransactionError> {
@@ -78,22 +78,26 @@ impl BTPage {
 
     pub fn append_new(&self, flag: i32) -> Result<BlockId, TransactionError> {
         if let Some(currentblk) = &self.currentblk {
-            let mut tx = self.tx.lock().unwrap();
-            let blk = tx.append(currentblk.file_name())?;
-            tx.pin(&blk)?;
+            let blk = {
+                let mut tx = self.tx.lock().unwrap();
+                let blk = tx.append(currentblk.file_name())?;
+                tx.pin(&blk)?;
+                blk
+            };
             self.format(&blk, flag)?;
+            self.tx.lock().unwrap().unpin(&blk)?;
             return Ok(blk);
         }
         Err(TransactionError::General)
     }
 
     pub fn format(&self, blk: &BlockId, flag: i32) -> Result<(), TransactionError> {
-        self.tx.lock().unwrap().set_int(blk, 0, flag, false)?;
+        self.tx.lock().unwrap().set_int(blk, 0, flag, true)?;
         let bytes = 4;
-        self.tx.lock().unwrap().set_int(blk, bytes, 0, false)?;
+        self.tx.lock().unwrap().set_int(blk, bytes, 0, true)?;
         let recsize = self.layout.slot_size();
         let mut pos = 2 * bytes;
-        while pos + recsize <= self.tx.lock().unwrap().block_size() {
+        while pos + recsize <= self.tx.lock().unwrap().page_size() {
             self.make_default_record(blk, pos)?;
             pos += recsize;
         }
@@ -105,8 +109,17 @@ impl BTPage {
         for fldname in self.layout.schema().fields() {
             let offset = self.layout.offset(fldname);
             match self.layout.schema().type_(fldname) {
-                schema::Type::Integer => tx.set_int(blk, pos + offset, 0, false)?,
-                schema::Type::Varchar => tx.set_string(blk, pos + offset, "", false)?,
+                schema::Type::Integer | schema::Type::Date => {
+                    tx.set_int(blk, pos + offset, 0, true)?
+                }
+                schema::Type::BigInt | schema::Type::Timestamp => {
+                    tx.set_long(blk, pos + offset, 0, true)?
+                }
+                schema::Type::Double => tx.set_double(blk, pos + offset, 0.0, true)?,
+                schema::Type::Boolean => tx.set_bool(blk, pos + offset, false, true)?,
+                schema::Type::Varchar | schema::Type::Text | schema::Type::Blob => {
+                    tx.set_string(blk, pos + offset, "", true)?
+                }
             };
         }
         Ok(())
@@ -122,6 +135,7 @@ impl BTPage {
         val: Constant,
         blknum: i32,
     ) -> Result<(), TransactionError> {
+        self.check_key(&val)?;
         self.insert(slot)?;
         self.set_val(slot, "dataval", val)?;
         self.set_int(slot, "block", blknum)?;
@@ -141,6 +155,7 @@ impl BTPage {
         val: Constant,
         rid: &Rid,
     ) -> Result<(), TransactionError> {
+        self.check_key(&val)?;
         self.insert(slot)?;
         self.set_val(slot, "dataval", val)?;
         self.set_int(slot, "block", rid.block_number())?;
@@ -172,19 +187,21 @@ impl BTPage {
         Err(TransactionError::General)
     }
 
-    fn get_string(&self, slot: usize, fldname: &str) -> Result<String, TransactionError> {
-        let pos = self.fldpos(slot, fldname);
-        if let Some(currentblk) = &self.currentblk {
-            return self.tx.lock().unwrap().get_string(currentblk, pos);
-        }
-        Err(TransactionError::General)
-    }
-
     fn get_val(&self, slot: usize, fldname: &str) -> Result<Constant, TransactionError> {
-        let type_ = self.layout.schema().type_(fldname);
-        match type_ {
-            schema::Type::Integer => Ok(Constant::with_int(self.get_int(slot, fldname)?)),
-            schema::Type::Varchar => Ok(Constant::with_string(&self.get_string(slot, fldname)?)),
+        let pos = self.fldpos(slot, fldname);
+        let currentblk = self.currentblk.as_ref().ok_or(TransactionError::General)?;
+        let mut tx = self.tx.lock().unwrap();
+        match self.layout.schema().type_(fldname) {
+            schema::Type::Integer => Ok(Constant::with_int(tx.get_int(currentblk, pos)?)),
+            schema::Type::BigInt => Ok(Constant::with_long(tx.get_long(currentblk, pos)?)),
+            schema::Type::Double => Ok(Constant::with_double(tx.get_double(currentblk, pos)?)),
+            schema::Type::Varchar | schema::Type::Text => {
+                Ok(Constant::with_string(&tx.get_string(currentblk, pos)?))
+            }
+            schema::Type::Boolean => Ok(Constant::with_bool(tx.get_bool(currentblk, pos)?)),
+            schema::Type::Date => Ok(Constant::with_date(tx.get_int(currentblk, pos)?)),
+            schema::Type::Timestamp => Ok(Constant::with_timestamp(tx.get_long(currentblk, pos)?)),
+            schema::Type::Blob => Err(TransactionError::General),
         }
     }
 
@@ -200,36 +217,39 @@ impl BTPage {
         Err(TransactionError::General)
     }
 
-    fn set_string(&self, slot: usize, fldname: &str, val: &str) -> Result<(), TransactionError> {
+    fn set_val(&self, slot: usize, fldname: &str, val: Constant) -> Result<(), TransactionError> {
         let pos = self.fldpos(slot, fldname);
-        if let Some(currentblk) = &self.currentblk {
-            self.tx
-                .lock()
-                .unwrap()
-                .set_string(currentblk, pos, val, true)?;
-            return Ok(());
+        let currentblk = self.currentblk.as_ref().ok_or(TransactionError::General)?;
+        let mut tx = self.tx.lock().unwrap();
+        let err = TransactionError::General;
+        match self.layout.schema().type_(fldname) {
+            schema::Type::Integer => tx.set_int(currentblk, pos, val.as_int().ok_or(err)?, true),
+            schema::Type::BigInt => tx.set_long(currentblk, pos, val.as_long().ok_or(err)?, true),
+            schema::Type::Double => {
+                tx.set_double(currentblk, pos, val.as_double().ok_or(err)?, true)
+            }
+            schema::Type::Varchar | schema::Type::Text => {
+                tx.set_string(currentblk, pos, &val.as_string().ok_or(err)?, true)
+            }
+            schema::Type::Blob => Err(err),
+            schema::Type::Boolean => tx.set_bool(currentblk, pos, val.as_bool().ok_or(err)?, true),
+            schema::Type::Date => tx.set_int(currentblk, pos, val.as_date().ok_or(err)?, true),
+            schema::Type::Timestamp => {
+                tx.set_long(currentblk, pos, val.as_timestamp().ok_or(err)?, true)
+            }
         }
-        Err(TransactionError::General)
     }
 
-    fn set_val(&self, slot: usize, fldname: &str, val: Constant) -> Result<(), TransactionError> {
-        let type_ = self.layout.schema().type_(fldname);
-        match type_ {
-            schema::Type::Integer => {
-                if let Some(val) = val.as_int() {
-                    self.set_int(slot, fldname, val)
-                } else {
-                    Err(TransactionError::General)
-                }
-            }
-            schema::Type::Varchar => {
-                if let Some(val) = val.as_string() {
-                    self.set_string(slot, fldname, &val)
-                } else {
-                    Err(TransactionError::General)
-                }
+    fn check_key(&self, val: &Constant) -> Result<(), TransactionError> {
+        if let Some(s) = val.as_string() {
+            if s.chars().count() > self.layout.schema().length("dataval") {
+                let currentblk = self.currentblk.as_ref().ok_or(TransactionError::General)?;
+                return Err(TransactionError::IndexKeyTooLong(
+                    currentblk.file_name().to_string(),
+                ));
             }
         }
+        Ok(())
     }
 
     fn set_num_recs(&self, n: usize) -> Result<(), TransactionError> {
@@ -51,8 +51,14 @@ impl BTreeIndex {
 
             let fldtype = dirsch.type_("dataval");
             let minval = match fldtype {
-                schema::Type::Integer => Constant::with_int(-2147483648),
-                schema::Type::Varchar => Constant::with_string(""),
+                schema::Type::Integer => Constant::with_int(i32::MIN),
+                schema::Type::BigInt => Constant::with_long(i64::MIN),
+                schema::Type::Double => Constant::with_double(f64::NEG_INFINITY),
+                schema::Type::Varchar | schema::Type::Text => Constant::with_string(""),
+                schema::Type::Boolean => Constant::with_bool(false),
+                schema::Type::Date => Constant::with_date(i32::MIN),
+                schema::Type::Timestamp => Constant::with_timestamp(i64::MIN),
+                schema::Type::Blob => Constant::with_bytes(&[]),
             };
             node.insert_dir(0, minval, 0)?;
             node.close()?;
@@ -67,6 +73,12 @@ impl BTreeIndex {
         })
     }
 
+    pub fn fits(layout: &Layout, blocksize: usize) -> bool {
+        let bytes = 4;
+        let minrecs = 4;
+        bytes + bytes + minrecs * layout.slot_size() < blocksize
+    }
+
     pub fn search_cost(numblocks: usize, rpb: usize) -> usize {
         1 + (((numblocks as f64).ln() / (rpb as f64).ln()) as usize)
     }
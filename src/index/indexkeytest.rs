@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        api::{
+            connection::ConnectionControl, driver::DriverControl, driver::SQLError,
+            embedded::embeddeddriver::EmbeddedDriver, resultset::ResultSetControl,
+            statement::StatementControl,
+        },
+        plan::plan::PlanError,
+        tx::transaction::TransactionError,
+    };
+
+    #[test]
+    fn indexkeytest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("indexkeytest").unwrap();
+        let mut stmt = conn.create_statement();
+
+        stmt.execute_update("create table T(A int, B varchar(20))")
+            .unwrap();
+        stmt.execute_update("create index IB on T(B)").unwrap();
+        for i in 0..40 {
+            let b = format!("{:x>15}", i);
+            stmt.execute_update(&format!("insert into T(A, B) values ({}, '{}')", i, b))
+                .unwrap();
+        }
+
+        let wide = "é".repeat(20);
+        stmt.execute_update(&format!("insert into T(A, B) values (40, '{}')", wide))
+            .unwrap();
+        let result = stmt.execute_update(&format!(
+            "insert into T(A, B) values (41, '{}')",
+            "é".repeat(21)
+        ));
+        assert!(matches!(
+            result,
+            Err(SQLError::Plan(PlanError::Transaction(
+                TransactionError::StringTooLong(_)
+            )))
+        ));
+
+        let keys = [0, 17, 39].map(|i| (i, format!("{:x>15}", i)));
+        for (i, b) in keys.into_iter().chain([(40, wide)]) {
+            let qry = format!("select A from T where B = '{}'", b);
+            let mut rs = stmt.execute_query(&qry).unwrap();
+            assert!(rs.next().unwrap());
+            assert_eq!(i, rs.get_int("a").unwrap());
+            assert!(!rs.next().unwrap());
+            rs.close().unwrap();
+        }
+
+        stmt.execute_update("create table U(C varchar(21))")
+            .unwrap();
+        let result = stmt.execute_update("create index IC on U(C)");
+        assert!(matches!(
+            result,
+            Err(SQLError::Plan(PlanError::Transaction(
+                TransactionError::IndexKeyTooLong(_)
+            )))
+        ));
+        conn.close().unwrap();
+
+        fs::remove_dir_all("indexkeytest").unwrap();
+    }
+}
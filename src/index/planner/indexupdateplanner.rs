@@ -53,7 +53,7 @@ impl UpdatePlannerControl for IndexUpdatePlanner {
                 for (fldname, val) in zip(data.fields(), data.vals()) {
                     s.set_val(&fldname, val.clone())?;
 
-                    let ii = indexes.get(&fldname);
+                    let ii = indexes.get(&fldname).filter(|_| !val.is_null());
                     if let Some(ii) = ii {
                         let mut idx = ii.open()?;
                         idx.insert(val, &rid)?;
@@ -85,6 +85,9 @@ impl UpdatePlannerControl for IndexUpdatePlanner {
                 if let Some(rid) = rid {
                     for (fldname, ii) in &indexes {
                         let val = s.get_val(fldname)?;
+                        if val.is_null() {
+                            continue;
+                        }
                         let mut idx = ii.open()?;
                         idx.delete(val, &rid)?;
                         idx.close()?;
@@ -127,8 +130,12 @@ impl UpdatePlannerControl for IndexUpdatePlanner {
                 if let Some(idx) = &mut idx {
                     let rid = s.get_rid();
                     if let Some(rid) = rid {
-                        idx.delete(oldval, &rid)?;
-                        idx.insert(newval, &rid)?;
+                        if !oldval.is_null() {
+                            idx.delete(oldval, &rid)?;
+                        }
+                        if !newval.is_null() {
+                            idx.insert(newval, &rid)?;
+                        }
                     }
                 }
                 count += 1;
@@ -8,11 +8,16 @@ pub struct LogIterator {
     p: Page,
     currentpos: i32,
     boundary: i32,
+    segments: Vec<String>,
 }
 
 impl LogIterator {
-    pub fn new(fm: Arc<FileMgr>, blk: &BlockId) -> Result<LogIterator, Error> {
-        let b: Vec<u8> = vec![0; fm.block_size()];
+    pub fn new(
+        fm: Arc<FileMgr>,
+        blk: &BlockId,
+        segments: Vec<String>,
+    ) -> Result<LogIterator, Error> {
+        let b: Vec<u8> = vec![0; fm.page_size()];
         let p = Page::with_vec(b);
         let mut l = LogIterator {
             fm,
@@ -20,6 +25,7 @@ impl LogIterator {
             p,
             currentpos: 0,
             boundary: 0,
+            segments,
         };
         l.move_to_block(blk)?;
         Ok(l)
@@ -37,11 +43,18 @@ impl Iterator for LogIterator {
     type Item = Vec<u8>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.currentpos >= self.fm.block_size() as i32 && self.blk.number() <= 0 {
-            return None;
-        }
-        if self.currentpos == self.fm.block_size() as i32 {
-            self.blk = BlockId::new(self.blk.file_name(), self.blk.number() - 1);
+        while self.currentpos >= self.fm.page_size() as i32 {
+            if self.blk.number() > 0 {
+                self.blk = BlockId::new(self.blk.file_name(), self.blk.number() - 1);
+            } else if let Some(segment) = self.segments.pop() {
+                let size = self.fm.length(&segment).ok()?;
+                if size == 0 {
+                    continue;
+                }
+                self.blk = BlockId::new(&segment, size as i32 - 1);
+            } else {
+                return None;
+            }
             self.move_to_block(&self.blk.clone()).ok();
         }
         let rec = self.p.get_bytes(self.currentpos as usize);
@@ -1,4 +1,9 @@
-use std::{io::Error, sync::Arc};
+use std::{
+    collections::HashSet,
+    io::{Error, ErrorKind},
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
 
 use crate::file::{blockid::BlockId, filemgr::FileMgr, page::Page};
 
@@ -11,25 +16,73 @@ pub struct LogMgr {
     currentblk: BlockId,
     lastest_lsn: usize,
     last_saved_lsn: usize,
+    segments: Vec<(usize, String)>,
+    segment_size: usize,
+    archive_dir: Option<String>,
+    checkpoint_lsn: Option<usize>,
+    max_wait: Duration,
+    group_leader: bool,
+    flushed: Arc<Condvar>,
+    flush_count: usize,
+    imaged_blocks: HashSet<BlockId>,
 }
 
 fn append_new_block(fm: &FileMgr, logpage: &mut Page, logfile: &str) -> Result<BlockId, Error> {
     let blk = fm.append(logfile)?;
-    logpage.set_int(0, fm.block_size() as i32);
+    logpage.set_int(0, fm.page_size() as i32);
     fm.write(&blk, logpage)?;
     Ok(blk)
 }
 
+fn segment_name(logfile: &str, first_lsn: usize) -> String {
+    format!("{}.{}", logfile, first_lsn)
+}
+
 impl LogMgr {
+    const SEGMENT_SIZE: usize = 1000;
+    const MAX_WAIT: Duration = Duration::ZERO;
+
     pub fn new(fm: Arc<FileMgr>, logfile: &str) -> Result<LogMgr, Error> {
-        let b = vec![0; fm.block_size()];
+        let b = vec![0; fm.page_size()];
         let mut logpage = Page::with_vec(b);
-        let logsize = fm.length(logfile)?;
-        let currentblk = if logsize == 0 {
-            append_new_block(&fm, &mut logpage, logfile)?
+        let prefix = format!("{}.", logfile);
+        let mut segments: Vec<(usize, String)> = fm
+            .file_names()?
+            .into_iter()
+            .filter_map(|name| {
+                let first_lsn = name.strip_prefix(&prefix)?.parse().ok()?;
+                Some((first_lsn, name))
+            })
+            .collect();
+        segments.sort();
+        if fm.file_names()?.iter().any(|name| name == logfile) {
+            if !segments.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("both {} and its segments exist", logfile),
+                ));
+            }
+            let segment = segment_name(logfile, 1);
+            fm.rename(logfile, &segment)?;
+            segments.push((1, segment));
+        }
+        let mut lastest_lsn = 0;
+        let currentblk = if let Some((first_lsn, segment)) = segments.last() {
+            let logsize = fm.length(segment)?;
+            if logsize == 0 {
+                lastest_lsn = first_lsn - 1;
+                append_new_block(&fm, &mut logpage, segment)?
+            } else {
+                let currentblk = BlockId::new(segment, logsize as i32 - 1);
+                let count = LogIterator::new(fm.clone(), &currentblk, Vec::new())?.count();
+                lastest_lsn = first_lsn + count - 1;
+                fm.read(&currentblk, &mut logpage)?;
+                currentblk
+            }
         } else {
-            let currentblk = BlockId::new(logfile, logsize as i32 - 1);
-            fm.read(&currentblk, &mut logpage)?;
+            let segment = segment_name(logfile, 1);
+            let currentblk = append_new_block(&fm, &mut logpage, &segment)?;
+            segments.push((1, segment));
             currentblk
         };
         Ok(LogMgr {
@@ -37,8 +90,17 @@ impl LogMgr {
             logfile: logfile.to_string(),
             logpage,
             currentblk,
-            lastest_lsn: 0,
-            last_saved_lsn: 0,
+            lastest_lsn,
+            last_saved_lsn: lastest_lsn,
+            segments,
+            segment_size: LogMgr::SEGMENT_SIZE,
+            archive_dir: None,
+            checkpoint_lsn: None,
+            max_wait: LogMgr::MAX_WAIT,
+            group_leader: false,
+            flushed: Arc::new(Condvar::new()),
+            flush_count: 0,
+            imaged_blocks: HashSet::new(),
         })
     }
 
@@ -49,9 +111,41 @@ impl LogMgr {
         Ok(())
     }
 
+    pub fn group_flush(lm: &Arc<Mutex<LogMgr>>, lsn: usize) -> Result<(), Error> {
+        let mut guard = lm.lock().unwrap();
+        let flushed = guard.flushed.clone();
+        while guard.group_leader && guard.last_saved_lsn < lsn {
+            guard = flushed.wait(guard).unwrap();
+        }
+        if guard.last_saved_lsn >= lsn {
+            return Ok(());
+        }
+        guard.group_leader = true;
+        let deadline = Instant::now() + guard.max_wait;
+        while guard.last_saved_lsn < lsn {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            guard = flushed.wait_timeout(guard, deadline - now).unwrap().0;
+        }
+        guard.group_leader = false;
+        let result = if guard.last_saved_lsn < lsn {
+            guard.flush_impl()
+        } else {
+            Ok(())
+        };
+        flushed.notify_all();
+        result
+    }
+
     pub fn iterator(&mut self) -> Result<LogIterator, Error> {
         self.flush_impl()?;
-        LogIterator::new(self.fm.clone(), &self.currentblk)
+        let older = self.segments[..self.segments.len() - 1]
+            .iter()
+            .map(|(_, segment)| segment.clone())
+            .collect();
+        LogIterator::new(self.fm.clone(), &self.currentblk, older)
     }
 
     pub fn append(&mut self, logrec: &Vec<u8>) -> Result<usize, Error> {
@@ -70,13 +164,99 @@ impl LogMgr {
         Ok(self.lastest_lsn)
     }
 
+    pub fn size(&self) -> Result<usize, Error> {
+        let mut blocks = 0;
+        for (_, segment) in self.segments.iter() {
+            blocks += self.fm.length(segment)?;
+        }
+        Ok(blocks * self.fm.block_size())
+    }
+
+    pub fn last_lsn(&self) -> usize {
+        self.lastest_lsn
+    }
+
+    pub fn first_lsn(&self) -> usize {
+        self.segments[0].0
+    }
+
+    pub fn checkpoint_lsn(&self) -> Option<usize> {
+        self.checkpoint_lsn
+    }
+
+    pub fn set_checkpoint_lsn(&mut self, lsn: usize) {
+        self.checkpoint_lsn = Some(lsn);
+    }
+
+    pub fn truncate(&mut self, lsn: usize) -> Result<(), Error> {
+        while self.segments.len() > 1 && self.segments[1].0 <= lsn {
+            let (_, segment) = self.segments.remove(0);
+            if let Some(archive_dir) = &self.archive_dir {
+                self.fm.archive(&segment, archive_dir)?;
+            } else {
+                self.fm.remove(&segment)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn reset(&mut self) -> Result<(), Error> {
+        self.flush_impl()?;
+        for (_, segment) in self.segments.drain(..) {
+            self.fm.remove(&segment)?;
+        }
+        let first_lsn = self.lastest_lsn + 1;
+        let segment = segment_name(&self.logfile, first_lsn);
+        self.currentblk = append_new_block(&self.fm, &mut self.logpage, &segment)?;
+        self.segments.push((first_lsn, segment));
+        self.checkpoint_lsn = None;
+        Ok(())
+    }
+
+    pub fn needs_page_image(&mut self, blk: &BlockId) -> bool {
+        self.imaged_blocks.insert(blk.clone())
+    }
+
+    pub fn clear_page_images(&mut self) {
+        self.imaged_blocks.clear();
+    }
+
+    #[allow(dead_code)]
+    pub fn flush_count(&self) -> usize {
+        self.flush_count
+    }
+
+    #[allow(dead_code)]
+    pub fn set_max_wait(&mut self, max_wait: Duration) {
+        self.max_wait = max_wait;
+    }
+
+    #[allow(dead_code)]
+    pub fn set_segment_size(&mut self, segment_size: usize) {
+        self.segment_size = segment_size;
+    }
+
+    #[allow(dead_code)]
+    pub fn set_archive_dir(&mut self, archive_dir: &str) {
+        self.archive_dir = Some(archive_dir.to_string());
+    }
+
     fn append_new_block(&mut self) -> Result<BlockId, Error> {
-        append_new_block(&self.fm, &mut self.logpage, self.logfile.as_str())
+        if self.currentblk.number() as usize + 1 >= self.segment_size {
+            let first_lsn = self.lastest_lsn + 1;
+            let segment = segment_name(&self.logfile, first_lsn);
+            self.segments.push((first_lsn, segment));
+        }
+        let segment = self.segments[self.segments.len() - 1].1.clone();
+        append_new_block(&self.fm, &mut self.logpage, &segment)
     }
 
     fn flush_impl(&mut self) -> Result<(), Error> {
         self.fm.write(&self.currentblk, &mut self.logpage)?;
+        self.fm.sync(self.currentblk.file_name())?;
         self.last_saved_lsn = self.lastest_lsn;
+        self.flush_count += 1;
+        self.flushed.notify_all();
         Ok(())
     }
 }
@@ -4,13 +4,19 @@ mod tests {
         fs,
         iter::zip,
         sync::{Arc, Mutex},
+        thread,
+        time::Duration,
     };
 
-    use crate::{file::page::Page, log::logmgr::LogMgr, server::simpledb::SimpleDB};
+    use crate::{
+        file::{blockid::BlockId, durability::Durability, page::Page},
+        log::logmgr::LogMgr,
+        server::simpledb::SimpleDB,
+    };
 
     #[test]
     fn logtest() {
-        let mut db = SimpleDB::with_params("logtest", 400, 8).unwrap();
+        let mut db = SimpleDB::with_params("logtest", 400, 8, Durability::NoSync).unwrap();
         let lm = db.log_mgr();
 
         assert_log_records(lm.clone(), Vec::new());
@@ -23,12 +29,136 @@ mod tests {
         fs::remove_dir_all("logtest").unwrap();
     }
 
+    #[test]
+    fn logsegmenttest() {
+        let mut db = SimpleDB::with_params("logsegmenttest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+        let lm = db.log_mgr();
+        lm.lock().unwrap().set_segment_size(2);
+
+        create_records(lm.clone(), 1, 200);
+        assert_log_records(lm.clone(), (1..=200).rev().collect());
+        assert_eq!(200, lm.lock().unwrap().iterator().unwrap().count());
+        let segments = fm
+            .file_names()
+            .unwrap()
+            .into_iter()
+            .filter(|name| name.starts_with("simpledb.log."))
+            .count();
+        assert!(segments > 1);
+        let size = lm.lock().unwrap().size().unwrap();
+        assert_eq!(segments * 2 * 400, size + 400);
+
+        lm.lock().unwrap().truncate(150).unwrap();
+        assert!(lm.lock().unwrap().first_lsn() > 1);
+        assert!(lm.lock().unwrap().first_lsn() <= 150);
+        assert!(lm.lock().unwrap().size().unwrap() < size);
+        let iter = lm.lock().unwrap().iterator().unwrap();
+        let first = 201 - iter.count() as i32;
+        assert_eq!(first, lm.lock().unwrap().first_lsn() as i32);
+        assert_log_records(lm.clone(), (first..=200).rev().collect());
+
+        fs::remove_dir_all("logsegmenttest").unwrap();
+    }
+
+    #[test]
+    fn logarchivetest() {
+        {
+            let mut db =
+                SimpleDB::with_params("logarchivetest", 400, 8, Durability::NoSync).unwrap();
+            let lm = db.log_mgr();
+            lm.lock().unwrap().set_segment_size(2);
+            lm.lock().unwrap().set_archive_dir("logarchivetest_archive");
+            create_records(lm.clone(), 1, 100);
+            lm.lock().unwrap().flush(100).unwrap();
+            lm.lock().unwrap().truncate(100).unwrap();
+            let first = lm.lock().unwrap().first_lsn();
+            assert!(first > 1);
+            let archived = fs::read_dir("logarchivetest_archive").unwrap().count();
+            assert!(archived > 0);
+            let mut lm = LogMgr::new(db.file_mgr(), "simpledb.log").unwrap();
+            assert_eq!(first, lm.first_lsn());
+            assert_eq!(
+                101,
+                lm.append(&create_log_record("record101", 201)).unwrap()
+            );
+        }
+
+        let db = SimpleDB::with_params("logarchivetest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 0);
+        let mut tx = db.new_tx().unwrap();
+        tx.append("testfile").unwrap();
+        tx.pin(&blk).unwrap();
+        tx.set_int(&blk, 0, 1, true).unwrap();
+        assert!(db.oldest_needed_lsn() < 101);
+        tx.commit().unwrap();
+        db.checkpoint().unwrap();
+        assert!(db.oldest_needed_lsn() > 101);
+        assert!(db.log_size().unwrap() > 0);
+
+        fs::remove_dir_all("logarchivetest").unwrap();
+        fs::remove_dir_all("logarchivetest_archive").unwrap();
+    }
+
+    #[test]
+    fn legacylogtest() {
+        let db = SimpleDB::with_params("legacylogtest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+        {
+            let lm = Arc::new(Mutex::new(LogMgr::new(fm.clone(), "legacy.log").unwrap()));
+            create_records(lm.clone(), 1, 50);
+            lm.lock().unwrap().flush(50).unwrap();
+        }
+        fm.rename("legacy.log.1", "legacy.log").unwrap();
+
+        let lm = Arc::new(Mutex::new(LogMgr::new(fm.clone(), "legacy.log").unwrap()));
+        let names = fm.file_names().unwrap();
+        assert!(!names.contains(&"legacy.log".to_string()));
+        assert!(names.contains(&"legacy.log.1".to_string()));
+        assert_eq!(50, lm.lock().unwrap().iterator().unwrap().count());
+        assert_log_records(lm.clone(), (1..=50).rev().collect());
+        create_records(lm.clone(), 51, 51);
+
+        fs::write("legacylogtest/legacy.log", []).unwrap();
+        assert!(LogMgr::new(fm, "legacy.log").is_err());
+
+        fs::remove_dir_all("legacylogtest").unwrap();
+    }
+
+    #[test]
+    fn groupcommittest() {
+        let mut db = SimpleDB::with_params("groupcommittest", 400, 8, Durability::NoSync)
+            .unwrap()
+            .with_group_commit_wait(Duration::from_millis(200));
+        let lm = db.log_mgr();
+        let flushes = lm.lock().unwrap().flush_count();
+
+        let mut handlers = Vec::new();
+        for i in 1..=8 {
+            let lm = lm.clone();
+            handlers.push(thread::spawn(move || {
+                let rec = create_log_record(&format!("record{}", i), i + 100);
+                let lsn = lm.lock().unwrap().append(&rec).unwrap();
+                LogMgr::group_flush(&lm, lsn).unwrap();
+            }));
+        }
+        for handler in handlers {
+            handler.join().unwrap();
+        }
+        assert!(lm.lock().unwrap().flush_count() - flushes < 8);
+
+        let iter = lm.lock().unwrap().iterator().unwrap();
+        assert_eq!(8, iter.count());
+
+        fs::remove_dir_all("groupcommittest").unwrap();
+    }
+
     fn assert_log_records(lm: Arc<Mutex<LogMgr>>, expected: Vec<i32>) {
         let iter = lm.lock().unwrap().iterator().unwrap();
         for (rec, exp) in zip(iter, expected) {
             let p = Page::with_vec(rec);
             let s = p.get_string(0).unwrap();
-            let npos = Page::max_length(s.len());
+            let npos = Page::bytes_length(s.len());
             let val = p.get_int(npos);
 
             assert_eq!(format!("record{}", exp), s);
@@ -47,7 +177,7 @@ mod tests {
 
     fn create_log_record(s: &str, n: usize) -> Vec<u8> {
         let spos = 0;
-        let npos = Page::max_length(s.len());
+        let npos = Page::bytes_length(s.len());
         let b: Vec<u8> = vec![0; npos + 4];
         let mut p = Page::with_vec(b);
         p.set_string(spos, s);
@@ -1,4 +1,7 @@
-use std::io::{self, Write};
+use std::{
+    env,
+    io::{self, Write},
+};
 
 use api::statement::Statement;
 
@@ -13,6 +16,7 @@ use crate::{
         statement::StatementControl,
     },
     record::schema::Type,
+    server::dbconfig::DbConfig,
 };
 
 mod api;
@@ -32,6 +36,7 @@ mod server;
 mod tx;
 
 fn main() {
+    let args: Vec<String> = env::args().collect();
     let stdin = io::stdin();
     print!("Connect> ");
     io::stdout().flush().unwrap();
@@ -39,7 +44,10 @@ fn main() {
     stdin.read_line(&mut s).unwrap();
     let is_embedded = !s.contains("//");
     let d: Driver = if is_embedded {
-        EmbeddedDriver::new().into()
+        match args.get(1) {
+            Some(path) => EmbeddedDriver::with_config(DbConfig::load(path).unwrap()).into(),
+            None => EmbeddedDriver::new().into(),
+        }
     } else {
         NetworkDriver::new().into()
     };
@@ -90,12 +98,24 @@ fn do_query(stmt: &mut Statement, cmd: &str) {
             let width = md.get_column_display_size(i).unwrap();
             match md.get_column_type(i).unwrap() {
                 Type::Integer => {
-                    let ival = rs.get_int(&fldname).unwrap();
-                    print!("{:>width$}", ival);
+                    let ival = rs.get_int(&fldname).unwrap().to_string();
+                    print!("{:>width$}", if rs.was_null() { "null" } else { &ival });
+                }
+                Type::BigInt => {
+                    let lval = rs.get_long(&fldname).unwrap().to_string();
+                    print!("{:>width$}", if rs.was_null() { "null" } else { &lval });
+                }
+                Type::Double => {
+                    let dval = rs.get_double(&fldname).unwrap().to_string();
+                    print!("{:>width$}", if rs.was_null() { "null" } else { &dval });
+                }
+                Type::Boolean => {
+                    let bval = rs.get_boolean(&fldname).unwrap().to_string();
+                    print!("{:>width$}", if rs.was_null() { "null" } else { &bval });
                 }
-                Type::Varchar => {
+                Type::Varchar | Type::Date | Type::Timestamp | Type::Text | Type::Blob => {
                     let sval = rs.get_string(&fldname).unwrap();
-                    print!("{:>width$}", sval);
+                    print!("{:>width$}", if rs.was_null() { "null" } else { &sval });
                 }
             }
         }
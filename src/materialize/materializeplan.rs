@@ -46,7 +46,7 @@ impl PlanControl for MaterializePlan {
 
     fn blocks_accessed(&self) -> usize {
         let layout = Layout::new(self.srcplan.schema());
-        let rpb = self.tx.lock().unwrap().block_size() / layout.slot_size();
+        let rpb = self.tx.lock().unwrap().page_size() / layout.slot_size();
         (self.srcplan.records_output() as f64 / rpb as f64).ceil() as usize
     }
 
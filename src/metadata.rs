@@ -5,6 +5,8 @@ pub mod metadatamgr;
 pub mod metadatamgrtest;
 pub mod statinfo;
 pub mod statmgr;
+pub mod systablemgr;
+pub mod systabletest;
 pub mod tablemgr;
 pub mod tablemgrtest;
 pub mod viewmgr;
@@ -6,25 +6,26 @@ mod tests {
     };
 
     use crate::{
-        metadata::tablemgr::TableMgr, query::scan::ScanControl, record::tablescan::TableScan,
+        file::durability::Durability,
+        metadata::tablemgr::TableMgr,
+        query::scan::ScanControl,
+        record::{schema::Type, tablescan::TableScan},
         server::simpledb::SimpleDB,
     };
 
     #[test]
     fn catalogtest() {
-        let db = SimpleDB::with_params("catalogtest", 400, 8).unwrap();
+        let db = SimpleDB::with_params("catalogtest", 400, 8, Durability::NoSync).unwrap();
         let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
         let tm = TableMgr::new(true, tx.clone()).unwrap();
         let tcat_layout = tm.get_layout("tblcat", tx.clone()).unwrap();
 
         let mut ts = TableScan::new(tx.clone(), "tblcat", tcat_layout).unwrap();
         let mut i = 0;
-        let e = [("tblcat", 28), ("fldcat", 56)];
+        let e = ["tblcat", "fldcat"];
         while ts.next().unwrap() {
             let tname = ts.get_string("tblname").unwrap();
-            let slotsize = ts.get_int("slotsize").unwrap();
-            assert_eq!(tname, e[i].0);
-            assert_eq!(slotsize, e[i].1);
+            assert_eq!(tname, e[i]);
             i += 1;
         }
         assert_eq!(i, e.len());
@@ -34,21 +35,21 @@ mod tests {
         ts = TableScan::new(tx.clone(), "fldcat", fcat_layout).unwrap();
         let mut i = 0;
         let e = [
-            ("tblcat", "tblname", 4),
-            ("tblcat", "slotsize", 24),
-            ("fldcat", "tblname", 4),
-            ("fldcat", "fldname", 24),
-            ("fldcat", "type", 44),
-            ("fldcat", "length", 48),
-            ("fldcat", "offset", 52),
+            ("tblcat", "tblname", Type::Varchar, 16),
+            ("fldcat", "tblname", Type::Varchar, 16),
+            ("fldcat", "fldname", Type::Varchar, 16),
+            ("fldcat", "type", Type::Integer, 0),
+            ("fldcat", "length", Type::Integer, 0),
         ];
         while ts.next().unwrap() {
             let tname = ts.get_string("tblname").unwrap();
             let fname = ts.get_string("fldname").unwrap();
-            let offset = ts.get_int("offset").unwrap();
+            let fldtype = ts.get_int("type").unwrap();
+            let fldlen = ts.get_int("length").unwrap();
             assert_eq!(tname, e[i].0);
             assert_eq!(fname, e[i].1);
-            assert_eq!(offset, e[i].2);
+            assert_eq!(fldtype, e[i].2 as i32);
+            assert_eq!(fldlen, e[i].3);
             i += 1;
         }
         assert_eq!(i, e.len());
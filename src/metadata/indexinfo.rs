@@ -20,16 +20,16 @@ pub struct IndexInfo {
     si: StatInfo,
 }
 
-fn create_idx_layout(fldname: &str, tbl_schema: &Schema) -> Layout {
+pub fn create_idx_layout(fldname: &str, tbl_schema: &Schema) -> Layout {
     let mut sch = Schema::new();
     sch.add_int_field("block");
     sch.add_int_field("id");
     match tbl_schema.type_(fldname) {
-        Type::Integer => sch.add_int_field("dataval"),
         Type::Varchar => {
             let fldlen = tbl_schema.length(fldname);
             sch.add_string_field("dataval", fldlen)
         }
+        fldtype => sch.add_field("dataval", fldtype, 0),
     }
     Layout::new(Arc::new(sch))
 }
@@ -57,7 +57,7 @@ impl IndexInfo {
     }
 
     pub fn blocks_accessed(&self) -> usize {
-        let rpb = self.tx.lock().unwrap().block_size() / self.idx_layout.slot_size();
+        let rpb = (self.tx.lock().unwrap().page_size() / self.idx_layout.slot_size()).max(2);
         let numblocks = self.si.records_output() / rpb;
         BTreeIndex::search_cost(numblocks, rpb)
     }
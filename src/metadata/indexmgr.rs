@@ -4,12 +4,21 @@ use std::{
 };
 
 use crate::{
+    index::btree::btreeindex::BTreeIndex,
     query::{scan::ScanControl, updatescan::UpdateScanControl},
-    record::{layout::Layout, schema::Schema, tablescan::TableScan},
+    record::{
+        layout::Layout,
+        schema::{Schema, Type},
+        tablescan::TableScan,
+    },
     tx::transaction::{Transaction, TransactionError},
 };
 
-use super::{indexinfo::IndexInfo, statmgr::StatMgr, tablemgr::TableMgr};
+use super::{
+    indexinfo::{create_idx_layout, IndexInfo},
+    statmgr::StatMgr,
+    tablemgr::TableMgr,
+};
 
 pub struct IndexMgr {
     layout: Layout,
@@ -46,6 +55,15 @@ impl IndexMgr {
         fldname: &str,
         tx: Arc<Mutex<Transaction>>,
     ) -> Result<(), TransactionError> {
+        let tbl_schema = self.tblmgr.get_layout(tblname, tx.clone())?.schema();
+        if tbl_schema.has_field(fldname) {
+            let idx_layout = create_idx_layout(fldname, &tbl_schema);
+            let blocksize = tx.lock().unwrap().page_size();
+            let unbounded = matches!(tbl_schema.type_(fldname), Type::Text | Type::Blob);
+            if unbounded || !BTreeIndex::fits(&idx_layout, blocksize) {
+                return Err(TransactionError::IndexKeyTooLong(idxname.to_string()));
+            }
+        }
         let mut ts = TableScan::new(tx, "idxcat", self.layout.clone())?;
         ts.insert()?;
         ts.set_string("indexname", idxname)?;
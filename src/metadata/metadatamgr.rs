@@ -4,7 +4,7 @@ use std::{
 };
 
 use crate::{
-    record::{layout::Layout, schema::Schema},
+    record::{freespacemap::FreeSpaceMap, layout::Layout, schema::Schema},
     tx::transaction::{Transaction, TransactionError},
 };
 
@@ -51,6 +51,10 @@ impl MetadataMgr {
         self.tblmgr.get_layout(tblname, tx)
     }
 
+    pub fn free_space(&self) -> Arc<FreeSpaceMap> {
+        self.tblmgr.free_space()
+    }
+
     pub fn create_view(
         &self,
         viewname: &str,
@@ -8,6 +8,7 @@ mod tests {
     use rand::{distributions::Uniform, prelude::Distribution};
 
     use crate::{
+        file::durability::Durability,
         metadata::metadatamgr::MetadataMgr,
         query::updatescan::UpdateScanControl,
         record::{
@@ -19,7 +20,7 @@ mod tests {
 
     #[test]
     fn metadatamgrtest() {
-        let db = SimpleDB::with_params("metadatamgrtest", 400, 8).unwrap();
+        let db = SimpleDB::with_params("metadatamgrtest", 400, 8, Durability::NoSync).unwrap();
         let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
         let mut mdm = MetadataMgr::new(true, tx.clone()).unwrap();
 
@@ -32,7 +33,7 @@ mod tests {
         let layout = mdm.get_layout("MyTable", tx.clone()).unwrap();
         let size = layout.slot_size();
         let sch2 = layout.schema();
-        assert_eq!(size, 21);
+        assert_eq!(size, 48);
         for (i, fldname) in sch2.fields().iter().enumerate() {
             match sch2.type_(fldname) {
                 Type::Integer => assert_eq!(i, 0),
@@ -40,6 +41,7 @@ mod tests {
                     assert_eq!(i, 1);
                     assert_eq!(sch2.length(&fldname), 9);
                 }
+                _ => panic!("unexpected field type"),
             };
         }
 
@@ -52,8 +54,9 @@ mod tests {
             ts.set_int("A", n).unwrap();
             ts.set_string("B", &format!("rec{}", n)).unwrap();
         }
+        let size = tx.lock().unwrap().size("MyTable.tbl").unwrap();
         let si = mdm.get_stat_info("MyTable", layout, tx.clone()).unwrap();
-        assert_eq!(si.blocks_accessed(), 3);
+        assert_eq!(si.blocks_accessed(), size);
         assert_eq!(si.records_output(), 50);
         assert_eq!(si.distinct_values("A"), 17);
         assert_eq!(si.distinct_values("B"), 17);
@@ -76,7 +79,7 @@ mod tests {
         assert_eq!(ii.distinct_values("B"), 17);
 
         ii = idxmap.get("B").unwrap();
-        assert_eq!(ii.blocks_accessed(), 1);
+        assert_eq!(ii.blocks_accessed(), 2);
         assert_eq!(ii.records_output(), 2);
         assert_eq!(ii.distinct_values("A"), 17);
         assert_eq!(ii.distinct_values("B"), 1);
@@ -0,0 +1,77 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    buffer::bufferstats::BufferStats,
+    query::constant::Constant,
+    record::{layout::Layout, schema::Schema},
+};
+
+use super::tablemgr::TableMgr;
+
+pub struct SysTableMgr {}
+
+impl SysTableMgr {
+    pub const SYS_BUFFERS: &'static str = "sys_buffers";
+    pub const SYS_BUFFER_FILES: &'static str = "sys_buffer_files";
+    const MAX_FILENAME: usize = TableMgr::MAX_NAME + ".".len() + (usize::MAX.ilog10() + 1) as usize;
+
+    pub fn get_layout(tblname: &str) -> Option<Layout> {
+        let mut sch = Schema::new();
+        match tblname {
+            SysTableMgr::SYS_BUFFERS => {
+                sch.add_long_field("hits");
+                sch.add_long_field("misses");
+                sch.add_long_field("evictions");
+                sch.add_long_field("dirty");
+                sch.add_long_field("pinwaits");
+                sch.add_long_field("pintimeouts");
+            }
+            SysTableMgr::SYS_BUFFER_FILES => {
+                sch.add_string_field("filename", SysTableMgr::MAX_FILENAME);
+                sch.add_long_field("resident");
+            }
+            _ => return None,
+        }
+        Some(Layout::new(Arc::new(sch)))
+    }
+
+    pub fn get_rows(tblname: &str, stats: &BufferStats) -> Vec<HashMap<String, Constant>> {
+        match tblname {
+            SysTableMgr::SYS_BUFFERS => vec![HashMap::from([
+                ("hits".to_string(), SysTableMgr::counter(stats.hits())),
+                ("misses".to_string(), SysTableMgr::counter(stats.misses())),
+                (
+                    "evictions".to_string(),
+                    SysTableMgr::counter(stats.evictions()),
+                ),
+                ("dirty".to_string(), SysTableMgr::counter(stats.dirty())),
+                (
+                    "pinwaits".to_string(),
+                    SysTableMgr::counter(stats.pin_waits()),
+                ),
+                (
+                    "pintimeouts".to_string(),
+                    SysTableMgr::counter(stats.pin_timeouts()),
+                ),
+            ])],
+            SysTableMgr::SYS_BUFFER_FILES => {
+                let mut files: Vec<_> = stats.resident().iter().collect();
+                files.sort();
+                files
+                    .into_iter()
+                    .map(|(filename, resident)| {
+                        HashMap::from([
+                            ("filename".to_string(), Constant::with_string(filename)),
+                            ("resident".to_string(), SysTableMgr::counter(*resident)),
+                        ])
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn counter(n: usize) -> Constant {
+        Constant::with_long(i64::try_from(n).unwrap_or(i64::MAX))
+    }
+}
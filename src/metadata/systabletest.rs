@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        file::durability::Durability,
+        plan::plan::PlanControl,
+        query::scan::ScanControl,
+        server::{dbconfig::DbConfig, simpledb::SimpleDB},
+    };
+
+    #[test]
+    fn systabletest() {
+        let config = DbConfig::new().with_durability(Durability::NoSync);
+        let db = SimpleDB::with_config("systabletest", &config).unwrap();
+        let planner = db.planner().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let cmd = "create table T1(A int, B varchar(9))";
+        planner
+            .lock()
+            .unwrap()
+            .execute_update(cmd, tx.clone())
+            .unwrap();
+        for a in 0..20 {
+            let cmd = format!("insert into T1(A,B) values({0}, 'rec{0}')", a);
+            planner
+                .lock()
+                .unwrap()
+                .execute_update(&cmd, tx.clone())
+                .unwrap();
+        }
+
+        let stats = tx.lock().unwrap().buffer_stats();
+        assert!(stats.hits() > 0);
+        assert!(stats.misses() > 0);
+        assert!(stats.dirty() > 0);
+        assert_eq!(Some(&1), stats.resident().get("t1.tbl"));
+
+        let qry = "select hits, misses, evictions, dirty, pinwaits, pintimeouts from sys_buffers";
+        let p = planner
+            .lock()
+            .unwrap()
+            .create_query_plan(qry, tx.clone())
+            .unwrap();
+        let mut s = p.open().unwrap();
+        assert!(s.next().unwrap());
+        assert!(s.get_val("hits").unwrap().as_long().unwrap() >= stats.hits() as i64);
+        assert!(s.get_val("misses").unwrap().as_long().unwrap() >= stats.misses() as i64);
+        assert_eq!(
+            Some(stats.dirty() as i64),
+            s.get_val("dirty").unwrap().as_long()
+        );
+        assert_eq!(Some(0), s.get_val("pintimeouts").unwrap().as_long());
+        assert!(!s.next().unwrap());
+        s.close().unwrap();
+
+        let qry = "select filename, resident from sys_buffer_files";
+        let p = planner
+            .lock()
+            .unwrap()
+            .create_query_plan(qry, tx.clone())
+            .unwrap();
+        let mut s = p.open().unwrap();
+        let mut resident = HashMap::new();
+        while s.next().unwrap() {
+            resident.insert(
+                s.get_string("filename").unwrap(),
+                s.get_val("resident").unwrap().as_long().unwrap() as usize,
+            );
+        }
+        s.close().unwrap();
+        assert_eq!(stats.resident(), &resident);
+
+        let cmd = "delete from sys_buffers";
+        let count = planner
+            .lock()
+            .unwrap()
+            .execute_update(cmd, tx.clone())
+            .unwrap();
+        assert_eq!(0, count);
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("systabletest").unwrap();
+    }
+}
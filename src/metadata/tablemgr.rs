@@ -1,11 +1,9 @@
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::sync::{Arc, Mutex};
 
 use crate::{
     query::{scan::ScanControl, updatescan::UpdateScanControl},
     record::{
+        freespacemap::FreeSpaceMap,
         layout::Layout,
         schema::{Schema, Type},
         tablescan::TableScan,
@@ -16,6 +14,7 @@ use crate::{
 pub struct TableMgr {
     tcat_layout: Layout,
     fcat_layout: Layout,
+    free_space: Arc<FreeSpaceMap>,
 }
 
 impl TableMgr {
@@ -24,7 +23,6 @@ impl TableMgr {
     pub fn new(is_new: bool, tx: Arc<Mutex<Transaction>>) -> Result<TableMgr, TransactionError> {
         let mut tcat_schema = Schema::new();
         tcat_schema.add_string_field("tblname", TableMgr::MAX_NAME);
-        tcat_schema.add_int_field("slotsize");
         let ts = Arc::new(tcat_schema);
         let tcat_layout = Layout::new(ts.clone());
 
@@ -33,13 +31,13 @@ impl TableMgr {
         fcat_schema.add_string_field("fldname", TableMgr::MAX_NAME);
         fcat_schema.add_int_field("type");
         fcat_schema.add_int_field("length");
-        fcat_schema.add_int_field("offset");
         let fs = Arc::new(fcat_schema);
         let fcat_layout = Layout::new(fs.clone());
 
         let tm = TableMgr {
             tcat_layout,
             fcat_layout,
+            free_space: Arc::new(FreeSpaceMap::new()),
         };
 
         if is_new {
@@ -56,12 +54,9 @@ impl TableMgr {
         sch: Arc<Schema>,
         tx: Arc<Mutex<Transaction>>,
     ) -> Result<(), TransactionError> {
-        let layout = Layout::new(sch.clone());
-
         let mut tcat = TableScan::new(tx.clone(), "tblcat", self.tcat_layout.clone())?;
         tcat.insert()?;
         tcat.set_string("tblname", tblname)?;
-        tcat.set_int("slotsize", layout.slot_size() as i32)?;
         tcat.close()?;
 
         let mut fcat = TableScan::new(tx, "fldcat", self.fcat_layout.clone())?;
@@ -71,51 +66,47 @@ impl TableMgr {
             fcat.set_string("fldname", fldname)?;
             fcat.set_int("type", sch.type_(fldname) as i32)?;
             fcat.set_int("length", sch.length(fldname) as i32)?;
-            fcat.set_int("offset", layout.offset(fldname) as i32)?;
         }
         fcat.close()?;
 
         Ok(())
     }
 
+    pub fn free_space(&self) -> Arc<FreeSpaceMap> {
+        self.free_space.clone()
+    }
+
     pub fn get_layout(
         &self,
         tblname: &str,
         tx: Arc<Mutex<Transaction>>,
     ) -> Result<Layout, TransactionError> {
-        let mut size = None;
+        let mut found = false;
         let mut tcat = TableScan::new(tx.clone(), "tblcat", self.tcat_layout.clone())?;
         while tcat.next()? {
             if tcat.get_string("tblname")? == tblname {
-                size = Some(tcat.get_int("slotsize")? as usize);
+                found = true;
                 break;
             }
         }
         tcat.close()?;
 
         let mut sch = Schema::new();
-        let mut offsets = HashMap::new();
         let mut fcat = TableScan::new(tx, "fldcat", self.fcat_layout.clone())?;
         while fcat.next()? {
             if fcat.get_string("tblname")? == tblname {
                 let fldname = fcat.get_string("fldname")?;
                 let fldtype = fcat.get_int("type")?;
                 let fldlen = fcat.get_int("length")?;
-                let offset = fcat.get_int("offset")?;
-                offsets.insert(fldname.clone(), offset as usize);
-
-                let t = match fldtype {
-                    x if x == Type::Integer as i32 => Type::Integer,
-                    x if x == Type::Varchar as i32 => Type::Varchar,
-                    _ => return Err(TransactionError::General),
-                };
+
+                let t = Type::from_code(fldtype).ok_or(TransactionError::General)?;
                 sch.add_field(&fldname, t, fldlen as usize);
             }
         }
         fcat.close()?;
 
-        if let Some(size) = size {
-            return Ok(Layout::with_metadata(Arc::new(sch), offsets, size));
+        if found {
+            return Ok(Layout::new(Arc::new(sch)));
         }
         Err(TransactionError::General)
     }
@@ -6,6 +6,7 @@ mod tests {
     };
 
     use crate::{
+        file::durability::Durability,
         metadata::tablemgr::TableMgr,
         record::schema::{Schema, Type},
         server::simpledb::SimpleDB,
@@ -13,7 +14,7 @@ mod tests {
 
     #[test]
     fn tablemgrtest() {
-        let db = SimpleDB::with_params("tblmgrtest", 400, 8).unwrap();
+        let db = SimpleDB::with_params("tblmgrtest", 400, 8, Durability::NoSync).unwrap();
         let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
         let tm = TableMgr::new(true, tx.clone()).unwrap();
 
@@ -26,7 +27,7 @@ mod tests {
         let layout = tm.get_layout("MyTable", tx.clone()).unwrap();
         let size = layout.slot_size();
         let sch2 = layout.schema();
-        assert_eq!(size, 21);
+        assert_eq!(size, 48);
         for (i, fldname) in sch2.fields().iter().enumerate() {
             match sch2.type_(&fldname) {
                 Type::Integer => assert_eq!(i, 0),
@@ -34,6 +35,7 @@ mod tests {
                     assert_eq!(i, 1);
                     assert_eq!(sch2.length(&fldname), 9);
                 }
+                _ => panic!("unexpected field type"),
             };
         }
         tx.lock().unwrap().commit().unwrap();
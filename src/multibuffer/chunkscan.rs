@@ -4,7 +4,7 @@ use crate::{
     buffer::buffermgr::AbortError,
     file::blockid::BlockId,
     query::{constant::Constant, scan::ScanControl},
-    record::{layout::Layout, recordpage::RecordPage, schema::Type},
+    record::{layout::Layout, recordpage::RecordPage},
     tx::transaction::{Transaction, TransactionError},
 };
 
@@ -109,10 +109,12 @@ impl ScanControl for ChunkScan {
     }
 
     fn get_val(&mut self, fldname: &str) -> Result<Constant, TransactionError> {
-        match self.layout.schema().type_(fldname) {
-            Type::Integer => Ok(Constant::with_int(self.get_int(fldname)?)),
-            Type::Varchar => Ok(Constant::with_string(&self.get_string(fldname)?)),
+        if let Some(rp) = self.buffs.get_mut(self.rpidx) {
+            if let Some(currentslot) = self.currentslot {
+                return rp.get_val(currentslot, fldname);
+            }
         }
+        Err(TransactionError::General)
     }
 
     fn has_field(&self, fldname: &str) -> bool {
@@ -34,10 +34,13 @@ impl TablePlanner {
     ) -> Result<TablePlanner, TransactionError> {
         let myplan = TablePlan::new(tx.clone(), tblname, mdm.clone())?;
         let myschema = myplan.schema();
-        let indexes = mdm.lock().unwrap().get_index_info(tblname, tx.clone())?;
+        let mut indexes = mdm.lock().unwrap().get_index_info(tblname, tx.clone())?;
+        if tx.lock().unwrap().has_snapshot() {
+            indexes.clear();
+        }
         Ok(TablePlanner {
             myplan,
-            mypred,
+            mypred: mypred.coerce(&myschema),
             myschema,
             indexes,
             tx,
@@ -10,3 +10,7 @@ pub mod modifydata;
 pub mod parser;
 pub mod parsertest;
 pub mod querydata;
+pub mod releasesavepointdata;
+pub mod rollbacktosavepointdata;
+pub mod savepointdata;
+pub mod settransactiondata;
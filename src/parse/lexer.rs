@@ -6,13 +6,15 @@ use super::badsyntaxerror::BadSyntaxError;
 enum TokenType {
     Delim,
     IntConstant,
+    DoubleConstant,
     StringConstant,
     Keyword,
     Id,
 }
 
 struct Token {
-    nval: Option<i32>,
+    nval: Option<i64>,
+    dval: Option<f64>,
     sval: Option<String>,
     ttype: TokenType,
 }
@@ -25,9 +27,19 @@ pub struct Lexer {
 }
 
 impl Token {
-    fn with_int(nval: i32, ttype: TokenType) -> Token {
+    fn with_int(nval: i64, ttype: TokenType) -> Token {
         Token {
             nval: Some(nval),
+            dval: None,
+            sval: None,
+            ttype,
+        }
+    }
+
+    fn with_double(dval: f64, ttype: TokenType) -> Token {
+        Token {
+            nval: None,
+            dval: Some(dval),
             sval: None,
             ttype,
         }
@@ -36,15 +48,20 @@ impl Token {
     fn with_string(sval: String, ttype: TokenType) -> Token {
         Token {
             nval: None,
+            dval: None,
             sval: Some(sval),
             ttype,
         }
     }
 
-    fn nval(&self) -> Option<i32> {
+    fn nval(&self) -> Option<i64> {
         self.nval
     }
 
+    fn dval(&self) -> Option<f64> {
+        self.dval
+    }
+
     fn sval(&self) -> Option<String> {
         self.sval.clone()
     }
@@ -57,8 +74,31 @@ impl Token {
 impl Lexer {
     pub fn new(s: &str) -> Lexer {
         let keywords = HashSet::from([
-            "select", "from", "where", "and", "insert", "into", "values", "delete", "update",
-            "set", "create", "table", "int", "varchar", "view", "as", "index", "on",
+            "select",
+            "from",
+            "where",
+            "and",
+            "or",
+            "between",
+            "in",
+            "like",
+            "insert",
+            "into",
+            "values",
+            "delete",
+            "update",
+            "set",
+            "create",
+            "table",
+            "int",
+            "varchar",
+            "view",
+            "as",
+            "index",
+            "on",
+            "null",
+            "is",
+            "not",
         ])
         .iter()
         .map(|s| s.to_string())
@@ -93,6 +133,13 @@ impl Lexer {
         false
     }
 
+    pub fn match_double_constant(&self) -> bool {
+        if let Some(token) = &self.token {
+            return token.ttype() == TokenType::DoubleConstant;
+        }
+        false
+    }
+
     pub fn match_string_constant(&self) -> bool {
         if let Some(token) = &self.token {
             return token.ttype() == TokenType::StringConstant;
@@ -112,6 +159,20 @@ impl Lexer {
         false
     }
 
+    pub fn match_word(&self, w: &str) -> bool {
+        if let Some(token) = &self.token {
+            return token.ttype() == TokenType::Id && token.sval().as_deref() == Some(w);
+        }
+        false
+    }
+
+    pub fn match_string_after(&self) -> bool {
+        let rest = self.chars[self.i..]
+            .iter()
+            .find(|c| !self.is_whitespce_char(**c));
+        matches!(rest, Some('\'') | Some('"'))
+    }
+
     pub fn match_id(&self) -> bool {
         if let Some(token) = &self.token {
             return token.ttype() == TokenType::Id;
@@ -128,6 +189,10 @@ impl Lexer {
     }
 
     pub fn eat_int_constant(&mut self) -> Result<i32, BadSyntaxError> {
+        i32::try_from(self.eat_long_constant()?).map_err(|_| BadSyntaxError)
+    }
+
+    pub fn eat_long_constant(&mut self) -> Result<i64, BadSyntaxError> {
         if !self.match_int_constant() {
             return Err(BadSyntaxError);
         }
@@ -140,6 +205,19 @@ impl Lexer {
         Err(BadSyntaxError)
     }
 
+    pub fn eat_double_constant(&mut self) -> Result<f64, BadSyntaxError> {
+        if !self.match_double_constant() {
+            return Err(BadSyntaxError);
+        }
+        if let Some(token) = &self.token {
+            if let Some(d) = token.dval() {
+                self.next_token();
+                return Ok(d);
+            }
+        }
+        Err(BadSyntaxError)
+    }
+
     pub fn eat_string_constant(&mut self) -> Result<String, BadSyntaxError> {
         if !self.match_string_constant() {
             return Err(BadSyntaxError);
@@ -161,6 +239,14 @@ impl Lexer {
         Ok(())
     }
 
+    pub fn eat_word(&mut self, w: &str) -> Result<(), BadSyntaxError> {
+        if !self.match_word(w) {
+            return Err(BadSyntaxError);
+        }
+        self.next_token();
+        Ok(())
+    }
+
     pub fn eat_id(&mut self) -> Result<String, BadSyntaxError> {
         if !self.match_id() {
             return Err(BadSyntaxError);
@@ -211,26 +297,40 @@ impl Lexer {
             return;
         }
 
+        let start = self.i;
         let mut is_negative = false;
         if self.chars[self.i] == '-' {
             is_negative = true;
             self.i += 1;
         }
-        let mut nval = 0;
         let mut is_number = false;
-        while self.is_number(self.chars[self.i]) {
+        while self.i < self.chars.len() && self.is_number(self.chars[self.i]) {
             is_number = true;
-            nval = 10 * nval + (self.chars[self.i] as i32 - '0' as i32);
             self.i += 1;
-            if self.i >= self.chars.len() {
-                break;
+        }
+        let mut is_double = false;
+        if is_number
+            && self.i + 1 < self.chars.len()
+            && self.chars[self.i] == '.'
+            && self.is_number(self.chars[self.i + 1])
+        {
+            is_double = true;
+            self.i += 1;
+            while self.i < self.chars.len() && self.is_number(self.chars[self.i]) {
+                self.i += 1;
             }
         }
         if is_number {
-            if is_negative {
-                nval = -nval;
-            }
-            self.token = Some(Token::with_int(nval, TokenType::IntConstant));
+            let text: String = self.chars[start..self.i].iter().collect();
+            self.token = if is_double {
+                text.parse()
+                    .ok()
+                    .map(|d| Token::with_double(d, TokenType::DoubleConstant))
+            } else {
+                text.parse()
+                    .ok()
+                    .map(|n| Token::with_int(n, TokenType::IntConstant))
+            };
             return;
         }
         if is_negative {
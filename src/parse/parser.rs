@@ -1,14 +1,24 @@
 use enum_dispatch::enum_dispatch;
 
 use crate::{
-    query::{constant::Constant, expression::Expression, predicate::Predicate, term::Term},
+    query::{
+        constant::Constant,
+        datetime::{parse_date, parse_timestamp},
+        expression::Expression,
+        operator::Operator,
+        predicate::Predicate,
+        term::Term,
+    },
     record::schema::Schema,
+    tx::isolationlevel::IsolationLevel,
 };
 
 use super::{
     badsyntaxerror::BadSyntaxError, createindexdata::CreateIndexData,
     createtabledata::CreateTableData, createviewdata::CreateViewData, deletedata::DeleteData,
     insertdata::InsertData, lexer::Lexer, modifydata::ModifyData, querydata::QueryData,
+    releasesavepointdata::ReleaseSavepointData, rollbacktosavepointdata::RollbackToSavepointData,
+    savepointdata::SavepointData, settransactiondata::SetTransactionData,
 };
 
 #[enum_dispatch(Object)]
@@ -22,6 +32,10 @@ pub enum Object {
     CreateTable(CreateTableData),
     CreateView(CreateViewData),
     CreateIndex(CreateIndexData),
+    SetTransaction(SetTransactionData),
+    Savepoint(SavepointData),
+    RollbackToSavepoint(RollbackToSavepointData),
+    ReleaseSavepoint(ReleaseSavepointData),
 }
 
 pub struct Parser {
@@ -39,31 +53,113 @@ impl Parser {
     }
 
     pub fn constant(&mut self) -> Result<Constant, BadSyntaxError> {
+        if self.lex.match_keyword("null") {
+            self.lex.eat_keyword("null")?;
+            return Ok(Constant::null());
+        }
+        if self.lex.match_word("true") {
+            self.lex.eat_word("true")?;
+            return Ok(Constant::with_bool(true));
+        }
+        if self.lex.match_word("false") {
+            self.lex.eat_word("false")?;
+            return Ok(Constant::with_bool(false));
+        }
+        if self.lex.match_word("date") {
+            self.lex.eat_word("date")?;
+            let days = parse_date(&self.lex.eat_string_constant()?).ok_or(BadSyntaxError)?;
+            return Ok(Constant::with_date(days));
+        }
+        if self.lex.match_word("timestamp") {
+            self.lex.eat_word("timestamp")?;
+            let micros = parse_timestamp(&self.lex.eat_string_constant()?).ok_or(BadSyntaxError)?;
+            return Ok(Constant::with_timestamp(micros));
+        }
         if self.lex.match_string_constant() {
             return Ok(Constant::with_string(&self.lex.eat_string_constant()?));
         }
-        Ok(Constant::with_int(self.lex.eat_int_constant()?))
+        if self.lex.match_double_constant() {
+            return Ok(Constant::with_double(self.lex.eat_double_constant()?));
+        }
+        let n = self.lex.eat_long_constant()?;
+        match i32::try_from(n) {
+            Ok(ival) => Ok(Constant::with_int(ival)),
+            Err(_) => Ok(Constant::with_long(n)),
+        }
     }
 
     pub fn expression(&mut self) -> Result<Expression, BadSyntaxError> {
-        if self.lex.match_id() {
+        if self.lex.match_id() && !self.match_literal() {
             return Ok(Expression::with_string(&self.field()?));
         }
         Ok(Expression::with_constant(self.constant()?))
     }
 
+    fn match_literal(&self) -> bool {
+        let typed = self.lex.match_word("date") || self.lex.match_word("timestamp");
+        self.lex.match_word("true")
+            || self.lex.match_word("false")
+            || (typed && self.lex.match_string_after())
+    }
+
     pub fn term(&mut self) -> Result<Term, BadSyntaxError> {
         let lhs = self.expression()?;
-        self.lex.eat_delim('=')?;
+        if self.lex.match_keyword("is") {
+            self.lex.eat_keyword("is")?;
+            let mut op = Operator::IsNull;
+            if self.lex.match_keyword("not") {
+                self.lex.eat_keyword("not")?;
+                op = Operator::IsNotNull;
+            }
+            self.lex.eat_keyword("null")?;
+            return Ok(Term::with_null_check(lhs, op));
+        }
+        let mut negated = false;
+        if self.lex.match_keyword("not") {
+            self.lex.eat_keyword("not")?;
+            negated = true;
+        }
+        let negate = |op: Operator| if negated { op.negate() } else { op };
+        if self.lex.match_keyword("between") {
+            self.lex.eat_keyword("between")?;
+            let low = self.expression()?;
+            self.lex.eat_keyword("and")?;
+            let high = self.expression()?;
+            return Ok(Term::with_between(
+                lhs,
+                negate(Operator::Between),
+                low,
+                high,
+            ));
+        }
+        if self.lex.match_keyword("in") {
+            self.lex.eat_keyword("in")?;
+            self.lex.eat_delim('(')?;
+            let list = self.expression_list()?;
+            self.lex.eat_delim(')')?;
+            return Ok(Term::with_list(lhs, negate(Operator::In), list));
+        }
+        if self.lex.match_keyword("like") {
+            self.lex.eat_keyword("like")?;
+            let rhs = self.expression()?;
+            return Ok(Term::with_operator(lhs, negate(Operator::Like), rhs));
+        }
+        if negated {
+            return Err(BadSyntaxError);
+        }
+        let op = self.comparison()?;
         let rhs = self.expression()?;
-        Ok(Term::new(lhs, rhs))
+        match op {
+            Operator::Equal => Ok(Term::new(lhs, rhs)),
+            op => Ok(Term::with_operator(lhs, op, rhs)),
+        }
     }
 
     pub fn predicate(&mut self) -> Result<Predicate, BadSyntaxError> {
-        let mut pred = Predicate::with_term(self.term()?);
-        if self.lex.match_keyword("and") {
-            self.lex.eat_keyword("and")?;
-            pred.conjoin_with(self.predicate()?);
+        let mut pred = self.conjunction()?;
+        while self.lex.match_keyword("or") {
+            self.lex.eat_keyword("or")?;
+            pred.disjoin_with(self.conjunction()?);
         }
         Ok(pred)
     }
@@ -81,6 +177,61 @@ impl Parser {
         Ok(QueryData::new(fields, tables, pred))
     }
 
+    fn conjunction(&mut self) -> Result<Predicate, BadSyntaxError> {
+        let mut pred = self.factor()?;
+        while self.lex.match_keyword("and") {
+            self.lex.eat_keyword("and")?;
+            pred.conjoin_with(self.factor()?);
+        }
+        Ok(pred)
+    }
+
+    fn factor(&mut self) -> Result<Predicate, BadSyntaxError> {
+        if self.lex.match_keyword("not") {
+            self.lex.eat_keyword("not")?;
+            let mut pred = self.factor()?;
+            pred.negate();
+            return Ok(pred);
+        }
+        if self.lex.match_delim('(') {
+            self.lex.eat_delim('(')?;
+            let pred = self.predicate()?;
+            self.lex.eat_delim(')')?;
+            return Ok(pred);
+        }
+        Ok(Predicate::with_term(self.term()?))
+    }
+
+    fn comparison(&mut self) -> Result<Operator, BadSyntaxError> {
+        if self.lex.match_delim('=') {
+            self.lex.eat_delim('=')?;
+            return Ok(Operator::Equal);
+        }
+        if self.lex.match_delim('!') {
+            self.lex.eat_delim('!')?;
+            self.lex.eat_delim('=')?;
+            return Ok(Operator::NotEqual);
+        }
+        if self.lex.match_delim('<') {
+            self.lex.eat_delim('<')?;
+            if self.lex.match_delim('=') {
+                self.lex.eat_delim('=')?;
+                return Ok(Operator::LessThanOrEqual);
+            }
+            if self.lex.match_delim('>') {
+                self.lex.eat_delim('>')?;
+                return Ok(Operator::NotEqual);
+            }
+            return Ok(Operator::LessThan);
+        }
+        self.lex.eat_delim('>')?;
+        if self.lex.match_delim('=') {
+            self.lex.eat_delim('=')?;
+            return Ok(Operator::GreaterThanOrEqual);
+        }
+        Ok(Operator::GreaterThan)
+    }
+
     fn select_list(&mut self) -> Result<Vec<String>, BadSyntaxError> {
         let mut l = vec![self.field()?];
         if self.lex.match_delim(',') {
@@ -106,6 +257,14 @@ impl Parser {
             return Ok(self.delete()?.into());
         } else if self.lex.match_keyword("update") {
             return Ok(self.modify()?.into());
+        } else if self.lex.match_keyword("set") {
+            return Ok(self.set_transaction()?.into());
+        } else if self.lex.match_word("savepoint") {
+            return Ok(self.savepoint()?.into());
+        } else if self.lex.match_word("rollback") {
+            return Ok(self.rollback_to_savepoint()?.into());
+        } else if self.lex.match_word("release") {
+            return Ok(self.release_savepoint()?.into());
         }
         self.create()
     }
@@ -164,6 +323,15 @@ impl Parser {
         Ok(l)
     }
 
+    fn expression_list(&mut self) -> Result<Vec<Expression>, BadSyntaxError> {
+        let mut l = vec![self.expression()?];
+        if self.lex.match_delim(',') {
+            self.lex.eat_delim(',')?;
+            l.extend(self.expression_list()?);
+        }
+        Ok(l)
+    }
+
     pub fn modify(&mut self) -> Result<ModifyData, BadSyntaxError> {
         self.lex.eat_keyword("update")?;
         let tblname = self.lex.eat_id()?;
@@ -208,6 +376,27 @@ impl Parser {
         if self.lex.match_keyword("int") {
             self.lex.eat_keyword("int")?;
             schema.add_int_field(fldname);
+        } else if self.lex.match_word("bigint") {
+            self.lex.eat_word("bigint")?;
+            schema.add_long_field(fldname);
+        } else if self.lex.match_word("boolean") {
+            self.lex.eat_word("boolean")?;
+            schema.add_bool_field(fldname);
+        } else if self.lex.match_word("double") {
+            self.lex.eat_word("double")?;
+            schema.add_double_field(fldname);
+        } else if self.lex.match_word("date") {
+            self.lex.eat_word("date")?;
+            schema.add_date_field(fldname);
+        } else if self.lex.match_word("timestamp") {
+            self.lex.eat_word("timestamp")?;
+            schema.add_timestamp_field(fldname);
+        } else if self.lex.match_word("text") {
+            self.lex.eat_word("text")?;
+            schema.add_text_field(fldname);
+        } else if self.lex.match_word("blob") {
+            self.lex.eat_word("blob")?;
+            schema.add_blob_field(fldname);
         } else {
             self.lex.eat_keyword("varchar")?;
             self.lex.eat_delim('(')?;
@@ -236,4 +425,50 @@ impl Parser {
         self.lex.eat_delim(')')?;
         Ok(CreateIndexData::new(&idxname, &tblname, &fldname))
     }
+
+    pub fn set_transaction(&mut self) -> Result<SetTransactionData, BadSyntaxError> {
+        self.lex.eat_keyword("set")?;
+        self.lex.eat_word("transaction")?;
+        self.lex.eat_word("isolation")?;
+        self.lex.eat_word("level")?;
+        let level = if self.lex.match_word("read") {
+            self.lex.eat_word("read")?;
+            if self.lex.match_word("uncommitted") {
+                self.lex.eat_word("uncommitted")?;
+                IsolationLevel::ReadUncommitted
+            } else {
+                self.lex.eat_word("committed")?;
+                IsolationLevel::ReadCommitted
+            }
+        } else if self.lex.match_word("repeatable") {
+            self.lex.eat_word("repeatable")?;
+            self.lex.eat_word("read")?;
+            IsolationLevel::RepeatableRead
+        } else {
+            self.lex.eat_word("serializable")?;
+            IsolationLevel::Serializable
+        };
+        Ok(SetTransactionData::new(level))
+    }
+
+    pub fn savepoint(&mut self) -> Result<SavepointData, BadSyntaxError> {
+        self.lex.eat_word("savepoint")?;
+        let name = self.lex.eat_id()?;
+        Ok(SavepointData::new(&name))
+    }
+
+    pub fn rollback_to_savepoint(&mut self) -> Result<RollbackToSavepointData, BadSyntaxError> {
+        self.lex.eat_word("rollback")?;
+        self.lex.eat_word("to")?;
+        self.lex.eat_word("savepoint")?;
+        let name = self.lex.eat_id()?;
+        Ok(RollbackToSavepointData::new(&name))
+    }
+
+    pub fn release_savepoint(&mut self) -> Result<ReleaseSavepointData, BadSyntaxError> {
+        self.lex.eat_word("release")?;
+        self.lex.eat_word("savepoint")?;
+        let name = self.lex.eat_id()?;
+        Ok(ReleaseSavepointData::new(&name))
+    }
 }
@@ -11,11 +11,41 @@ mod tests {
             ("update x set a = b where c = 3", true),
             ("insert into x (a, b, c) values (3, 'glop', 4)", true),
             ("create table x ( a varchar(3), b int, c varchar(2) )", true),
+            ("set transaction isolation level read committed", true),
+            ("set transaction isolation level repeatable read", true),
+            ("savepoint s1", true),
+            ("rollback to savepoint s1", true),
+            ("release savepoint s1", true),
+            ("select a from x where b is null", true),
+            ("select a from x where b is not null and c = 2", true),
+            ("insert into x (a, b) values (null, 'glop')", true),
+            ("update x set a = null where b = 3", true),
+            ("select a from x where b < 3 or not (c >= 2 and d <> 'x')", true),
+            ("select a from x where b between 1 and 5 and c not in (1, 2, 3)", true),
+            ("select a from x where b like 'ab%' or b not like '_c'", true),
+            ("select a from x where b != 3 and c <= 4 and d > 1", true),
+            ("create table x ( date date, text text, double double )", true),
+            ("select date from x where date = date '2024-01-01' and b = true", true),
+            ("insert into x (date, b) values (timestamp '2024-01-01 10:00', false)", true),
+            ("update x set text = 'glop' where double > 1.5", true),
+            ("create table x ( level int, read varchar(3) )", true),
+            ("select level, read from x where level = 1", true),
+            ("update x set read = 'y' where level > 1", true),
+            ("select to, release from savepoint where rollback = 1", true),
+            ("insert into x (to, savepoint) values (1, 2)", true),
             ("select from x", false),
             ("select x x from x", false),
             ("select a from where b=3", false),
             ("select a from y where b -=3", false),
             ("select a from y where", false),
+            ("set transaction isolation level read", false),
+            ("rollback to s1", false),
+            ("select a from x where b is 3", false),
+            ("select a from x where b not null", false),
+            ("select a from x where (b = 3", false),
+            ("select a from x where b in ()", false),
+            ("select a from x where b not = 3", false),
+            ("select a from x where b between 1", false),
         ];
         for (s, b) in ss.iter() {
             let mut p = Parser::new(&s.trim_end());
@@ -0,0 +1,19 @@
+use super::parser::ObjectControl;
+
+pub struct ReleaseSavepointData {
+    name: String,
+}
+
+impl ReleaseSavepointData {
+    pub fn new(name: &str) -> ReleaseSavepointData {
+        ReleaseSavepointData {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl ObjectControl for ReleaseSavepointData {}
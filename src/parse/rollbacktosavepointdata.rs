@@ -0,0 +1,19 @@
+use super::parser::ObjectControl;
+
+pub struct RollbackToSavepointData {
+    name: String,
+}
+
+impl RollbackToSavepointData {
+    pub fn new(name: &str) -> RollbackToSavepointData {
+        RollbackToSavepointData {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl ObjectControl for RollbackToSavepointData {}
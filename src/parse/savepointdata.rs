@@ -0,0 +1,19 @@
+use super::parser::ObjectControl;
+
+pub struct SavepointData {
+    name: String,
+}
+
+impl SavepointData {
+    pub fn new(name: &str) -> SavepointData {
+        SavepointData {
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl ObjectControl for SavepointData {}
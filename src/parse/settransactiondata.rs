@@ -0,0 +1,19 @@
+use crate::tx::isolationlevel::IsolationLevel;
+
+use super::parser::ObjectControl;
+
+pub struct SetTransactionData {
+    level: IsolationLevel,
+}
+
+impl SetTransactionData {
+    pub fn new(level: IsolationLevel) -> SetTransactionData {
+        SetTransactionData { level }
+    }
+
+    pub fn level(&self) -> IsolationLevel {
+        self.level
+    }
+}
+
+impl ObjectControl for SetTransactionData {}
@@ -42,7 +42,7 @@ mod tests {
         let pred = Predicate::with_term(t);
         let p4 = SelectPlan::new(p3.clone().into(), pred);
 
-        let e = [(9, 1), (3, 1), (27, 10), (6, 10)];
+        let e = [(9, 2), (3, 1), (27, 11), (6, 11)];
         assert_stats(1, &p1.into(), &e);
         assert_stats(2, &p2.into(), &e);
         assert_stats(3, &p3.clone().into(), &e);
@@ -50,6 +50,22 @@ impl Planner {
             Object::CreateTable(object) => self.uplanner.execute_create_table(&object, tx)?,
             Object::CreateView(object) => self.uplanner.execute_create_view(&object, tx)?,
             Object::CreateIndex(object) => self.uplanner.execute_create_index(&object, tx)?,
+            Object::SetTransaction(object) => {
+                tx.lock().unwrap().set_isolation_level(object.level());
+                0
+            }
+            Object::Savepoint(object) => {
+                tx.lock().unwrap().savepoint(&object.name())?;
+                0
+            }
+            Object::RollbackToSavepoint(object) => {
+                tx.lock().unwrap().rollback_to_savepoint(&object.name())?;
+                0
+            }
+            Object::ReleaseSavepoint(object) => {
+                tx.lock().unwrap().release_savepoint(&object.name())?;
+                0
+            }
         })
     }
 
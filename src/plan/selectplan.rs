@@ -16,6 +16,7 @@ pub struct SelectPlan {
 
 impl SelectPlan {
     pub fn new(p: Plan, pred: Predicate) -> SelectPlan {
+        let pred = pred.coerce(&p.schema());
         SelectPlan {
             p: Box::new(p),
             pred,
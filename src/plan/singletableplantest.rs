@@ -53,7 +53,7 @@ mod tests {
             .collect();
         let p4 = ProjectPlan::new(p3.clone().into(), c);
 
-        let e = [(9, 1), (2, 1), (0, 1), (0, 1)];
+        let e = [(9, 2), (2, 2), (0, 2), (0, 2)];
         assert_stats(1, &p1.into(), &e);
         assert_stats(2, &p2.clone().into(), &e);
         assert_stats(3, &p3.into(), &e);
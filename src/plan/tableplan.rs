@@ -1,9 +1,12 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
-    metadata::{metadatamgr::MetadataMgr, statinfo::StatInfo},
-    query::scan::Scan,
-    record::{layout::Layout, schema::Schema, tablescan::TableScan},
+    metadata::{metadatamgr::MetadataMgr, statinfo::StatInfo, systablemgr::SysTableMgr},
+    query::{constant::Constant, scan::Scan, systablescan::SysTableScan},
+    record::{freespacemap::FreeSpaceMap, layout::Layout, schema::Schema, tablescan::TableScan},
     tx::transaction::{Transaction, TransactionError},
 };
 
@@ -15,6 +18,8 @@ pub struct TablePlan {
     tx: Arc<Mutex<Transaction>>,
     layout: Layout,
     si: StatInfo,
+    sysrows: Option<Vec<HashMap<String, Constant>>>,
+    free_space: Option<Arc<FreeSpaceMap>>,
 }
 
 impl TablePlan {
@@ -23,23 +28,47 @@ impl TablePlan {
         tblname: &str,
         md: Arc<Mutex<MetadataMgr>>,
     ) -> Result<TablePlan, TransactionError> {
+        if let Some(layout) = SysTableMgr::get_layout(tblname) {
+            let stats = tx.lock().unwrap().buffer_stats();
+            let rows = SysTableMgr::get_rows(tblname, &stats);
+            return Ok(TablePlan {
+                tblname: tblname.to_string(),
+                tx,
+                layout,
+                si: StatInfo::new(1, rows.len()),
+                sysrows: Some(rows),
+                free_space: None,
+            });
+        }
         let layout = md.lock().unwrap().get_layout(tblname, tx.clone())?;
         let si = md
             .lock()
             .unwrap()
             .get_stat_info(tblname, layout.clone(), tx.clone())?;
+        let free_space = md.lock().unwrap().free_space();
         Ok(TablePlan {
             tblname: tblname.to_string(),
             tx,
             layout,
             si,
+            sysrows: None,
+            free_space: Some(free_space),
         })
     }
 }
 
 impl PlanControl for TablePlan {
     fn open(&self) -> Result<Scan, TransactionError> {
-        Ok(TableScan::new(self.tx.clone(), &self.tblname, self.layout.clone())?.into())
+        if let Some(rows) = &self.sysrows {
+            return Ok(SysTableScan::new(self.layout.schema(), rows.clone()).into());
+        }
+        Ok(TableScan::with_free_space(
+            self.tx.clone(),
+            &self.tblname,
+            self.layout.clone(),
+            self.free_space.clone(),
+        )?
+        .into())
     }
 
     fn blocks_accessed(&self) -> usize {
@@ -1,12 +1,19 @@
 pub mod constant;
+pub mod datetime;
 pub mod expression;
+pub mod nulltest;
+pub mod operator;
 pub mod predicate;
+pub mod predicatetest;
 pub mod productscan;
 pub mod projectscan;
 pub mod projecttest;
+pub mod querytest;
 pub mod scan;
 pub mod scantest1;
 pub mod scantest2;
 pub mod selectscan;
+pub mod systablescan;
 pub mod term;
+pub mod typestest;
 pub mod updatescan;
@@ -1,43 +1,255 @@
-use std::fmt;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
-#[derive(Clone, Eq, Hash, PartialEq, PartialOrd)]
-pub struct Constant {
-    ival: Option<i32>,
-    sval: Option<String>,
+use crate::record::schema::Type;
+
+use super::datetime::{
+    date_to_timestamp, format_date, format_timestamp, parse_date, parse_timestamp,
+};
+
+#[derive(Clone)]
+pub enum Constant {
+    Null,
+    Int(i32),
+    BigInt(i64),
+    Boolean(bool),
+    Double(f64),
+    Date(i32),
+    Timestamp(i64),
+    Varchar(String),
+    Blob(Vec<u8>),
 }
 
 impl fmt::Display for Constant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(ival) = self.ival {
-            return write!(f, "{}", ival);
+        match self {
+            Constant::Null => write!(f, "null"),
+            Constant::Int(ival) => write!(f, "{}", ival),
+            Constant::BigInt(lval) => write!(f, "{}", lval),
+            Constant::Boolean(bval) => write!(f, "{}", bval),
+            Constant::Double(dval) => write!(f, "{}", dval),
+            Constant::Date(days) => write!(f, "{}", format_date(*days)),
+            Constant::Timestamp(micros) => write!(f, "{}", format_timestamp(*micros)),
+            Constant::Varchar(sval) => write!(f, "{}", sval),
+            Constant::Blob(bytes) => {
+                for b in bytes {
+                    write!(f, "{:02x}", b)?;
+                }
+                Ok(())
+            }
         }
-        if let Some(sval) = &self.sval {
-            return write!(f, "{}", sval);
+    }
+}
+
+impl PartialEq for Constant {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Constant {}
+
+impl PartialOrd for Constant {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Constant {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+            .unwrap_or_else(|| self.rank().cmp(&other.rank()))
+    }
+}
+
+impl Hash for Constant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            Constant::Int(ival) => (*ival as i64).hash(state),
+            Constant::BigInt(lval) => lval.hash(state),
+            Constant::Double(dval) => {
+                if dval.fract() == 0.0 && *dval >= i64::MIN as f64 && *dval < i64::MAX as f64 {
+                    (*dval as i64).hash(state)
+                } else {
+                    dval.to_bits().hash(state)
+                }
+            }
+            Constant::Boolean(bval) => bval.hash(state),
+            Constant::Varchar(sval) => sval.hash(state),
+            Constant::Blob(bytes) => bytes.hash(state),
+            Constant::Date(_) | Constant::Timestamp(_) => self.as_timestamp().hash(state),
+            Constant::Null => {}
         }
-        write!(f, "")
     }
 }
 
+fn cmp_long_double(lval: i64, dval: f64) -> Ordering {
+    let bound = -(i64::MIN as f64);
+    if dval.is_nan() {
+        return (lval as f64).total_cmp(&dval);
+    }
+    if dval >= bound {
+        return Ordering::Less;
+    }
+    if dval < -bound {
+        return Ordering::Greater;
+    }
+    lval.cmp(&(dval.trunc() as i64))
+        .then_with(|| 0.0.partial_cmp(&dval.fract()).unwrap_or(Ordering::Equal))
+}
+
 impl Constant {
     pub fn with_int(ival: i32) -> Constant {
-        Constant {
-            ival: Some(ival),
-            sval: None,
-        }
+        Constant::Int(ival)
+    }
+
+    pub fn with_long(lval: i64) -> Constant {
+        Constant::BigInt(lval)
+    }
+
+    pub fn with_bool(bval: bool) -> Constant {
+        Constant::Boolean(bval)
+    }
+
+    pub fn with_double(dval: f64) -> Constant {
+        Constant::Double(dval)
+    }
+
+    pub fn with_date(days: i32) -> Constant {
+        Constant::Date(days)
+    }
+
+    pub fn with_timestamp(micros: i64) -> Constant {
+        Constant::Timestamp(micros)
     }
 
     pub fn with_string(sval: &str) -> Self {
-        Constant {
-            ival: None,
-            sval: Some(sval.to_string()),
-        }
+        Constant::Varchar(sval.to_string())
+    }
+
+    pub fn with_bytes(bytes: &[u8]) -> Constant {
+        Constant::Blob(bytes.to_vec())
+    }
+
+    pub fn null() -> Constant {
+        Constant::Null
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Constant::Null)
     }
 
     pub fn as_int(&self) -> Option<i32> {
-        self.ival
+        match self {
+            Constant::Int(ival) => Some(*ival),
+            Constant::BigInt(lval) => i32::try_from(*lval).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_long(&self) -> Option<i64> {
+        match self {
+            Constant::Int(ival) => Some(*ival as i64),
+            Constant::BigInt(lval) => Some(*lval),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Constant::Boolean(bval) => Some(*bval),
+            _ => None,
+        }
+    }
+
+    pub fn as_double(&self) -> Option<f64> {
+        match self {
+            Constant::Int(ival) => Some(*ival as f64),
+            Constant::BigInt(lval) => Some(*lval as f64),
+            Constant::Double(dval) => Some(*dval),
+            _ => None,
+        }
+    }
+
+    pub fn as_date(&self) -> Option<i32> {
+        match self {
+            Constant::Date(days) => Some(*days),
+            Constant::Varchar(sval) => parse_date(sval),
+            _ => None,
+        }
+    }
+
+    pub fn as_timestamp(&self) -> Option<i64> {
+        match self {
+            Constant::Date(days) => Some(date_to_timestamp(*days)),
+            Constant::Timestamp(micros) => Some(*micros),
+            Constant::Varchar(sval) => parse_timestamp(sval),
+            _ => None,
+        }
     }
 
     pub fn as_string(&self) -> Option<String> {
-        self.sval.clone()
+        match self {
+            Constant::Varchar(sval) => Some(sval.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Constant::Blob(bytes) => Some(bytes.clone()),
+            Constant::Varchar(sval) => Some(sval.as_bytes().to_vec()),
+            _ => None,
+        }
+    }
+
+    pub fn compare(&self, other: &Constant) -> Option<Ordering> {
+        match (self, other) {
+            (Constant::Double(a), Constant::Double(b)) => {
+                Some(a.partial_cmp(b).unwrap_or_else(|| a.total_cmp(b)))
+            }
+            (Constant::Double(dval), _) => Some(cmp_long_double(other.as_long()?, *dval).reverse()),
+            (_, Constant::Double(dval)) => Some(cmp_long_double(self.as_long()?, *dval)),
+            (Constant::Int(_) | Constant::BigInt(_), Constant::Int(_) | Constant::BigInt(_)) => {
+                Some(self.as_long().cmp(&other.as_long()))
+            }
+            (Constant::Date(a), Constant::Date(b)) => Some(a.cmp(b)),
+            (Constant::Boolean(a), Constant::Boolean(b)) => Some(a.cmp(b)),
+            (Constant::Varchar(a), Constant::Varchar(b)) => Some(a.cmp(b)),
+            (Constant::Blob(a), Constant::Blob(b)) => Some(a.cmp(b)),
+            (
+                Constant::Date(_) | Constant::Timestamp(_),
+                Constant::Date(_) | Constant::Timestamp(_),
+            ) => Some(self.as_timestamp().cmp(&other.as_timestamp())),
+            _ => None,
+        }
+    }
+
+    pub fn coerce_to(&self, type_: Type) -> Constant {
+        match (self, type_) {
+            (Constant::Varchar(sval), Type::Date) => parse_date(sval)
+                .map(Constant::Date)
+                .or_else(|| parse_timestamp(sval).map(Constant::Timestamp))
+                .unwrap_or_else(|| self.clone()),
+            (Constant::Varchar(sval), Type::Timestamp) => parse_timestamp(sval)
+                .map(Constant::Timestamp)
+                .unwrap_or_else(|| self.clone()),
+            _ => self.clone(),
+        }
+    }
+
+    fn rank(&self) -> usize {
+        match self {
+            Constant::Null => 0,
+            Constant::Boolean(_) => 1,
+            Constant::Int(_) | Constant::BigInt(_) | Constant::Double(_) => 2,
+            Constant::Date(_) | Constant::Timestamp(_) => 3,
+            Constant::Varchar(_) => 4,
+            Constant::Blob(_) => 5,
+        }
     }
 }
@@ -1,6 +1,9 @@
 use std::fmt;
 
-use crate::{record::schema::Schema, tx::transaction::TransactionError};
+use crate::{
+    record::schema::{Schema, Type},
+    tx::transaction::TransactionError,
+};
 
 use super::{constant::Constant, scan::ScanControl};
 
@@ -55,6 +58,13 @@ impl Expression {
         self.fldname.clone()
     }
 
+    pub fn coerce_to(&self, type_: Type) -> Expression {
+        match &self.val {
+            Some(val) => Expression::with_constant(val.coerce_to(type_)),
+            None => self.clone(),
+        }
+    }
+
     pub fn applies_to(&self, sch: &Schema) -> bool {
         if self.val.is_some() {
             return true;
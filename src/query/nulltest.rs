@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        api::{
+            connection::ConnectionControl,
+            driver::{DriverControl, SQLError},
+            embedded::embeddeddriver::EmbeddedDriver,
+            resultset::ResultSetControl,
+            statement::StatementControl,
+        },
+        query::querytest::tests::{assert_queries, execute_all},
+        tx::transaction::TransactionError,
+    };
+
+    #[test]
+    fn nulltest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("nulltest").unwrap();
+        let mut stmt = conn.create_statement();
+
+        let cmds = [
+            "create table T(A int, B varchar(9), C int)",
+            "create index tbidx on T(B)",
+            "insert into T(A, B, C) values (1, 'one', 10)",
+            "insert into T(A, B, C) values (2, null, 20)",
+            "insert into T(A, C) values (3, 30)",
+            "insert into T(A, B) values (4, 'four')",
+        ];
+        execute_all(&mut stmt, &cmds);
+
+        let queries = [
+            ("select A from T where B is null", vec![2, 3]),
+            ("select A from T where B is not null", vec![1, 4]),
+            ("select A from T where C is null", vec![4]),
+            ("select A from T where B = null", vec![]),
+        ];
+        assert_queries(&mut stmt, &queries, |rs| rs.get_int("a").unwrap());
+
+        let mut rs = stmt.execute_query("select A, B, C from T").unwrap();
+        while rs.next().unwrap() {
+            let a = rs.get_int("a").unwrap();
+            assert!(!rs.was_null());
+            let b = rs.get_string("b").unwrap();
+            assert_eq!(a == 2 || a == 3, rs.was_null());
+            if rs.was_null() {
+                assert_eq!("", b);
+            }
+            let c = rs.get_int("c").unwrap();
+            assert_eq!(a == 4, rs.was_null());
+            if rs.was_null() {
+                assert_eq!(0, c);
+            }
+        }
+        rs.close().unwrap();
+
+        let count = stmt
+            .execute_update("update T set B = null where A = 1")
+            .unwrap();
+        assert_eq!(1, count);
+        stmt.execute_update("delete from T where B is null")
+            .unwrap();
+        assert_queries(&mut stmt, &[("select A from T", vec![4])], |rs| {
+            rs.get_int("a").unwrap()
+        });
+
+        execute_all(
+            &mut stmt,
+            &[
+                "create table U(L bigint)",
+                "insert into U(L) values (9000000000)",
+                "insert into U(L) values (null)",
+            ],
+        );
+        let mut rs = stmt.execute_query("select L from U").unwrap();
+        while rs.next().unwrap() {
+            match rs.get_int("l") {
+                Ok(l) => assert!(rs.was_null() && l == 0),
+                Err(e) => {
+                    assert!(!rs.was_null());
+                    assert!(matches!(
+                        e,
+                        SQLError::Transaction(TransactionError::TypeMismatch(_))
+                    ));
+                }
+            }
+        }
+        rs.close().unwrap();
+        conn.close().unwrap();
+
+        fs::remove_dir_all("nulltest").unwrap();
+    }
+}
@@ -0,0 +1,38 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    IsNull,
+    IsNotNull,
+    Between,
+    NotBetween,
+    In,
+    NotIn,
+    Like,
+    NotLike,
+}
+
+impl Operator {
+    pub fn negate(&self) -> Operator {
+        match self {
+            Operator::Equal => Operator::NotEqual,
+            Operator::NotEqual => Operator::Equal,
+            Operator::LessThan => Operator::GreaterThanOrEqual,
+            Operator::LessThanOrEqual => Operator::GreaterThan,
+            Operator::GreaterThan => Operator::LessThanOrEqual,
+            Operator::GreaterThanOrEqual => Operator::LessThan,
+            Operator::IsNull => Operator::IsNotNull,
+            Operator::IsNotNull => Operator::IsNull,
+            Operator::Between => Operator::NotBetween,
+            Operator::NotBetween => Operator::Between,
+            Operator::In => Operator::NotIn,
+            Operator::NotIn => Operator::In,
+            Operator::Like => Operator::NotLike,
+            Operator::NotLike => Operator::Like,
+        }
+    }
+}
@@ -5,63 +5,146 @@ use crate::{plan::plan::Plan, record::schema::Schema, tx::transaction::Transacti
 use super::{constant::Constant, scan::Scan, term::Term};
 
 #[derive(Clone)]
-pub struct Predicate {
-    terms: Vec<Term>,
+pub enum Predicate {
+    Term(Term),
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+    Not(Box<Predicate>),
 }
 
 impl fmt::Display for Predicate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.terms
-                .iter()
-                .map(|t| t.to_string())
-                .collect::<Vec<_>>()
-                .join(" and ")
-        )
+        match self {
+            Predicate::Term(t) => write!(f, "{}", t),
+            Predicate::And(preds) => write!(
+                f,
+                "{}",
+                preds
+                    .iter()
+                    .map(|p| match p {
+                        Predicate::Or(_) => format!("({})", p),
+                        _ => p.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            ),
+            Predicate::Or(preds) => write!(
+                f,
+                "{}",
+                preds
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ),
+            Predicate::Not(pred) => write!(f, "not ({})", pred),
+        }
     }
 }
 
 impl Predicate {
     pub fn new() -> Predicate {
-        Predicate { terms: Vec::new() }
+        Predicate::And(Vec::new())
     }
 
     pub fn with_term(t: Term) -> Predicate {
-        let terms = vec![t];
-        Predicate { terms }
+        Predicate::Term(t)
     }
 
     pub fn conjoin_with(&mut self, pred: Predicate) {
-        self.terms.extend(pred.terms)
+        let mut preds = match std::mem::replace(self, Predicate::new()) {
+            Predicate::And(preds) => preds,
+            p => vec![p],
+        };
+        match pred {
+            Predicate::And(more) => preds.extend(more),
+            p => preds.push(p),
+        }
+        *self = if preds.len() == 1 {
+            preds.remove(0)
+        } else {
+            Predicate::And(preds)
+        };
+    }
+
+    pub fn disjoin_with(&mut self, pred: Predicate) {
+        let mut preds = match std::mem::replace(self, Predicate::new()) {
+            Predicate::Or(preds) => preds,
+            p => vec![p],
+        };
+        match pred {
+            Predicate::Or(more) => preds.extend(more),
+            p => preds.push(p),
+        }
+        *self = Predicate::Or(preds);
+    }
+
+    pub fn negate(&mut self) {
+        *self = match std::mem::replace(self, Predicate::new()) {
+            Predicate::Not(pred) => *pred,
+            p => Predicate::Not(Box::new(p)),
+        };
     }
 
     pub fn is_satisfied(&self, s: &mut Scan) -> Result<bool, TransactionError> {
-        for t in &self.terms {
-            if !t.is_satisfied(s)? {
-                return Ok(false);
+        Ok(self.evaluate(s)? == Some(true))
+    }
+
+    pub fn evaluate(&self, s: &mut Scan) -> Result<Option<bool>, TransactionError> {
+        match self {
+            Predicate::Term(t) => t.evaluate(s),
+            Predicate::And(preds) => {
+                let mut result = Some(true);
+                for p in preds {
+                    match p.evaluate(s)? {
+                        Some(false) => return Ok(Some(false)),
+                        Some(true) => {}
+                        None => result = None,
+                    }
+                }
+                Ok(result)
             }
+            Predicate::Or(preds) => {
+                let mut result = Some(false);
+                for p in preds {
+                    match p.evaluate(s)? {
+                        Some(true) => return Ok(Some(true)),
+                        Some(false) => {}
+                        None => result = None,
+                    }
+                }
+                Ok(result)
+            }
+            Predicate::Not(pred) => Ok(pred.evaluate(s)?.map(|b| !b)),
         }
-        Ok(true)
     }
 
     pub fn reduction_factor(&self, p: &Plan) -> usize {
-        let mut factor = 1;
-        for t in self.terms.iter() {
-            factor *= t.reduction_factor(p);
+        match self {
+            Predicate::Term(t) => t.reduction_factor(p),
+            Predicate::And(preds) => preds.iter().fold(1, |factor, pred| {
+                factor.saturating_mul(pred.reduction_factor(p))
+            }),
+            Predicate::Or(preds) => {
+                let miss = preds.iter().fold(1.0, |miss, pred| {
+                    miss * (1.0 - Predicate::selectivity(pred.reduction_factor(p)))
+                });
+                Predicate::factor(1.0 - miss)
+            }
+            Predicate::Not(pred) => {
+                Predicate::factor(1.0 - Predicate::selectivity(pred.reduction_factor(p)))
+            }
         }
-        factor
     }
 
     pub fn select_sub_pred(&self, sch: Arc<Schema>) -> Option<Predicate> {
         let mut result = Predicate::new();
-        for t in self.terms.iter() {
-            if t.applies_to(&sch) {
-                result.terms.push(t.clone());
+        for p in self.conjuncts() {
+            if p.applies_to(&sch) {
+                result.conjoin_with(p.clone());
             }
         }
-        if result.terms.is_empty() {
+        if result.conjuncts().is_empty() {
             return None;
         }
         Some(result)
@@ -72,34 +155,76 @@ impl Predicate {
         let mut newsch = Schema::new();
         newsch.add_all(&sch1);
         newsch.add_all(&sch2);
-        for t in self.terms.iter() {
-            if !t.applies_to(&sch1) && !t.applies_to(&sch2) && t.applies_to(&newsch) {
-                result.terms.push(t.clone());
+        for p in self.conjuncts() {
+            if !p.applies_to(&sch1) && !p.applies_to(&sch2) && p.applies_to(&newsch) {
+                result.conjoin_with(p.clone());
             }
         }
-        if result.terms.is_empty() {
+        if result.conjuncts().is_empty() {
             return None;
         }
         Some(result)
     }
 
+    pub fn coerce(&self, sch: &Schema) -> Predicate {
+        match self {
+            Predicate::Term(t) => Predicate::Term(t.coerce(sch)),
+            Predicate::And(preds) => Predicate::And(preds.iter().map(|p| p.coerce(sch)).collect()),
+            Predicate::Or(preds) => Predicate::Or(preds.iter().map(|p| p.coerce(sch)).collect()),
+            Predicate::Not(pred) => Predicate::Not(Box::new(pred.coerce(sch))),
+        }
+    }
+
     pub fn equates_with_constant(&self, fldname: &str) -> Option<Constant> {
-        for t in self.terms.iter() {
-            let c = t.equates_with_constant(fldname);
-            if c.is_some() {
-                return c;
+        for p in self.conjuncts() {
+            if let Predicate::Term(t) = p {
+                let c = t.equates_with_constant(fldname);
+                if c.is_some() {
+                    return c;
+                }
             }
         }
         None
     }
 
     pub fn equates_with_field(&self, fldname: &str) -> Option<String> {
-        for t in self.terms.iter() {
-            let s = t.equates_with_field(fldname);
-            if s.is_some() {
-                return s;
+        for p in self.conjuncts() {
+            if let Predicate::Term(t) = p {
+                let s = t.equates_with_field(fldname);
+                if s.is_some() {
+                    return s;
+                }
             }
         }
         None
     }
+
+    fn applies_to(&self, sch: &Schema) -> bool {
+        match self {
+            Predicate::Term(t) => t.applies_to(sch),
+            Predicate::And(preds) | Predicate::Or(preds) => preds.iter().all(|p| p.applies_to(sch)),
+            Predicate::Not(pred) => pred.applies_to(sch),
+        }
+    }
+
+    fn conjuncts(&self) -> &[Predicate] {
+        match self {
+            Predicate::And(preds) => preds,
+            p => std::slice::from_ref(p),
+        }
+    }
+
+    fn selectivity(factor: usize) -> f64 {
+        if factor == usize::MAX {
+            return 0.0;
+        }
+        1.0 / factor.max(1) as f64
+    }
+
+    fn factor(selectivity: f64) -> usize {
+        if selectivity <= 0.0 {
+            return usize::MAX;
+        }
+        ((1.0 / selectivity).round() as usize).max(1)
+    }
 }
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        api::{
+            connection::ConnectionControl, driver::DriverControl,
+            embedded::embeddeddriver::EmbeddedDriver, resultset::ResultSetControl,
+            statement::StatementControl,
+        },
+        parse::parser::Parser,
+        plan::{plan::PlanControl, selectplan::SelectPlan, tableplan::TablePlan},
+        query::querytest::tests::{assert_queries, execute_all},
+        server::simpledb::SimpleDB,
+    };
+
+    #[test]
+    fn predicatetest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("predicatetest").unwrap();
+        let mut stmt = conn.create_statement();
+
+        let cmds = [
+            "create table T(A int, B varchar(9), C int)",
+            "insert into T(A, B, C) values (1, 'apple', 10)",
+            "insert into T(A, B, C) values (2, 'banana', 20)",
+            "insert into T(A, B, C) values (3, 'cherry', null)",
+            "insert into T(A, B, C) values (4, 'apricot', 40)",
+            "insert into T(A, C) values (5, 50)",
+        ];
+        execute_all(&mut stmt, &cmds);
+
+        let queries = [
+            ("select A from T where A < 3", vec![1, 2]),
+            ("select A from T where A <= 3", vec![1, 2, 3]),
+            ("select A from T where A > 3", vec![4, 5]),
+            ("select A from T where A >= 3", vec![3, 4, 5]),
+            ("select A from T where A <> 3", vec![1, 2, 4, 5]),
+            ("select A from T where A != 3", vec![1, 2, 4, 5]),
+            ("select A from T where A = 1 or A = 4", vec![1, 4]),
+            ("select A from T where A = 1 or A = 2 and C = 10", vec![1]),
+            ("select A from T where (A = 1 or A = 2) and C = 20", vec![2]),
+            ("select A from T where not A = 1", vec![2, 3, 4, 5]),
+            ("select A from T where not (A = 1 or C > 20)", vec![2]),
+            ("select A from T where A between 2 and 4", vec![2, 3, 4]),
+            ("select A from T where A not between 2 and 4", vec![1, 5]),
+            ("select A from T where C between 15 and 45", vec![2, 4]),
+            ("select A from T where A in (1, 3, 5)", vec![1, 3, 5]),
+            ("select A from T where A not in (1, 3, 5)", vec![2, 4]),
+            ("select A from T where C not in (10, null)", vec![]),
+            ("select A from T where B like 'ap%'", vec![1, 4]),
+            ("select A from T where B like '_a%a'", vec![2]),
+            ("select A from T where B not like '%an%'", vec![1, 3, 4]),
+            (
+                "select A from T where C > 15 or C is null",
+                vec![2, 3, 4, 5],
+            ),
+            ("select A from T where not C > 15", vec![1]),
+            ("select A from T where C = 99 or C < 0", vec![]),
+        ];
+        assert_queries(&mut stmt, &queries, |rs| rs.get_int("a").unwrap());
+
+        let count = stmt
+            .execute_update("delete from T where A < 2 or B like 'b%'")
+            .unwrap();
+        assert_eq!(2, count);
+        let count = stmt
+            .execute_update("update T set C = 0 where C is null or not C in (40)")
+            .unwrap();
+        assert_eq!(2, count);
+        conn.close().unwrap();
+
+        fs::remove_dir_all("predicatetest").unwrap();
+    }
+
+    #[test]
+    fn reductionfactortest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("reductionfactortest").unwrap();
+        let mut stmt = conn.create_statement();
+        stmt.execute_update("create table T(A int, B varchar(9))")
+            .unwrap();
+        for i in 0..9 {
+            stmt.execute_update(&format!("insert into T(A, B) values ({}, 'b{}')", i, i))
+                .unwrap();
+        }
+        conn.close().unwrap();
+
+        let db = SimpleDB::new("reductionfactortest").unwrap();
+        let mdm = db.md_mgr().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let p = TablePlan::new(tx.clone(), "t", mdm).unwrap();
+        assert_eq!(9, p.records_output());
+        assert_eq!(4, p.distinct_values("a"));
+
+        let cases = [
+            ("a = 3", 2),
+            ("a <> 3", 9),
+            ("a < 3", 3),
+            ("a >= 3", 3),
+            ("a between 2 and 5", 2),
+            ("a not between 2 and 5", 9),
+            ("a in (1, 2)", 4),
+            ("a not in (1, 2)", 4),
+            ("a = 1 or a = 2", 4),
+            ("a = 1 and b = 'b1'", 0),
+            ("not a = 1", 9),
+            ("b like 'b1'", 2),
+            ("b like 'b%'", 2),
+            ("b like '%'", 9),
+            ("b is not null", 9),
+            ("1 = 2", 0),
+            ("1 < 2", 9),
+        ];
+        for (cond, expected) in cases {
+            let mut parser = Parser::new(&format!("select a from t where {}", cond));
+            let pred = parser.query().unwrap().pred();
+            let sp = SelectPlan::new(p.clone().into(), pred);
+            assert_eq!(expected, sp.records_output(), "{}", cond);
+        }
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("reductionfactortest").unwrap();
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+pub mod tests {
+    use std::fmt::Debug;
+
+    use crate::api::{
+        resultset::{ResultSet, ResultSetControl},
+        statement::{Statement, StatementControl},
+    };
+
+    pub fn execute_all(stmt: &mut Statement, cmds: &[&str]) {
+        for cmd in cmds {
+            stmt.execute_update(cmd).unwrap();
+        }
+    }
+
+    pub fn assert_queries<T: Ord + Debug>(
+        stmt: &mut Statement,
+        queries: &[(&str, Vec<T>)],
+        get: impl Fn(&mut ResultSet) -> T,
+    ) {
+        for (qry, expected) in queries {
+            let mut rs = stmt.execute_query(qry).unwrap();
+            let mut found = Vec::new();
+            while rs.next().unwrap() {
+                found.push(get(&mut rs));
+            }
+            rs.close().unwrap();
+            found.sort();
+            assert_eq!(*expected, found, "{}", qry);
+        }
+    }
+}
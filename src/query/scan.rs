@@ -11,6 +11,7 @@ use crate::{
 
 use super::{
     constant::Constant, productscan::ProductScan, projectscan::ProjectScan, selectscan::SelectScan,
+    systablescan::SysTableScan,
 };
 
 #[enum_dispatch(Scan)]
@@ -37,4 +38,5 @@ pub enum Scan {
     Sort(SortScan),
     GroupBy(GroupByScan),
     MergeJoin(MergeJoinScan),
+    SysTable(SysTableScan),
 }
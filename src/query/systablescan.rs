@@ -0,0 +1,64 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    buffer::buffermgr::AbortError, record::schema::Schema, tx::transaction::TransactionError,
+};
+
+use super::{constant::Constant, scan::ScanControl};
+
+pub struct SysTableScan {
+    schema: Arc<Schema>,
+    rows: Vec<HashMap<String, Constant>>,
+    current: Option<usize>,
+}
+
+impl ScanControl for SysTableScan {
+    fn before_first(&mut self) -> Result<(), TransactionError> {
+        self.current = None;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<bool, TransactionError> {
+        let next = self.current.map_or(0, |i| i + 1);
+        self.current = Some(next.min(self.rows.len()));
+        Ok(next < self.rows.len())
+    }
+
+    fn get_int(&mut self, fldname: &str) -> Result<i32, TransactionError> {
+        self.get_val(fldname)?
+            .as_int()
+            .ok_or(TransactionError::General)
+    }
+
+    fn get_string(&mut self, fldname: &str) -> Result<String, TransactionError> {
+        self.get_val(fldname)?
+            .as_string()
+            .ok_or(TransactionError::General)
+    }
+
+    fn get_val(&mut self, fldname: &str) -> Result<Constant, TransactionError> {
+        self.current
+            .and_then(|i| self.rows.get(i))
+            .and_then(|row| row.get(fldname))
+            .cloned()
+            .ok_or(TransactionError::General)
+    }
+
+    fn has_field(&self, fldname: &str) -> bool {
+        self.schema.has_field(fldname)
+    }
+
+    fn close(&mut self) -> Result<(), AbortError> {
+        Ok(())
+    }
+}
+
+impl SysTableScan {
+    pub fn new(schema: Arc<Schema>, rows: Vec<HashMap<String, Constant>>) -> SysTableScan {
+        SysTableScan {
+            schema,
+            rows,
+            current: None,
+        }
+    }
+}
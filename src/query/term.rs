@@ -6,76 +6,154 @@ use crate::{
     tx::transaction::TransactionError,
 };
 
-use super::{constant::Constant, expression::Expression, scan::Scan};
+use super::{constant::Constant, expression::Expression, operator::Operator, scan::Scan};
 
 #[derive(Clone)]
 pub struct Term {
     lhs: Expression,
-    rhs: Expression,
+    rhs: Vec<Expression>,
+    op: Operator,
 }
 
 impl fmt::Display for Term {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}={}", self.lhs, self.rhs)
+        let list = self
+            .rhs
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.op {
+            Operator::Equal => write!(f, "{}={}", self.lhs, self.rhs[0]),
+            Operator::NotEqual => write!(f, "{}<>{}", self.lhs, self.rhs[0]),
+            Operator::LessThan => write!(f, "{}<{}", self.lhs, self.rhs[0]),
+            Operator::LessThanOrEqual => write!(f, "{}<={}", self.lhs, self.rhs[0]),
+            Operator::GreaterThan => write!(f, "{}>{}", self.lhs, self.rhs[0]),
+            Operator::GreaterThanOrEqual => write!(f, "{}>={}", self.lhs, self.rhs[0]),
+            Operator::IsNull => write!(f, "{} is null", self.lhs),
+            Operator::IsNotNull => write!(f, "{} is not null", self.lhs),
+            Operator::Between => write!(
+                f,
+                "{} between {} and {}",
+                self.lhs, self.rhs[0], self.rhs[1]
+            ),
+            Operator::NotBetween => write!(
+                f,
+                "{} not between {} and {}",
+                self.lhs, self.rhs[0], self.rhs[1]
+            ),
+            Operator::In => write!(f, "{} in ({})", self.lhs, list),
+            Operator::NotIn => write!(f, "{} not in ({})", self.lhs, list),
+            Operator::Like => write!(f, "{} like {}", self.lhs, self.rhs[0]),
+            Operator::NotLike => write!(f, "{} not like {}", self.lhs, self.rhs[0]),
+        }
     }
 }
 
 impl Term {
     pub fn new(lhs: Expression, rhs: Expression) -> Term {
-        Term { lhs, rhs }
+        Term {
+            lhs,
+            rhs: vec![rhs],
+            op: Operator::Equal,
+        }
     }
 
-    pub fn is_satisfied(&self, s: &mut Scan) -> Result<bool, TransactionError> {
-        let lhsval = self.lhs.evaluate(s)?;
-        let rhsval = self.rhs.evaluate(s)?;
-        Ok(lhsval == rhsval)
+    pub fn with_operator(lhs: Expression, op: Operator, rhs: Expression) -> Term {
+        Term {
+            lhs,
+            rhs: vec![rhs],
+            op,
+        }
     }
 
-    pub fn reduction_factor(&self, p: &Plan) -> usize {
-        let lhs_name = self.lhs.as_field_name();
-        let rhs_name = self.rhs.as_field_name();
-        if let Some(lhs_name) = lhs_name.clone() {
-            if let Some(rhs_name) = rhs_name {
-                return cmp::max(p.distinct_values(&lhs_name), p.distinct_values(&rhs_name));
-            }
+    pub fn with_null_check(lhs: Expression, op: Operator) -> Term {
+        Term {
+            lhs,
+            rhs: Vec::new(),
+            op,
         }
-        if let Some(lhs_name) = lhs_name {
-            return p.distinct_values(&lhs_name);
+    }
+
+    pub fn with_between(lhs: Expression, op: Operator, low: Expression, high: Expression) -> Term {
+        Term {
+            lhs,
+            rhs: vec![low, high],
+            op,
         }
-        if let Some(rhs_name) = rhs_name {
-            return p.distinct_values(&rhs_name);
+    }
+
+    pub fn with_list(lhs: Expression, op: Operator, list: Vec<Expression>) -> Term {
+        Term { lhs, rhs: list, op }
+    }
+
+    pub fn evaluate(&self, s: &mut Scan) -> Result<Option<bool>, TransactionError> {
+        let lhsval = self.lhs.evaluate(s)?;
+        let mut rhsvals = Vec::with_capacity(self.rhs.len());
+        for e in self.rhs.iter() {
+            rhsvals.push(e.evaluate(s)?);
         }
-        if self.lhs.as_constant() == self.rhs.as_constant() {
-            return 1;
+        Ok(self.apply(&lhsval, &rhsvals))
+    }
+
+    pub fn reduction_factor(&self, p: &Plan) -> usize {
+        if let Some(result) = self.constant_result() {
+            return match result {
+                Some(true) => 1,
+                _ => usize::MAX,
+            };
+        }
+        let lhs_distinct = self.lhs.as_field_name().map(|n| p.distinct_values(&n));
+        match self.op {
+            Operator::Equal => self.equal_factor(p),
+            Operator::NotEqual => Term::complement(self.equal_factor(p)),
+            Operator::LessThan
+            | Operator::LessThanOrEqual
+            | Operator::GreaterThan
+            | Operator::GreaterThanOrEqual => 3,
+            Operator::IsNull => lhs_distinct.unwrap_or(1),
+            Operator::IsNotNull => 1,
+            Operator::Between => 4,
+            Operator::NotBetween => Term::complement(4),
+            Operator::In => self.in_factor(lhs_distinct),
+            Operator::NotIn => Term::complement(self.in_factor(lhs_distinct)),
+            Operator::Like => self.like_factor(lhs_distinct),
+            Operator::NotLike => Term::complement(self.like_factor(lhs_distinct)),
         }
-        usize::MAX
     }
 
     pub fn equates_with_constant(&self, fldname: &str) -> Option<Constant> {
+        if self.op != Operator::Equal {
+            return None;
+        }
+        let rhs = &self.rhs[0];
         if let Some(lhs_name) = self.lhs.as_field_name() {
             if lhs_name != fldname {
                 return None;
             }
 
-            if let Some(rhs_name) = self.rhs.as_constant() {
-                return Some(rhs_name);
+            if let Some(rhs_name) = rhs.as_constant() {
+                return Some(rhs_name).filter(|c| !c.is_null());
             }
         }
-        if let Some(rhs_name) = self.rhs.as_field_name() {
+        if let Some(rhs_name) = rhs.as_field_name() {
             if rhs_name != fldname {
                 return None;
             }
 
             if let Some(lhs_name) = self.lhs.as_constant() {
-                return Some(lhs_name);
+                return Some(lhs_name).filter(|c| !c.is_null());
             }
         }
         None
     }
 
     pub fn equates_with_field(&self, fldname: &str) -> Option<String> {
+        if self.op != Operator::Equal {
+            return None;
+        }
         if let Some(lhs_name) = self.lhs.as_field_name() {
-            if let Some(rhs_name) = self.rhs.as_field_name() {
+            if let Some(rhs_name) = self.rhs[0].as_field_name() {
                 if lhs_name == fldname {
                     return Some(rhs_name);
                 }
@@ -88,6 +166,144 @@ impl Term {
     }
 
     pub fn applies_to(&self, sch: &Schema) -> bool {
-        self.lhs.applies_to(sch) && self.rhs.applies_to(sch)
+        self.lhs.applies_to(sch) && self.rhs.iter().all(|e| e.applies_to(sch))
+    }
+
+    pub fn coerce(&self, sch: &Schema) -> Term {
+        let mut t = self.clone();
+        if matches!(self.op, Operator::Like | Operator::NotLike) {
+            return t;
+        }
+        let type_of = |e: &Expression| {
+            e.as_field_name()
+                .filter(|n| sch.has_field(n))
+                .map(|n| sch.type_(&n))
+        };
+        if let Some(type_) = type_of(&self.lhs) {
+            t.rhs = self.rhs.iter().map(|e| e.coerce_to(type_)).collect();
+        } else if let Some(type_) = self.rhs.first().and_then(type_of) {
+            t.lhs = self.lhs.coerce_to(type_);
+        }
+        t
+    }
+
+    fn apply(&self, lhsval: &Constant, rhsvals: &[Constant]) -> Option<bool> {
+        match self.op {
+            Operator::IsNull => return Some(lhsval.is_null()),
+            Operator::IsNotNull => return Some(!lhsval.is_null()),
+            Operator::In | Operator::NotIn => {
+                if lhsval.is_null() {
+                    return None;
+                }
+                let found = if rhsvals.contains(lhsval) {
+                    Some(true)
+                } else if rhsvals.iter().any(|v| v.is_null()) {
+                    None
+                } else {
+                    Some(false)
+                };
+                return found.map(|b| b == (self.op == Operator::In));
+            }
+            _ => {}
+        }
+        if lhsval.is_null() || rhsvals.iter().any(|v| v.is_null()) {
+            return None;
+        }
+        let rhsval = &rhsvals[0];
+        match self.op {
+            Operator::Equal => Some(lhsval.compare(rhsval)?.is_eq()),
+            Operator::NotEqual => Some(lhsval.compare(rhsval)?.is_ne()),
+            Operator::LessThan => Some(lhsval.compare(rhsval)?.is_lt()),
+            Operator::LessThanOrEqual => Some(lhsval.compare(rhsval)?.is_le()),
+            Operator::GreaterThan => Some(lhsval.compare(rhsval)?.is_gt()),
+            Operator::GreaterThanOrEqual => Some(lhsval.compare(rhsval)?.is_ge()),
+            Operator::Between | Operator::NotBetween => {
+                let low = rhsval.compare(lhsval)?.is_le();
+                let high = lhsval.compare(&rhsvals[1])?.is_le();
+                Some((low && high) == (self.op == Operator::Between))
+            }
+            Operator::Like | Operator::NotLike => {
+                let matched = like(&lhsval.as_string()?, &rhsval.as_string()?);
+                Some(matched == (self.op == Operator::Like))
+            }
+            Operator::IsNull | Operator::IsNotNull | Operator::In | Operator::NotIn => None,
+        }
+    }
+
+    fn constant_result(&self) -> Option<Option<bool>> {
+        let lhsval = self.lhs.as_constant()?;
+        let mut rhsvals = Vec::with_capacity(self.rhs.len());
+        for e in self.rhs.iter() {
+            rhsvals.push(e.as_constant()?);
+        }
+        Some(self.apply(&lhsval, &rhsvals))
+    }
+
+    fn equal_factor(&self, p: &Plan) -> usize {
+        let lhs_name = self.lhs.as_field_name();
+        let rhs_name = self.rhs[0].as_field_name();
+        if let Some(lhs_name) = lhs_name.clone() {
+            if let Some(rhs_name) = rhs_name {
+                return cmp::max(p.distinct_values(&lhs_name), p.distinct_values(&rhs_name));
+            }
+        }
+        if let Some(lhs_name) = lhs_name {
+            return p.distinct_values(&lhs_name);
+        }
+        if let Some(rhs_name) = rhs_name {
+            return p.distinct_values(&rhs_name);
+        }
+        usize::MAX
+    }
+
+    fn in_factor(&self, lhs_distinct: Option<usize>) -> usize {
+        match lhs_distinct {
+            Some(distinct) => cmp::max(1, distinct / cmp::max(1, self.rhs.len())),
+            None => 1,
+        }
+    }
+
+    fn like_factor(&self, lhs_distinct: Option<usize>) -> usize {
+        let pattern = self.rhs[0].as_constant().and_then(|c| c.as_string());
+        let distinct = lhs_distinct.unwrap_or(usize::MAX);
+        match pattern {
+            Some(pattern) if pattern.chars().all(|c| c == '%') => 1,
+            Some(pattern) if !pattern.contains(['%', '_']) => distinct,
+            _ => distinct.clamp(1, 10),
+        }
+    }
+
+    fn complement(factor: usize) -> usize {
+        if factor <= 1 {
+            return usize::MAX;
+        }
+        if factor == usize::MAX {
+            return 1;
+        }
+        let factor = factor as f64;
+        (factor / (factor - 1.0)).round() as usize
+    }
+}
+
+fn like(s: &str, pattern: &str) -> bool {
+    let s: Vec<char> = s.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut i, mut j) = (0, 0);
+    let mut backtrack = None;
+    while i < s.len() {
+        if j < pattern.len() && (pattern[j] == '_' || pattern[j] == s[i]) {
+            i += 1;
+            j += 1;
+        } else if j < pattern.len() && pattern[j] == '%' {
+            backtrack = Some((i, j));
+            j += 1;
+        } else if let Some((bi, bj)) = backtrack {
+            backtrack = Some((bi + 1, bj));
+            i = bi + 1;
+            j = bj + 1;
+        } else {
+            return false;
+        }
     }
+    pattern[j..].iter().all(|c| *c == '%')
 }
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, fs};
+
+    use crate::{
+        api::{
+            connection::ConnectionControl, driver::DriverControl,
+            embedded::embeddeddriver::EmbeddedDriver, metadata::MetaDataControl,
+            resultset::ResultSetControl, statement::StatementControl,
+        },
+        file::{blockid::BlockId, durability::Durability},
+        query::{
+            constant::Constant,
+            datetime::{format_date, format_timestamp, parse_date, parse_timestamp},
+            querytest::tests::{assert_queries, execute_all},
+        },
+        record::schema::Type,
+        server::simpledb::SimpleDB,
+        tx::transaction::Transaction,
+    };
+
+    #[test]
+    fn typestest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("typestest").unwrap();
+        let mut stmt = conn.create_statement();
+
+        let cmds = [
+            "create table T(Id bigint, Flag boolean, Price double, Day date, At timestamp)",
+            "create index idxid on T(Id)",
+            "create index idxday on T(Day)",
+            "insert into T(Id, Flag, Price, Day, At) values (9000000000, true, 19.99, date '2024-02-29', timestamp '2024-02-29 13:45:30.25')",
+            "insert into T(Id, Flag, Price, Day, At) values (-7, false, -0.5, date '1969-12-31', timestamp '1970-01-01 00:00:00')",
+            "insert into T(Id, Flag) values (42, true)",
+        ];
+        execute_all(&mut stmt, &cmds);
+
+        let mut rs = stmt
+            .execute_query("select Id, Flag, Price, Day, At from T where Id = 9000000000")
+            .unwrap();
+        let mut md = rs.get_meta_data();
+        assert!(matches!(md.get_column_type(1).unwrap(), Type::BigInt));
+        assert!(matches!(md.get_column_type(2).unwrap(), Type::Boolean));
+        assert!(matches!(md.get_column_type(3).unwrap(), Type::Double));
+        assert!(matches!(md.get_column_type(4).unwrap(), Type::Date));
+        assert!(matches!(md.get_column_type(5).unwrap(), Type::Timestamp));
+        assert!(rs.next().unwrap());
+        assert_eq!(9000000000, rs.get_long("id").unwrap());
+        assert!(rs.get_boolean("flag").unwrap());
+        assert_eq!(19.99, rs.get_double("price").unwrap());
+        assert_eq!("2024-02-29", rs.get_string("day").unwrap());
+        assert_eq!("2024-02-29 13:45:30.25", rs.get_string("at").unwrap());
+        assert!(!rs.next().unwrap());
+        rs.close().unwrap();
+
+        let queries = [
+            ("select Id from T where Flag = true", vec![42, 9000000000]),
+            ("select Id from T where Price = -0.5", vec![-7]),
+            ("select Id from T where Day = date '1969-12-31'", vec![-7]),
+            ("select Id from T where At = date '1970-01-01'", vec![-7]),
+            ("select Id from T where Day < '2000-01-01'", vec![-7]),
+            ("select Id from T where Day = '1969-12-31'", vec![-7]),
+            ("select Id from T where '2024-01-01' < At", vec![9000000000]),
+            ("select Id from T where Id = 42", vec![42]),
+        ];
+        assert_queries(&mut stmt, &queries, |rs| rs.get_long("id").unwrap());
+
+        let count = stmt
+            .execute_update("update T set Price = 5 where Id = 42")
+            .unwrap();
+        assert_eq!(1, count);
+        let mut rs = stmt
+            .execute_query("select Price from T where Id = 42")
+            .unwrap();
+        assert!(rs.next().unwrap());
+        assert_eq!(5.0, rs.get_double("price").unwrap());
+        rs.close().unwrap();
+        conn.close().unwrap();
+
+        fs::remove_dir_all("typestest").unwrap();
+    }
+
+    #[test]
+    fn typesrollbacktest() {
+        let mut db =
+            SimpleDB::with_params("typesrollbacktest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+        let lm = db.log_mgr();
+        let bm = db.buffer_mgr();
+        let lt = db.lock_table();
+        let vm = db.version_mgr();
+
+        let mut tx1 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
+        let blk = BlockId::new("testfile", 1);
+        tx1.pin(&blk).unwrap();
+        tx1.set_long(&blk, 80, i64::MAX, true).unwrap();
+        tx1.set_double(&blk, 100, 3.25, true).unwrap();
+        tx1.set_bool(&blk, 120, true, true).unwrap();
+        tx1.commit().unwrap();
+
+        let mut tx2 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
+        tx2.pin(&blk).unwrap();
+        tx2.set_long(&blk, 80, -1, true).unwrap();
+        tx2.set_double(&blk, 100, -2.5, true).unwrap();
+        tx2.set_bool(&blk, 120, false, true).unwrap();
+        assert_eq!(-1, tx2.get_long(&blk, 80).unwrap());
+        tx2.rollback().unwrap();
+
+        let mut tx3 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
+        tx3.pin(&blk).unwrap();
+        assert_eq!(i64::MAX, tx3.get_long(&blk, 80).unwrap());
+        assert_eq!(3.25, tx3.get_double(&blk, 100).unwrap());
+        assert!(tx3.get_bool(&blk, 120).unwrap());
+        tx3.commit().unwrap();
+
+        fs::remove_dir_all("typesrollbacktest").unwrap();
+    }
+
+    #[test]
+    fn datetimetest() {
+        assert_eq!(Some(0), parse_date("1970-01-01"));
+        assert_eq!(Some(-1), parse_date("1969-12-31"));
+        assert_eq!(Some(19782), parse_date("2024-02-29"));
+        assert_eq!(None, parse_date("2023-02-29"));
+        assert_eq!(None, parse_date("2024-13-01"));
+        assert_eq!("2024-02-29", format_date(19782));
+        assert_eq!("0001-01-01", format_date(parse_date("0001-01-01").unwrap()));
+
+        let micros = parse_timestamp("2000-03-01 23:59:59.000001").unwrap();
+        assert_eq!("2000-03-01 23:59:59.000001", format_timestamp(micros));
+        assert_eq!(Some(-1_000_000), parse_timestamp("1969-12-31 23:59:59"));
+        assert_eq!("1969-12-31 23:59:59", format_timestamp(-1_000_000));
+        assert_eq!(None, parse_timestamp("2000-01-01 24:00:00"));
+
+        assert!(Constant::with_int(5) == Constant::with_long(5));
+        assert!(Constant::with_int(5) == Constant::with_double(5.0));
+        assert!(Constant::with_long(5) < Constant::with_double(5.5));
+        assert!(Constant::with_date(0) == Constant::with_timestamp(0));
+        assert!(Constant::with_date(0) < Constant::with_timestamp(1));
+    }
+
+    #[test]
+    fn constantcmptest() {
+        let exact = 1i64 << 53;
+        assert!(Constant::with_long(exact + 1) != Constant::with_double(exact as f64));
+        assert!(Constant::with_long(exact + 1) > Constant::with_double(exact as f64));
+        assert!(Constant::with_long(-5) > Constant::with_double(-5.5));
+        assert!(Constant::with_long(i64::MAX) < Constant::with_double(i64::MAX as f64));
+
+        let day = Constant::with_date(parse_date("2024-02-29").unwrap());
+        let midnight = Constant::with_timestamp(parse_timestamp("2024-02-29").unwrap());
+        assert!(day == midnight);
+        assert!(day != Constant::with_string("2024-02-29"));
+        assert!(day != Constant::with_string("2024-02-29 00:00:00"));
+        assert_eq!(None, day.compare(&Constant::with_string("2024-02-29")));
+        assert!(day == Constant::with_string("2024-02-29").coerce_to(Type::Date));
+        assert!(day < Constant::with_string("2024-03-01").coerce_to(Type::Date));
+        assert!(day > Constant::with_string("2024-02-28 23:59:59").coerce_to(Type::Date));
+        assert!(
+            Constant::with_string("soon").coerce_to(Type::Date) == Constant::with_string("soon")
+        );
+        assert_eq!(
+            None,
+            Constant::with_int(1).compare(&Constant::with_bool(true))
+        );
+
+        let set: HashSet<Constant> = [
+            Constant::with_double(exact as f64),
+            midnight.clone(),
+            Constant::with_string("2024-02-29"),
+        ]
+        .into_iter()
+        .collect();
+        assert!(set.contains(&Constant::with_long(exact)));
+        assert!(!set.contains(&Constant::with_long(exact + 1)));
+        assert!(set.contains(&day));
+        assert_eq!(3, set.len());
+    }
+}
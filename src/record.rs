@@ -1,8 +1,12 @@
+pub mod freespacemap;
 pub mod layout;
 pub mod layouttest;
+pub mod overflowfile;
 pub mod recordpage;
 pub mod recordtest;
 pub mod rid;
 pub mod schema;
+pub mod slottedpagetest;
 pub mod tablescan;
 pub mod tablescantest;
+pub mod varchartest;
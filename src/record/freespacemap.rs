@@ -0,0 +1,48 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+};
+
+pub struct FreeSpaceMap {
+    blocks: Mutex<HashMap<String, BTreeSet<i32>>>,
+}
+
+impl FreeSpaceMap {
+    pub fn new() -> FreeSpaceMap {
+        FreeSpaceMap {
+            blocks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_tracked(&self, filename: &str) -> bool {
+        self.blocks.lock().unwrap().contains_key(filename)
+    }
+
+    pub fn track(&self, filename: &str, free: BTreeSet<i32>) {
+        self.blocks
+            .lock()
+            .unwrap()
+            .entry(filename.to_string())
+            .or_insert(free);
+    }
+
+    pub fn first_free(&self, filename: &str) -> Option<i32> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .get(filename)
+            .and_then(|free| free.iter().next().copied())
+    }
+
+    pub fn mark_free(&self, filename: &str, blknum: i32) {
+        if let Some(free) = self.blocks.lock().unwrap().get_mut(filename) {
+            free.insert(blknum);
+        }
+    }
+
+    pub fn mark_full(&self, filename: &str, blknum: i32) {
+        if let Some(free) = self.blocks.lock().unwrap().get_mut(filename) {
+            free.remove(&blknum);
+        }
+    }
+}
@@ -2,24 +2,29 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::file::page::Page;
 
-use super::schema::Schema;
+use super::schema::{Schema, Type};
 
 #[derive(Clone)]
 pub struct Layout {
     schema: Arc<Schema>,
     offsets: HashMap<String, usize>,
+    nullbits: HashMap<String, usize>,
     slotsize: usize,
 }
 
 impl Layout {
+    const FLAG_BITS: usize = 32;
+
     pub fn new(schema: Arc<Schema>) -> Layout {
         let offsets = HashMap::new();
         let bytes = 4;
-        let pos = bytes;
+        let words = (schema.fields().len() + 1).div_ceil(Layout::FLAG_BITS);
+        let pos = bytes * words;
 
         let mut l = Layout {
             schema,
             offsets,
+            nullbits: HashMap::new(),
             slotsize: pos,
         };
 
@@ -27,20 +32,22 @@ impl Layout {
             l.offsets.insert(fldname.to_string(), l.slotsize);
             l.slotsize += l.length_in_bytes(fldname);
         }
+        l.nullbits = Layout::null_bits(&l.offsets);
 
         l
     }
 
-    pub fn with_metadata(
-        schema: Arc<Schema>,
-        offsets: HashMap<String, usize>,
-        slotsize: usize,
-    ) -> Layout {
-        Layout {
-            schema,
-            offsets,
-            slotsize,
-        }
+    pub fn flag_words(&self) -> usize {
+        (self.nullbits.len() + 1).div_ceil(Layout::FLAG_BITS)
+    }
+
+    pub fn null_bit(&self, fldname: &str) -> (usize, i32) {
+        let bit = self.nullbits[fldname];
+        let bytes = 4;
+        (
+            bytes * (bit / Layout::FLAG_BITS),
+            1 << (bit % Layout::FLAG_BITS),
+        )
     }
 
     pub fn schema(&self) -> Arc<Schema> {
@@ -55,12 +62,25 @@ impl Layout {
         self.slotsize
     }
 
-    fn length_in_bytes(&self, fldname: &str) -> usize {
+    fn null_bits(offsets: &HashMap<String, usize>) -> HashMap<String, usize> {
+        let mut fields: Vec<_> = offsets.iter().collect();
+        fields.sort_by_key(|(_, offset)| **offset);
+        fields
+            .into_iter()
+            .enumerate()
+            .map(|(i, (fldname, _))| (fldname.clone(), i + 1))
+            .collect()
+    }
+
+    pub fn length_in_bytes(&self, fldname: &str) -> usize {
         let fldtype = self.schema.type_(fldname);
         let bytes = 4;
         match fldtype {
-            super::schema::Type::Integer => bytes,
-            super::schema::Type::Varchar => Page::max_length(self.schema.length(fldname)),
+            Type::Integer | Type::Date => bytes,
+            Type::BigInt | Type::Double | Type::Timestamp => 8,
+            Type::Boolean => 1,
+            Type::Varchar => Page::max_length(self.schema.length(fldname)),
+            Type::Text | Type::Blob => Page::bytes_length(0),
         }
     }
 }
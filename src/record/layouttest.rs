@@ -18,5 +18,18 @@ mod tests {
             let offset = layout.offset(fldname);
             assert_eq!(offset, e[i].1);
         }
+        assert_eq!(1, layout.flag_words());
+        assert_eq!((0, 2), layout.null_bit("A"));
+        assert_eq!((0, 4), layout.null_bit("B"));
+
+        let mut sch = Schema::new();
+        for i in 0..40 {
+            sch.add_int_field(&format!("f{}", i));
+        }
+        let layout = Layout::new(Arc::new(sch));
+        assert_eq!(2, layout.flag_words());
+        assert_eq!(8, layout.offset("f0"));
+        assert_eq!((0, i32::MIN), layout.null_bit("f30"));
+        assert_eq!((4, 1), layout.null_bit("f31"));
     }
 }
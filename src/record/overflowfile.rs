@@ -0,0 +1,119 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    file::blockid::BlockId,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+pub struct OverflowFile {
+    tx: Arc<Mutex<Transaction>>,
+    filename: String,
+}
+
+impl OverflowFile {
+    const NO_BLOCK: i32 = 0;
+    const FREE_HEAD: usize = 0;
+    const NEXT: usize = 0;
+    const LENGTH: usize = 4;
+    const DATA: usize = 8;
+
+    pub fn new(tx: Arc<Mutex<Transaction>>, tblfile: &str) -> OverflowFile {
+        let tblname = tblfile.strip_suffix(".tbl").unwrap_or(tblfile);
+        OverflowFile {
+            tx,
+            filename: format!("{}.ovf", tblname),
+        }
+    }
+
+    pub fn write(&self, val: &[u8]) -> Result<i32, TransactionError> {
+        let capacity = self.tx.lock().unwrap().page_size() - OverflowFile::DATA;
+        let chunks: Vec<&[u8]> = val.chunks(capacity).collect();
+        let mut blknums = Vec::with_capacity(chunks.len());
+        for _ in 0..chunks.len().max(1) {
+            blknums.push(self.allocate()?);
+        }
+        let mut tx = self.tx.lock().unwrap();
+        for (i, blknum) in blknums.iter().enumerate() {
+            let blk = BlockId::new(&self.filename, *blknum);
+            let next = blknums
+                .get(i + 1)
+                .copied()
+                .unwrap_or(OverflowFile::NO_BLOCK);
+            let chunk = chunks.get(i).copied().unwrap_or_default();
+            tx.pin(&blk)?;
+            tx.set_int(&blk, OverflowFile::NEXT, next, true)?;
+            tx.set_int(&blk, OverflowFile::LENGTH, chunk.len() as i32, true)?;
+            tx.set_bytes(&blk, OverflowFile::DATA, chunk, true)?;
+            tx.unpin(&blk)?;
+        }
+        Ok(blknums[0])
+    }
+
+    pub fn read(&self, first: i32, len: usize) -> Result<Vec<u8>, TransactionError> {
+        let mut val = Vec::with_capacity(len);
+        let mut tx = self.tx.lock().unwrap();
+        let mut blknum = first;
+        while blknum != OverflowFile::NO_BLOCK && val.len() < len {
+            let blk = BlockId::new(&self.filename, blknum);
+            tx.pin(&blk)?;
+            let n = tx.get_int(&blk, OverflowFile::LENGTH)? as usize;
+            val.extend(tx.get_bytes(&blk, OverflowFile::DATA, n)?);
+            blknum = tx.get_int(&blk, OverflowFile::NEXT)?;
+            tx.unpin(&blk)?;
+        }
+        if val.len() != len {
+            return Err(TransactionError::General);
+        }
+        Ok(val)
+    }
+
+    pub fn free(&self, first: i32) -> Result<(), TransactionError> {
+        let header = self.header()?;
+        let mut tx = self.tx.lock().unwrap();
+        let mut last = BlockId::new(&self.filename, first);
+        tx.pin(&last)?;
+        loop {
+            let next = tx.get_int(&last, OverflowFile::NEXT)?;
+            if next == OverflowFile::NO_BLOCK {
+                break;
+            }
+            tx.unpin(&last)?;
+            last = BlockId::new(&self.filename, next);
+            tx.pin(&last)?;
+        }
+        tx.pin(&header)?;
+        let head = tx.get_int(&header, OverflowFile::FREE_HEAD)?;
+        tx.set_int(&last, OverflowFile::NEXT, head, true)?;
+        tx.set_int(&header, OverflowFile::FREE_HEAD, first, true)?;
+        tx.unpin(&header)?;
+        tx.unpin(&last)?;
+        Ok(())
+    }
+
+    fn allocate(&self) -> Result<i32, TransactionError> {
+        let header = self.header()?;
+        let mut tx = self.tx.lock().unwrap();
+        tx.pin(&header)?;
+        let head = tx.get_int(&header, OverflowFile::FREE_HEAD)?;
+        let blknum = if head == OverflowFile::NO_BLOCK {
+            tx.append(&self.filename)?.number()
+        } else {
+            let blk = BlockId::new(&self.filename, head);
+            tx.pin(&blk)?;
+            let next = tx.get_int(&blk, OverflowFile::NEXT)?;
+            tx.unpin(&blk)?;
+            tx.set_int(&header, OverflowFile::FREE_HEAD, next, true)?;
+            head
+        };
+        tx.unpin(&header)?;
+        Ok(blknum)
+    }
+
+    fn header(&self) -> Result<BlockId, TransactionError> {
+        let mut tx = self.tx.lock().unwrap();
+        if tx.size(&self.filename)? == 0 {
+            return tx.append(&self.filename);
+        }
+        Ok(BlockId::new(&self.filename, 0))
+    }
+}
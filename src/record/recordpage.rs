@@ -1,17 +1,16 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    cmp::Reverse,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     buffer::buffermgr::AbortError,
-    file::blockid::BlockId,
+    file::{blockid::BlockId, page::Page},
+    query::constant::Constant,
     tx::transaction::{Transaction, TransactionError},
 };
 
-use super::{layout::Layout, schema::Type};
-
-enum Flag {
-    Empty = 0,
-    Used = 1,
-}
+use super::{layout::Layout, overflowfile::OverflowFile, schema::Type};
 
 pub struct RecordPage {
     tx: Arc<Mutex<Transaction>>,
@@ -20,6 +19,19 @@ pub struct RecordPage {
 }
 
 impl RecordPage {
+    const SLOT_COUNT: usize = 0;
+    const DATA_START: usize = 4;
+    const HEADER_SIZE: usize = 8;
+    const ENTRY_SIZE: usize = 8;
+    const OVERFLOW: i32 = -1;
+    const OVERFLOW_SIZE: usize = 12;
+    const XMIN: usize = 0;
+    const XMAX: usize = 4;
+    const PREV: usize = 8;
+    const PREV_LEN: usize = 12;
+    const TX_HEADER: usize = 16;
+    const NO_VERSION: i32 = 0;
+
     pub fn new(
         tx: Arc<Mutex<Transaction>>,
         blk: BlockId,
@@ -30,13 +42,21 @@ impl RecordPage {
     }
 
     pub fn get_int(&mut self, slot: usize, fldname: &str) -> Result<i32, TransactionError> {
-        let fldpos = self.offset(slot) + self.layout.offset(fldname);
-        self.tx.lock().unwrap().get_int(&self.blk, fldpos)
+        let v = self.get_val(slot, fldname)?;
+        if v.is_null() {
+            return Ok(0);
+        }
+        v.as_int()
+            .ok_or(TransactionError::TypeMismatch(fldname.to_string()))
     }
 
     pub fn get_string(&mut self, slot: usize, fldname: &str) -> Result<String, TransactionError> {
-        let fldpos = self.offset(slot) + self.layout.offset(fldname);
-        self.tx.lock().unwrap().get_string(&self.blk, fldpos)
+        let v = self.get_val(slot, fldname)?;
+        if v.is_null() {
+            return Ok(String::new());
+        }
+        v.as_string()
+            .ok_or(TransactionError::TypeMismatch(fldname.to_string()))
     }
 
     pub fn set_int(
@@ -45,11 +65,7 @@ impl RecordPage {
         fldname: &str,
         val: i32,
     ) -> Result<(), TransactionError> {
-        let fldpos = self.offset(slot) + self.layout.offset(fldname);
-        self.tx
-            .lock()
-            .unwrap()
-            .set_int(&self.blk, fldpos, val, true)
+        self.set_val(slot, fldname, &Constant::with_int(val))
     }
 
     pub fn set_string(
@@ -58,96 +74,549 @@ impl RecordPage {
         fldname: &str,
         val: &str,
     ) -> Result<(), TransactionError> {
-        let fldpos = self.offset(slot) + self.layout.offset(fldname);
-        self.tx
-            .lock()
-            .unwrap()
-            .set_string(&self.blk, fldpos, val, true)
+        self.set_val(slot, fldname, &Constant::with_string(val))
     }
 
-    pub fn delete(&mut self, slot: usize) -> Result<(), TransactionError> {
-        self.set_flag(slot, Flag::Empty)
+    pub fn get_val(&mut self, slot: usize, fldname: &str) -> Result<Constant, TransactionError> {
+        let rec = self.visible_image(slot)?.ok_or(TransactionError::General)?;
+        let (pos, mask) = self.null_bit(fldname);
+        if rec.get_int(pos) & mask != 0 {
+            return Ok(Constant::null());
+        }
+        let pos = self.field_pos(&rec, fldname);
+        match self.layout.schema().type_(fldname) {
+            Type::Integer => Ok(Constant::with_int(rec.get_int(pos))),
+            Type::BigInt => Ok(Constant::with_long(rec.get_long(pos))),
+            Type::Double => Ok(Constant::with_double(rec.get_double(pos))),
+            Type::Varchar | Type::Text => {
+                let bytes = self.read_bytes(&rec, pos)?;
+                Ok(Constant::with_string(&String::from_utf8(bytes)?))
+            }
+            Type::Blob => Ok(Constant::with_bytes(&self.read_bytes(&rec, pos)?)),
+            Type::Boolean => Ok(Constant::with_bool(rec.get_bool(pos))),
+            Type::Date => Ok(Constant::with_date(rec.get_int(pos))),
+            Type::Timestamp => Ok(Constant::with_timestamp(rec.get_long(pos))),
+        }
     }
 
-    pub fn format(&mut self) -> Result<(), TransactionError> {
-        let mut slot = 0;
-        while self.is_valid_slot(slot) {
-            self.tx.lock().unwrap().set_int(
-                &self.blk,
-                self.offset(slot),
-                Flag::Empty as i32,
-                false,
-            )?;
-            let sch = self.layout.schema();
-            for fldname in sch.fields() {
-                let fldpos = self.offset(slot) + self.layout.offset(fldname);
-                match sch.type_(fldname) {
-                    Type::Integer => {
-                        self.tx
-                            .lock()
-                            .unwrap()
-                            .set_int(&self.blk, fldpos, 0, false)?;
-                    }
-                    Type::Varchar => {
-                        self.tx
-                            .lock()
-                            .unwrap()
-                            .set_string(&self.blk, fldpos, "", false)?;
-                    }
+    pub fn set_val(
+        &mut self,
+        slot: usize,
+        fldname: &str,
+        val: &Constant,
+    ) -> Result<(), TransactionError> {
+        if val.is_null() {
+            return self.set_null(slot, fldname);
+        }
+        self.new_version(slot)?;
+        let mut rec = Page::with_vec(self.read_record(slot)?);
+        let pos = self.field_pos(&rec, fldname);
+        let err = TransactionError::General;
+        let mut field = Page::new(self.layout.length_in_bytes(fldname));
+        match self.layout.schema().type_(fldname) {
+            Type::Integer => field.set_int(0, val.as_int().ok_or(err)?),
+            Type::BigInt => field.set_long(0, val.as_long().ok_or(err)?),
+            Type::Double => field.set_double(0, val.as_double().ok_or(err)?),
+            Type::Varchar => {
+                let s = val.as_string().ok_or(err)?;
+                let maxlen = self.layout.schema().length(fldname);
+                if s.chars().count() > maxlen {
+                    return Err(TransactionError::StringTooLong(fldname.to_string()));
                 }
+                self.free_overflow(&rec, pos)?;
+                field = self.encode_bytes(s.as_bytes())?;
             }
-            slot += 1;
+            Type::Text => {
+                let s = val.as_string().ok_or(err)?;
+                self.free_overflow(&rec, pos)?;
+                field = self.encode_bytes(s.as_bytes())?;
+            }
+            Type::Blob => {
+                let bytes = val.as_bytes().ok_or(err)?;
+                self.free_overflow(&rec, pos)?;
+                field = self.encode_bytes(&bytes)?;
+            }
+            Type::Boolean => field.set_bool(0, val.as_bool().ok_or(err)?),
+            Type::Date => field.set_int(0, val.as_date().ok_or(err)?),
+            Type::Timestamp => field.set_long(0, val.as_timestamp().ok_or(err)?),
         }
-        Ok(())
+        let (npos, mask) = self.null_bit(fldname);
+        rec.set_int(npos, rec.get_int(npos) & !mask);
+        let oldlen = self.field_len(&rec, fldname, pos);
+        let mut image = rec.contents().clone();
+        image.splice(pos..pos + oldlen, field.contents().iter().copied());
+        self.write_record(slot, image)
+    }
+
+    pub fn is_null(&mut self, slot: usize, fldname: &str) -> Result<bool, TransactionError> {
+        let rec = self.visible_image(slot)?.ok_or(TransactionError::General)?;
+        let (pos, mask) = self.null_bit(fldname);
+        Ok(rec.get_int(pos) & mask != 0)
+    }
+
+    pub fn set_null(&mut self, slot: usize, fldname: &str) -> Result<(), TransactionError> {
+        if self.is_null(slot, fldname)? {
+            return Ok(());
+        }
+        self.new_version(slot)?;
+        let mut rec = Page::with_vec(self.read_record(slot)?);
+        let (npos, mask) = self.null_bit(fldname);
+        rec.set_int(npos, rec.get_int(npos) | mask);
+        let mut image = rec.contents().clone();
+        if self.layout.schema().type_(fldname).is_variable_length() {
+            let pos = self.field_pos(&rec, fldname);
+            self.free_overflow(&rec, pos)?;
+            let oldlen = self.field_len(&rec, fldname, pos);
+            image.splice(pos..pos + oldlen, 0i32.to_be_bytes());
+        }
+        self.write_record(slot, image)
+    }
+
+    pub fn delete(&mut self, slot: usize) -> Result<(), TransactionError> {
+        let (offset, _) = self.entry(slot)?;
+        let mut tx = self.tx.lock().unwrap();
+        if tx.is_versioned() {
+            let txnum = tx.tx_number() as i32;
+            return tx.set_int(&self.blk, offset + RecordPage::XMAX, txnum, true);
+        }
+        drop(tx);
+        self.purge(slot)
+    }
+
+    pub fn format(&mut self) -> Result<(), TransactionError> {
+        let mut tx = self.tx.lock().unwrap();
+        let blocksize = tx.page_size() as i32;
+        tx.set_int(&self.blk, RecordPage::SLOT_COUNT, 0, false)?;
+        tx.set_int(&self.blk, RecordPage::DATA_START, blocksize, false)
     }
 
     pub fn next_after(&mut self, slot: Option<usize>) -> Result<Option<usize>, TransactionError> {
-        self.search_after(slot, Flag::Used)
+        let mut s = slot.map_or(0, |slot| slot + 1);
+        let count = self.slot_count()?;
+        while s < count {
+            if self.entry(s)?.0 != 0 && self.visible_image(s)?.is_some() {
+                return Ok(Some(s));
+            }
+            s += 1;
+        }
+        Ok(None)
     }
 
     pub fn insert_after(&mut self, slot: Option<usize>) -> Result<Option<usize>, TransactionError> {
-        let newslot = self.search_after(slot, Flag::Empty)?;
-        if let Some(newslot) = newslot {
-            self.set_flag(newslot, Flag::Used)?;
+        let mut s = self.search_empty(slot)?;
+        let mut count = self.slot_count()?;
+        if !self.fits_record(s == count)? && self.vacuum()? {
+            s = self.search_empty(slot)?;
+            count = self.slot_count()?;
+        }
+        if !self.fits_record(s == count)? {
+            return Ok(None);
+        }
+        if s == count {
+            if self.contiguous_space()? < RecordPage::ENTRY_SIZE {
+                self.compact()?;
+            }
+            self.tx.lock().unwrap().set_int(
+                &self.blk,
+                RecordPage::SLOT_COUNT,
+                count as i32 + 1,
+                true,
+            )?;
+            self.set_entry(s, 0, 0)?;
+        }
+        let txnum = self.tx.lock().unwrap().tx_number();
+        self.write_record(s, self.empty_record(txnum))?;
+        Ok(Some(s))
+    }
+
+    pub fn may_have_room(&mut self) -> Result<bool, TransactionError> {
+        let mut tx = self.tx.lock().unwrap();
+        let count = tx.peek_int(&self.blk, RecordPage::SLOT_COUNT)? as usize;
+        let mut used = RecordPage::HEADER_SIZE + RecordPage::ENTRY_SIZE * count;
+        let mut new_entry = true;
+        for s in 0..count {
+            let pos = RecordPage::HEADER_SIZE + RecordPage::ENTRY_SIZE * s;
+            if tx.peek_int(&self.blk, pos)? == 0 {
+                new_entry = false;
+            }
+            used += tx.peek_int(&self.blk, pos + 4)? as usize;
         }
-        Ok(newslot)
+        let blocksize = tx.page_size();
+        drop(tx);
+        Ok(blocksize.saturating_sub(used) >= self.reserve(new_entry, blocksize))
     }
 
     pub fn block(&self) -> &BlockId {
         &self.blk
     }
 
-    fn set_flag(&mut self, slot: usize, flag: Flag) -> Result<(), TransactionError> {
+    fn search_empty(&mut self, slot: Option<usize>) -> Result<usize, TransactionError> {
+        let mut s = slot.map_or(0, |slot| slot + 1);
+        let count = self.slot_count()?;
+        while s < count && self.entry(s)?.0 != 0 {
+            s += 1;
+        }
+        Ok(s)
+    }
+
+    fn fits_record(&mut self, new_entry: bool) -> Result<bool, TransactionError> {
+        let blocksize = self.tx.lock().unwrap().page_size();
+        Ok(self.free_space(None)? >= self.reserve(new_entry, blocksize))
+    }
+
+    fn reserve(&self, new_entry: bool, blocksize: usize) -> usize {
+        let reserve = self
+            .empty_record(0)
+            .len()
+            .max(self.layout.slot_size().min(blocksize / 4));
+        if new_entry {
+            return reserve + RecordPage::ENTRY_SIZE;
+        }
+        reserve
+    }
+
+    fn visible_image(&mut self, slot: usize) -> Result<Option<Page>, TransactionError> {
+        let mut rec = Page::with_vec(self.read_record(slot)?);
+        loop {
+            let xmin = rec.get_int(RecordPage::XMIN) as usize;
+            let xmax = rec.get_int(RecordPage::XMAX) as usize;
+            let tx = self.tx.lock().unwrap();
+            if tx.sees(xmin) {
+                let deleted = xmax != 0 && tx.sees(xmax);
+                return Ok((!deleted).then_some(rec));
+            }
+            drop(tx);
+            match self.read_version(&rec)? {
+                Some(prev) => rec = prev,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn read_version(&mut self, rec: &Page) -> Result<Option<Page>, TransactionError> {
+        let prev = rec.get_int(RecordPage::PREV);
+        if prev == RecordPage::NO_VERSION {
+            return Ok(None);
+        }
+        let len = rec.get_int(RecordPage::PREV_LEN) as usize;
+        Ok(Some(Page::with_vec(self.overflow().read(prev, len)?)))
+    }
+
+    fn new_version(&mut self, slot: usize) -> Result<(), TransactionError> {
+        let tx = self.tx.lock().unwrap();
+        let (versioned, txnum) = (tx.is_versioned(), tx.tx_number());
+        drop(tx);
+        if !versioned {
+            return Ok(());
+        }
+        let mut rec = Page::with_vec(self.read_record(slot)?);
+        let xmin = rec.get_int(RecordPage::XMIN) as usize;
+        if xmin == txnum {
+            return Ok(());
+        }
+        if self.tx.lock().unwrap().is_obsolete(xmin) {
+            self.free_versions(&rec)?;
+            rec.set_int(RecordPage::PREV, RecordPage::NO_VERSION);
+            rec.set_int(RecordPage::PREV_LEN, 0);
+        }
+        let mut old = Page::with_vec(self.inline_fields(rec.contents().clone())?);
+        old.set_int(RecordPage::XMAX, txnum as i32);
+        let prev = self.overflow().write(old.contents())?;
+        rec.set_int(RecordPage::XMIN, txnum as i32);
+        rec.set_int(RecordPage::PREV, prev);
+        rec.set_int(RecordPage::PREV_LEN, old.contents().len() as i32);
+        let (offset, _) = self.entry(slot)?;
+        let header = rec.get_raw(0, RecordPage::TX_HEADER);
         self.tx
             .lock()
             .unwrap()
-            .set_int(&self.blk, self.offset(slot), flag as i32, true)
+            .set_bytes(&self.blk, offset, header, true)
     }
 
-    fn search_after(
-        &mut self,
-        slot: Option<usize>,
-        flag: Flag,
-    ) -> Result<Option<usize>, TransactionError> {
-        let mut s = 0;
-        if let Some(slot) = slot {
-            s = slot + 1;
-        }
-        let f = flag as i32;
-        while self.is_valid_slot(s) {
-            if self.tx.lock().unwrap().get_int(&self.blk, self.offset(s))? == f {
-                return Ok(Some(s));
+    fn inline_fields(&mut self, image: Vec<u8>) -> Result<Vec<u8>, TransactionError> {
+        let mut rec = Page::with_vec(image);
+        let sch = self.layout.schema();
+        for fldname in sch.fields() {
+            let pos = self.field_pos(&rec, fldname);
+            if sch.type_(fldname).is_variable_length() && rec.get_int(pos) == RecordPage::OVERFLOW {
+                let bytes = self.read_bytes(&rec, pos)?;
+                let mut field = Page::new(Page::bytes_length(bytes.len()));
+                field.set_bytes(0, &bytes);
+                let range = pos..pos + RecordPage::OVERFLOW_SIZE;
+                rec.contents()
+                    .splice(range, field.contents().iter().copied());
             }
-            s += 1;
         }
-        Ok(None)
+        Ok(rec.contents().clone())
     }
 
-    fn is_valid_slot(&self, slot: usize) -> bool {
-        self.offset(slot + 1) <= self.tx.lock().unwrap().block_size()
+    fn free_versions(&mut self, rec: &Page) -> Result<(), TransactionError> {
+        let mut first = rec.get_int(RecordPage::PREV);
+        let mut next = self.read_version(rec)?;
+        while let Some(old) = next {
+            self.overflow().free(first)?;
+            first = old.get_int(RecordPage::PREV);
+            next = self.read_version(&old)?;
+        }
+        Ok(())
+    }
+
+    fn purge(&mut self, slot: usize) -> Result<(), TransactionError> {
+        let rec = Page::with_vec(self.read_record(slot)?);
+        let sch = self.layout.schema();
+        for fldname in sch.fields() {
+            if sch.type_(fldname).is_variable_length() {
+                self.free_overflow(&rec, self.field_pos(&rec, fldname))?;
+            }
+        }
+        self.free_versions(&rec)?;
+        self.set_entry(slot, 0, 0)
+    }
+
+    fn vacuum(&mut self) -> Result<bool, TransactionError> {
+        if !self.tx.lock().unwrap().is_versioned() {
+            return Ok(false);
+        }
+        let mut purged = false;
+        for s in 0..self.slot_count()? {
+            let (offset, _) = self.entry(s)?;
+            if offset == 0 {
+                continue;
+            }
+            let mut tx = self.tx.lock().unwrap();
+            let xmax = tx.get_int(&self.blk, offset + RecordPage::XMAX)? as usize;
+            let dead = xmax != 0 && tx.is_obsolete(xmax);
+            drop(tx);
+            if dead {
+                self.purge(s)?;
+                purged = true;
+            }
+        }
+        Ok(purged)
+    }
+
+    fn read_record(&mut self, slot: usize) -> Result<Vec<u8>, TransactionError> {
+        let (offset, len) = self.entry(slot)?;
+        if offset == 0 {
+            return Err(TransactionError::General);
+        }
+        self.tx.lock().unwrap().get_bytes(&self.blk, offset, len)
+    }
+
+    fn write_record(&mut self, slot: usize, mut image: Vec<u8>) -> Result<(), TransactionError> {
+        let (offset, len) = self.entry(slot)?;
+        if offset != 0 && image.len() <= len {
+            self.tx
+                .lock()
+                .unwrap()
+                .set_bytes(&self.blk, offset, &image, true)?;
+            if image.len() != len {
+                self.set_entry(slot, offset, image.len())?;
+            }
+            return Ok(());
+        }
+        while image.len() > self.free_space(Some(slot))? {
+            image = self.spill_largest(image)?;
+        }
+        self.set_entry(slot, 0, 0)?;
+        if image.len() > self.contiguous_space()? {
+            self.compact()?;
+        }
+        let start = self.data_start()? - image.len();
+        let mut tx = self.tx.lock().unwrap();
+        tx.set_bytes(&self.blk, start, &image, true)?;
+        tx.set_int(&self.blk, RecordPage::DATA_START, start as i32, true)?;
+        drop(tx);
+        self.set_entry(slot, start, image.len())
+    }
+
+    fn spill_largest(&mut self, image: Vec<u8>) -> Result<Vec<u8>, TransactionError> {
+        let mut rec = Page::with_vec(image);
+        let sch = self.layout.schema();
+        let mut largest: Option<(usize, usize)> = None;
+        for fldname in sch.fields() {
+            if sch.type_(fldname).is_variable_length() {
+                let pos = self.field_pos(&rec, fldname);
+                let len = self.field_len(&rec, fldname, pos);
+                if len > RecordPage::OVERFLOW_SIZE && largest.is_none_or(|(_, l)| len > l) {
+                    largest = Some((pos, len));
+                }
+            }
+        }
+        let (pos, len) = largest.ok_or(TransactionError::General)?;
+        let mut field = self.encode_overflow(rec.get_bytes(pos))?;
+        let mut image = rec.contents().clone();
+        image.splice(pos..pos + len, field.contents().iter().copied());
+        Ok(image)
+    }
+
+    fn compact(&mut self) -> Result<(), TransactionError> {
+        let count = self.slot_count()?;
+        let mut live = Vec::new();
+        for s in 0..count {
+            let (offset, len) = self.entry(s)?;
+            if offset != 0 {
+                live.push((offset, len, s));
+            }
+        }
+        live.sort_by_key(|(offset, _, _)| Reverse(*offset));
+        let blocksize = self.tx.lock().unwrap().page_size();
+        let mut images = Vec::with_capacity(live.len());
+        let mut start = blocksize;
+        for (offset, len, s) in live {
+            let image = self.tx.lock().unwrap().get_bytes(&self.blk, offset, len)?;
+            start -= len;
+            images.push((start, image, offset, s));
+        }
+        let mut region = vec![0; blocksize - start];
+        for (newoffset, image, _, _) in images.iter() {
+            let pos = newoffset - start;
+            region[pos..pos + image.len()].copy_from_slice(image);
+        }
+        let mut tx = self.tx.lock().unwrap();
+        tx.set_bytes(&self.blk, start, &region, true)?;
+        tx.set_int(&self.blk, RecordPage::DATA_START, start as i32, true)?;
+        drop(tx);
+        for (newoffset, image, offset, s) in images {
+            if newoffset != offset {
+                self.set_entry(s, newoffset, image.len())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, rec: &Page, pos: usize) -> Result<Vec<u8>, TransactionError> {
+        if rec.get_int(pos) == RecordPage::OVERFLOW {
+            let len = rec.get_int(pos + 4) as usize;
+            return self.overflow().read(rec.get_int(pos + 8), len);
+        }
+        Ok(rec.get_bytes(pos).to_vec())
+    }
+
+    fn encode_bytes(&mut self, bytes: &[u8]) -> Result<Page, TransactionError> {
+        let blocksize = self.tx.lock().unwrap().page_size();
+        if Page::bytes_length(bytes.len()) > blocksize / 4 {
+            return self.encode_overflow(bytes);
+        }
+        let mut field = Page::new(Page::bytes_length(bytes.len()));
+        field.set_bytes(0, bytes);
+        Ok(field)
+    }
+
+    fn encode_overflow(&mut self, bytes: &[u8]) -> Result<Page, TransactionError> {
+        let first = self.overflow().write(bytes)?;
+        let mut field = Page::new(RecordPage::OVERFLOW_SIZE);
+        field.set_int(0, RecordPage::OVERFLOW);
+        field.set_int(4, bytes.len() as i32);
+        field.set_int(8, first);
+        Ok(field)
+    }
+
+    fn free_overflow(&mut self, rec: &Page, pos: usize) -> Result<(), TransactionError> {
+        if rec.get_int(pos) == RecordPage::OVERFLOW {
+            self.overflow().free(rec.get_int(pos + 8))?;
+        }
+        Ok(())
+    }
+
+    fn overflow(&self) -> OverflowFile {
+        OverflowFile::new(self.tx.clone(), self.blk.file_name())
+    }
+
+    fn empty_record(&self, xmin: usize) -> Vec<u8> {
+        let sch = self.layout.schema();
+        let bytes = 4;
+        let flags = RecordPage::TX_HEADER;
+        let mut len = flags + bytes * self.layout.flag_words();
+        for fldname in sch.fields() {
+            len += match sch.type_(fldname) {
+                t if t.is_variable_length() => bytes,
+                _ => self.layout.length_in_bytes(fldname),
+            };
+        }
+        let mut rec = Page::new(len);
+        rec.set_int(RecordPage::XMIN, xmin as i32);
+        rec.contents()[flags..flags + bytes * self.layout.flag_words()].fill(0xff);
+        rec.contents().clone()
+    }
+
+    fn null_bit(&self, fldname: &str) -> (usize, i32) {
+        let (pos, mask) = self.layout.null_bit(fldname);
+        (RecordPage::TX_HEADER + pos, mask)
+    }
+
+    fn field_pos(&self, rec: &Page, fldname: &str) -> usize {
+        let bytes = 4;
+        let mut pos = RecordPage::TX_HEADER + bytes * self.layout.flag_words();
+        for f in self.layout.schema().fields() {
+            if f == fldname {
+                break;
+            }
+            pos += self.field_len(rec, f, pos);
+        }
+        pos
+    }
+
+    fn field_len(&self, rec: &Page, fldname: &str, pos: usize) -> usize {
+        match self.layout.schema().type_(fldname) {
+            t if !t.is_variable_length() => self.layout.length_in_bytes(fldname),
+            _ if rec.get_int(pos) == RecordPage::OVERFLOW => RecordPage::OVERFLOW_SIZE,
+            _ => Page::bytes_length(rec.get_int(pos) as usize),
+        }
+    }
+
+    fn free_space(&mut self, except: Option<usize>) -> Result<usize, TransactionError> {
+        let count = self.slot_count()?;
+        let mut used = RecordPage::HEADER_SIZE + RecordPage::ENTRY_SIZE * count;
+        for s in 0..count {
+            if Some(s) != except {
+                used += self.entry(s)?.1;
+            }
+        }
+        Ok(self.tx.lock().unwrap().page_size().saturating_sub(used))
+    }
+
+    fn contiguous_space(&mut self) -> Result<usize, TransactionError> {
+        let count = self.slot_count()?;
+        let dirend = RecordPage::HEADER_SIZE + RecordPage::ENTRY_SIZE * count;
+        Ok(self.data_start()?.saturating_sub(dirend))
+    }
+
+    fn slot_count(&mut self) -> Result<usize, TransactionError> {
+        let count = self
+            .tx
+            .lock()
+            .unwrap()
+            .get_int(&self.blk, RecordPage::SLOT_COUNT)?;
+        Ok(count as usize)
+    }
+
+    fn data_start(&mut self) -> Result<usize, TransactionError> {
+        let start = self
+            .tx
+            .lock()
+            .unwrap()
+            .get_int(&self.blk, RecordPage::DATA_START)?;
+        Ok(start as usize)
     }
 
-    fn offset(&self, slot: usize) -> usize {
-        slot * self.layout.slot_size()
+    fn entry(&mut self, slot: usize) -> Result<(usize, usize), TransactionError> {
+        let pos = RecordPage::HEADER_SIZE + RecordPage::ENTRY_SIZE * slot;
+        let mut tx = self.tx.lock().unwrap();
+        let offset = tx.get_int(&self.blk, pos)?;
+        let len = tx.get_int(&self.blk, pos + 4)?;
+        Ok((offset as usize, len as usize))
+    }
+
+    fn set_entry(
+        &mut self,
+        slot: usize,
+        offset: usize,
+        len: usize,
+    ) -> Result<(), TransactionError> {
+        let pos = RecordPage::HEADER_SIZE + RecordPage::ENTRY_SIZE * slot;
+        let mut tx = self.tx.lock().unwrap();
+        tx.set_int(&self.blk, pos, offset as i32, true)?;
+        tx.set_int(&self.blk, pos + 4, len as i32, true)
     }
 }
@@ -8,13 +8,14 @@ mod tests {
     use rand::{distributions::Uniform, prelude::Distribution};
 
     use crate::{
+        file::durability::Durability,
         record::{layout::Layout, recordpage::RecordPage, schema::Schema},
         server::simpledb::SimpleDB,
     };
 
     #[test]
     fn recordtest() {
-        let db = SimpleDB::with_params("recordtest", 400, 8).unwrap();
+        let db = SimpleDB::with_params("recordtest", 400, 8, Durability::NoSync).unwrap();
         let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
 
         let mut sch = Schema::new();
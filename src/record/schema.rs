@@ -2,8 +2,36 @@ use std::collections::HashMap;
 
 #[derive(Clone, Copy)]
 pub enum Type {
+    BigInt = -5,
     Integer = 4,
+    Double = 8,
     Varchar = 12,
+    Boolean = 16,
+    Date = 91,
+    Timestamp = 93,
+    Blob = 2004,
+    Text = 2005,
+}
+
+impl Type {
+    pub fn from_code(code: i32) -> Option<Type> {
+        match code {
+            x if x == Type::BigInt as i32 => Some(Type::BigInt),
+            x if x == Type::Integer as i32 => Some(Type::Integer),
+            x if x == Type::Double as i32 => Some(Type::Double),
+            x if x == Type::Varchar as i32 => Some(Type::Varchar),
+            x if x == Type::Boolean as i32 => Some(Type::Boolean),
+            x if x == Type::Date as i32 => Some(Type::Date),
+            x if x == Type::Timestamp as i32 => Some(Type::Timestamp),
+            x if x == Type::Blob as i32 => Some(Type::Blob),
+            x if x == Type::Text as i32 => Some(Type::Text),
+            _ => None,
+        }
+    }
+
+    pub fn is_variable_length(&self) -> bool {
+        matches!(self, Type::Varchar | Type::Text | Type::Blob)
+    }
 }
 
 pub struct Schema {
@@ -29,10 +57,38 @@ impl Schema {
         self.add_field(fldname, Type::Integer, 0)
     }
 
+    pub fn add_long_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::BigInt, 0)
+    }
+
+    pub fn add_double_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::Double, 0)
+    }
+
+    pub fn add_bool_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::Boolean, 0)
+    }
+
+    pub fn add_date_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::Date, 0)
+    }
+
+    pub fn add_timestamp_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::Timestamp, 0)
+    }
+
     pub fn add_string_field(&mut self, fldname: &str, length: usize) {
         self.add_field(fldname, Type::Varchar, length)
     }
 
+    pub fn add_text_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::Text, 0)
+    }
+
+    pub fn add_blob_field(&mut self, fldname: &str) {
+        self.add_field(fldname, Type::Blob, 0)
+    }
+
     pub fn add(&mut self, fldname: &str, sch: &Schema) {
         let type_ = sch.type_(fldname);
         let length = sch.length(fldname);
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        api::{
+            connection::ConnectionControl, driver::DriverControl,
+            embedded::embeddeddriver::EmbeddedDriver, resultset::ResultSetControl,
+            statement::StatementControl,
+        },
+        file::durability::Durability,
+        query::{constant::Constant, scan::ScanControl, updatescan::UpdateScanControl},
+        record::{layout::Layout, schema::Schema, tablescan::TableScan},
+        server::simpledb::SimpleDB,
+    };
+
+    #[test]
+    fn slottedpagetest() {
+        let db = SimpleDB::with_params("slottedpagetest", 400, 8, Durability::NoSync).unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+
+        let mut sch = Schema::new();
+        sch.add_int_field("A");
+        sch.add_string_field("B", 2000);
+        let layout = Layout::new(Arc::new(sch));
+
+        let mut ts = TableScan::new(tx.clone(), "T", layout.clone()).unwrap();
+        for i in 0..30 {
+            ts.insert().unwrap();
+            ts.set_int("A", i).unwrap();
+            ts.set_string("B", &"x".repeat(i as usize % 10)).unwrap();
+        }
+        assert!(ts.get_rid().unwrap().block_number() < 5);
+
+        let big = "0123456789".repeat(150);
+        ts.insert().unwrap();
+        ts.set_int("A", 100).unwrap();
+        ts.set_string("B", &big).unwrap();
+        let bigrid = ts.get_rid().unwrap();
+        let ovfsize = tx.lock().unwrap().size("T.ovf").unwrap();
+        assert!(ovfsize > 4);
+
+        ts.before_first().unwrap();
+        while ts.next().unwrap() {
+            let a = ts.get_int("A").unwrap();
+            if a == 7 {
+                ts.set_string("B", &"y".repeat(90)).unwrap();
+            } else if a == 8 {
+                ts.set_string("B", "").unwrap();
+            }
+        }
+        ts.before_first().unwrap();
+        let mut count = 0;
+        while ts.next().unwrap() {
+            let a = ts.get_int("A").unwrap();
+            let b = ts.get_string("B").unwrap();
+            match a {
+                7 => assert_eq!("y".repeat(90), b),
+                8 => assert_eq!("", b),
+                100 => assert_eq!(big, b),
+                _ => assert_eq!("x".repeat(a as usize % 10), b),
+            }
+            count += 1;
+        }
+        assert_eq!(31, count);
+
+        let ovfsize = tx.lock().unwrap().size("T.ovf").unwrap();
+        ts.move_to_rid(&bigrid).unwrap();
+        ts.delete().unwrap();
+        ts.insert().unwrap();
+        ts.set_int("A", 101).unwrap();
+        ts.set_string("B", &big.to_uppercase()).unwrap();
+        assert_eq!(ovfsize, tx.lock().unwrap().size("T.ovf").unwrap());
+        ts.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts = TableScan::new(tx.clone(), "T", layout.clone()).unwrap();
+        while ts.next().unwrap() {
+            if ts.get_int("A").unwrap() == 3 {
+                ts.set_string("B", &"z".repeat(1000)).unwrap();
+            }
+        }
+        ts.close().unwrap();
+        tx.lock().unwrap().rollback().unwrap();
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts = TableScan::new(tx.clone(), "T", layout).unwrap();
+        let mut found = Vec::new();
+        while ts.next().unwrap() {
+            let a = ts.get_int("A").unwrap();
+            let b = ts.get_string("B").unwrap();
+            if a == 3 || a == 101 {
+                found.push((a, b));
+            }
+        }
+        found.sort();
+        assert_eq!(
+            vec![(3, "xxx".to_string()), (101, big.to_uppercase())],
+            found
+        );
+        ts.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("slottedpagetest").unwrap();
+    }
+
+    #[test]
+    fn textblobtest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("textblobtest").unwrap();
+        let mut stmt = conn.create_statement();
+        let text = "ä".repeat(600);
+        stmt.execute_update("create table T(A int, B text, C blob)")
+            .unwrap();
+        stmt.execute_update(&format!(
+            "insert into T(A, B, C) values (1, '{}', 'ab')",
+            text
+        ))
+        .unwrap();
+        let mut rs = stmt.execute_query("select B, C from T").unwrap();
+        assert!(rs.next().unwrap());
+        assert_eq!(text, rs.get_string("b").unwrap());
+        assert_eq!("6162", rs.get_string("c").unwrap());
+        rs.close().unwrap();
+        assert!(stmt.execute_update("create index IB on T(B)").is_err());
+        conn.close().unwrap();
+
+        let db = SimpleDB::new("textblobtest").unwrap();
+        let mdm = db.md_mgr().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let layout = mdm.lock().unwrap().get_layout("t", tx.clone()).unwrap();
+        let blob: Vec<u8> = (0..1500).map(|i| (i % 256) as u8).collect();
+        let mut ts = TableScan::new(tx.clone(), "t", layout).unwrap();
+        assert!(ts.next().unwrap());
+        ts.set_val("c", Constant::with_bytes(&blob)).unwrap();
+        ts.before_first().unwrap();
+        assert!(ts.next().unwrap());
+        assert_eq!(Some(blob), ts.get_val("c").unwrap().as_bytes());
+        assert_eq!(Some(text), ts.get_val("b").unwrap().as_string());
+        ts.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("textblobtest").unwrap();
+    }
+}
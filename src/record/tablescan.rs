@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     buffer::buffermgr::AbortError,
@@ -7,7 +10,7 @@ use crate::{
     tx::transaction::{Transaction, TransactionError},
 };
 
-use super::{layout::Layout, recordpage::RecordPage, rid::Rid, schema::Type};
+use super::{freespacemap::FreeSpaceMap, layout::Layout, recordpage::RecordPage, rid::Rid};
 
 pub struct TableScan {
     tx: Arc<Mutex<Transaction>>,
@@ -15,10 +18,14 @@ pub struct TableScan {
     rp: Option<RecordPage>,
     filename: String,
     currentslot: Option<usize>,
+    free_space: Option<Arc<FreeSpaceMap>>,
 }
 
 impl ScanControl for TableScan {
     fn before_first(&mut self) -> Result<(), TransactionError> {
+        if self.rp.is_none() {
+            return Ok(());
+        }
         self.move_to_block(0)
     }
 
@@ -26,7 +33,7 @@ impl ScanControl for TableScan {
         if let Some(rp) = &mut self.rp {
             self.currentslot = rp.next_after(self.currentslot)?;
         } else {
-            return Err(TransactionError::General);
+            return Ok(false);
         }
         while self.currentslot.is_none() {
             if self.at_last_block()? {
@@ -53,6 +60,9 @@ impl ScanControl for TableScan {
     fn get_int(&mut self, fldname: &str) -> Result<i32, TransactionError> {
         if let Some(rp) = &mut self.rp {
             if let Some(currentslot) = self.currentslot {
+                if rp.is_null(currentslot, fldname)? {
+                    return Ok(0);
+                }
                 return rp.get_int(currentslot, fldname);
             }
         }
@@ -62,6 +72,9 @@ impl ScanControl for TableScan {
     fn get_string(&mut self, fldname: &str) -> Result<String, TransactionError> {
         if let Some(rp) = &mut self.rp {
             if let Some(currentslot) = self.currentslot {
+                if rp.is_null(currentslot, fldname)? {
+                    return Ok(String::new());
+                }
                 return rp.get_string(currentslot, fldname);
             }
         }
@@ -69,10 +82,12 @@ impl ScanControl for TableScan {
     }
 
     fn get_val(&mut self, fldname: &str) -> Result<Constant, TransactionError> {
-        match self.layout.schema().type_(fldname) {
-            Type::Integer => Ok(Constant::with_int(self.get_int(fldname)?)),
-            Type::Varchar => Ok(Constant::with_string(&self.get_string(fldname)?)),
+        if let Some(rp) = &mut self.rp {
+            if let Some(currentslot) = self.currentslot {
+                return rp.get_val(currentslot, fldname);
+            }
         }
+        Err(TransactionError::General)
     }
 
     fn has_field(&self, fldname: &str) -> bool {
@@ -89,14 +104,12 @@ impl ScanControl for TableScan {
 
 impl UpdateScanControl for TableScan {
     fn set_val(&mut self, fldname: &str, val: Constant) -> Result<(), TransactionError> {
-        match self.layout.schema().type_(fldname) {
-            Type::Integer => {
-                Ok(self.set_int(fldname, val.as_int().ok_or(TransactionError::General)?)?)
-            }
-            Type::Varchar => {
-                Ok(self.set_string(fldname, &val.as_string().ok_or(TransactionError::General)?)?)
+        if let Some(rp) = &mut self.rp {
+            if let Some(currentslot) = self.currentslot {
+                return rp.set_val(currentslot, fldname, &val);
             }
         }
+        Err(TransactionError::General)
     }
 
     fn set_int(&mut self, fldname: &str, val: i32) -> Result<(), TransactionError> {
@@ -125,22 +138,37 @@ impl UpdateScanControl for TableScan {
         } else {
             return Err(TransactionError::General);
         }
+        if self.currentslot.is_some() {
+            return Ok(());
+        }
+        let Some(fsm) = self.free_space.clone() else {
+            return self.insert_at_end();
+        };
+        if !fsm.is_tracked(&self.filename) {
+            let free = self.find_free_blocks()?;
+            fsm.track(&self.filename, free);
+        }
         while self.currentslot.is_none() {
-            if self.at_last_block()? {
-                self.move_to_new_block()?;
-            } else {
-                let mut blknum = None;
-                if let Some(rp) = &self.rp {
-                    blknum = Some(rp.block().number() + 1);
-                }
-                if let Some(blknum) = blknum {
-                    self.move_to_block(blknum)?;
-                } else {
-                    return Err(TransactionError::General);
+            match fsm.first_free(&self.filename) {
+                Some(blknum) => self.move_to_block(blknum)?,
+                None => {
+                    self.move_to_new_block()?;
+                    if let Some(rp) = &self.rp {
+                        fsm.mark_free(&self.filename, rp.block().number());
+                    }
                 }
             }
             if let Some(rp) = &mut self.rp {
-                self.currentslot = rp.insert_after(self.currentslot)?;
+                self.currentslot = rp.insert_after(None)?;
+                if self.currentslot.is_none() {
+                    let blknum = rp.block().number();
+                    fsm.mark_full(&self.filename, blknum);
+                    let (fsm, filename) = (fsm.clone(), self.filename.clone());
+                    self.tx
+                        .lock()
+                        .unwrap()
+                        .on_undo(Box::new(move || fsm.mark_free(&filename, blknum)));
+                }
             } else {
                 return Err(TransactionError::General);
             }
@@ -152,6 +180,9 @@ impl UpdateScanControl for TableScan {
         if let Some(rp) = &mut self.rp {
             if let Some(currentslot) = self.currentslot {
                 rp.delete(currentslot)?;
+                if let Some(fsm) = &self.free_space {
+                    fsm.mark_free(&self.filename, rp.block().number());
+                }
                 return Ok(());
             }
         }
@@ -181,6 +212,15 @@ impl TableScan {
         tx: Arc<Mutex<Transaction>>,
         tablname: &str,
         layout: Layout,
+    ) -> Result<TableScan, TransactionError> {
+        TableScan::with_free_space(tx, tablname, layout, None)
+    }
+
+    pub fn with_free_space(
+        tx: Arc<Mutex<Transaction>>,
+        tablname: &str,
+        layout: Layout,
+        free_space: Option<Arc<FreeSpaceMap>>,
     ) -> Result<TableScan, TransactionError> {
         let filename = format!("{}.tbl", tablname);
         let mut t = TableScan {
@@ -189,8 +229,13 @@ impl TableScan {
             rp: None,
             filename: filename.clone(),
             currentslot: None,
+            free_space,
         };
-        if tx.lock().unwrap().size(&filename)? == 0 {
+        let size = tx.lock().unwrap().size(&filename)?;
+        if size == 0 && tx.lock().unwrap().is_read_only() {
+            return Ok(t);
+        }
+        if size == 0 {
             t.move_to_new_block()?;
         } else {
             t.move_to_block(0)?;
@@ -216,6 +261,44 @@ impl TableScan {
         Ok(())
     }
 
+    fn insert_at_end(&mut self) -> Result<(), TransactionError> {
+        while self.currentslot.is_none() {
+            if self.at_last_block()? {
+                self.move_to_new_block()?;
+            } else {
+                let mut blknum = None;
+                if let Some(rp) = &self.rp {
+                    blknum = Some(rp.block().number() + 1);
+                }
+                if let Some(blknum) = blknum {
+                    self.move_to_block(blknum)?;
+                } else {
+                    return Err(TransactionError::General);
+                }
+            }
+            if let Some(rp) = &mut self.rp {
+                self.currentslot = rp.insert_after(self.currentslot)?;
+            } else {
+                return Err(TransactionError::General);
+            }
+        }
+        Ok(())
+    }
+
+    fn find_free_blocks(&mut self) -> Result<BTreeSet<i32>, TransactionError> {
+        let mut free = BTreeSet::new();
+        let size = self.tx.lock().unwrap().size(&self.filename)?;
+        for blknum in 0..size as i32 {
+            self.move_to_block(blknum)?;
+            if let Some(rp) = &mut self.rp {
+                if rp.may_have_room()? {
+                    free.insert(blknum);
+                }
+            }
+        }
+        Ok(free)
+    }
+
     fn at_last_block(&self) -> Result<bool, TransactionError> {
         if let Some(rp) = &self.rp {
             return Ok(
@@ -8,14 +8,18 @@ mod tests {
     use rand::{distributions::Uniform, prelude::Distribution};
 
     use crate::{
+        file::durability::Durability,
         query::{scan::ScanControl, updatescan::UpdateScanControl},
-        record::{layout::Layout, schema::Schema, tablescan::TableScan},
+        record::{
+            freespacemap::FreeSpaceMap, layout::Layout, rid::Rid, schema::Schema,
+            tablescan::TableScan,
+        },
         server::simpledb::SimpleDB,
     };
 
     #[test]
     fn tablescantest() {
-        let db = SimpleDB::with_params("tabletest", 400, 8).unwrap();
+        let db = SimpleDB::with_params("tabletest", 400, 8, Durability::NoSync).unwrap();
         let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
 
         let mut sch = Schema::new();
@@ -34,15 +38,25 @@ mod tests {
         let mut ts = TableScan::new(tx.clone(), "T", layout.clone()).unwrap();
         let mut rng = rand::thread_rng();
         let die = Uniform::from(0..50);
-        for i in 0..50 {
+        let mut rids: Vec<Rid> = Vec::new();
+        for _ in 0..50 {
             ts.insert().unwrap();
             let n = die.sample(&mut rng);
             ts.set_int("A", n).unwrap();
             ts.set_string("B", &format!("rec{}", n)).unwrap();
 
             let rid = ts.get_rid().unwrap();
-            assert_eq!(rid.block_number(), i / 19);
-            assert_eq!(rid.slot(), i as usize % 19);
+            match rids.last() {
+                Some(prev) if prev.block_number() == rid.block_number() => {
+                    assert_eq!(rid.slot(), prev.slot() + 1)
+                }
+                Some(prev) => {
+                    assert_eq!(rid.block_number(), prev.block_number() + 1);
+                    assert_eq!(rid.slot(), 0);
+                }
+                None => assert!(rid == Rid::new(0, 0)),
+            }
+            rids.push(rid);
             assert!(n >= 0 && n < 50);
         }
 
@@ -56,13 +70,7 @@ mod tests {
 
                 let rid = ts.get_rid().unwrap();
 
-                let blknum = rid.block_number();
-                assert!(blknum >= 0 && blknum <= 2);
-
-                let slot = rid.slot();
-                assert!(slot < 19);
-
-                assert!(blknum * 19 + (slot as i32) < 50);
+                assert!(rids.contains(&rid));
 
                 assert!(a < 25);
                 assert_eq!(format!("rec{}", a), b);
@@ -79,13 +87,7 @@ mod tests {
 
             let rid = ts.get_rid().unwrap();
 
-            let blknum = rid.block_number();
-            assert!(blknum >= 0 && blknum <= 2);
-
-            let slot = rid.slot();
-            assert!(slot < 19);
-
-            assert!(blknum * 19 + (slot as i32) < 50);
+            assert!(rids.contains(&rid));
 
             assert!(a >= 25);
             assert_eq!(format!("rec{}", a), b);
@@ -95,4 +97,75 @@ mod tests {
 
         fs::remove_dir_all("tabletest").unwrap();
     }
+
+    #[test]
+    fn freespacetest() {
+        let db = SimpleDB::with_params("freespacetest", 400, 8, Durability::NoSync).unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+
+        let mut sch = Schema::new();
+        sch.add_int_field("A");
+        sch.add_string_field("B", 9);
+        let layout = Layout::new(Arc::new(sch));
+
+        let fsm = Arc::new(FreeSpaceMap::new());
+        let mut ts =
+            TableScan::with_free_space(tx.clone(), "T", layout.clone(), Some(fsm.clone())).unwrap();
+        for i in 0..30 {
+            ts.insert().unwrap();
+            ts.set_int("A", i).unwrap();
+        }
+        let last = ts.get_rid().unwrap();
+        assert_eq!(2, last.block_number());
+        assert_eq!(9, last.slot());
+
+        ts.move_to_rid(&Rid::new(0, 5)).unwrap();
+        ts.delete().unwrap();
+        ts.move_to_rid(&Rid::new(1, 7)).unwrap();
+        ts.delete().unwrap();
+        assert_eq!(Some(0), fsm.first_free("T.tbl"));
+
+        ts.move_to_rid(&last).unwrap();
+        ts.insert().unwrap();
+        let rid = ts.get_rid().unwrap();
+        assert_eq!(0, rid.block_number());
+        assert_eq!(5, rid.slot());
+
+        ts.insert().unwrap();
+        let rid = ts.get_rid().unwrap();
+        assert_eq!(1, rid.block_number());
+        assert_eq!(7, rid.slot());
+
+        ts.insert().unwrap();
+        let rid = ts.get_rid().unwrap();
+        assert_eq!(3, rid.block_number());
+        assert_eq!(0, rid.slot());
+        assert_eq!(4, tx.lock().unwrap().size("T.tbl").unwrap());
+        ts.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts =
+            TableScan::with_free_space(tx.clone(), "T", layout.clone(), Some(fsm.clone())).unwrap();
+        for i in 0..20 {
+            ts.insert().unwrap();
+            ts.set_int("A", i).unwrap();
+        }
+        assert_eq!(5, ts.get_rid().unwrap().block_number());
+        assert_eq!(Some(5), fsm.first_free("T.tbl"));
+        ts.close().unwrap();
+        tx.lock().unwrap().rollback().unwrap();
+        assert_eq!(Some(3), fsm.first_free("T.tbl"));
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts = TableScan::with_free_space(tx.clone(), "T", layout, Some(fsm)).unwrap();
+        ts.insert().unwrap();
+        let rid = ts.get_rid().unwrap();
+        assert_eq!(3, rid.block_number());
+        assert_eq!(1, rid.slot());
+        ts.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("freespacetest").unwrap();
+    }
 }
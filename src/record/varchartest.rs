@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        api::{
+            connection::ConnectionControl, driver::DriverControl, driver::SQLError,
+            embedded::embeddeddriver::EmbeddedDriver, resultset::ResultSetControl,
+            statement::StatementControl,
+        },
+        file::durability::Durability,
+        plan::plan::PlanError,
+        query::{
+            querytest::tests::{assert_queries, execute_all},
+            scan::ScanControl,
+            updatescan::UpdateScanControl,
+        },
+        record::{layout::Layout, schema::Schema, tablescan::TableScan},
+        server::simpledb::SimpleDB,
+        tx::transaction::TransactionError,
+    };
+
+    #[test]
+    fn varchartest() {
+        let db = SimpleDB::with_params("varchartest", 400, 8, Durability::NoSync).unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+
+        let mut sch = Schema::new();
+        sch.add_string_field("A", 10);
+        sch.add_int_field("B");
+        let layout = Layout::new(Arc::new(sch));
+
+        let mut ts = TableScan::new(tx.clone(), "T", layout).unwrap();
+        ts.insert().unwrap();
+        ts.set_string("A", "äöüßéèêëçñ").unwrap();
+        ts.set_int("B", 7).unwrap();
+        assert!(matches!(
+            ts.set_string("A", "日本語のテキストです。"),
+            Err(TransactionError::StringTooLong(_))
+        ));
+        ts.insert().unwrap();
+        ts.set_string("A", "日本語のテキストです").unwrap();
+        ts.set_int("B", 8).unwrap();
+
+        ts.before_first().unwrap();
+        assert!(ts.next().unwrap());
+        assert_eq!("äöüßéèêëçñ", ts.get_string("A").unwrap());
+        assert_eq!(7, ts.get_int("B").unwrap());
+        assert!(ts.next().unwrap());
+        assert_eq!("日本語のテキストです", ts.get_string("A").unwrap());
+        assert_eq!(8, ts.get_int("B").unwrap());
+        ts.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("varchartest").unwrap();
+    }
+
+    #[test]
+    fn varcharsqltest() {
+        let d = EmbeddedDriver::new();
+        let mut conn = d.connect("varcharsqltest").unwrap();
+        let mut stmt = conn.create_statement();
+
+        execute_all(
+            &mut stmt,
+            &[
+                "create table T(A varchar(3), B int)",
+                "insert into T(A, B) values ('€€€', 1)",
+            ],
+        );
+        let result = stmt.execute_update("insert into T(A, B) values ('€€€€', 2)");
+        assert!(matches!(
+            result,
+            Err(SQLError::Plan(PlanError::Transaction(
+                TransactionError::StringTooLong(_)
+            )))
+        ));
+
+        assert_queries(
+            &mut stmt,
+            &[("select A from T", vec!["€€€".to_string()])],
+            |rs| rs.get_string("a").unwrap(),
+        );
+        conn.close().unwrap();
+
+        fs::remove_dir_all("varcharsqltest").unwrap();
+    }
+}
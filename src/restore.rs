@@ -0,0 +1,53 @@
+#![allow(dead_code)]
+
+use std::{env, process};
+
+use crate::{server::simpledb::SimpleDB, tx::recovery::recoverytarget::RecoveryTarget};
+
+mod api;
+mod buffer;
+mod file;
+mod index;
+mod log;
+mod materialize;
+mod metadata;
+mod multibuffer;
+mod opt;
+mod parse;
+mod plan;
+mod query;
+mod record;
+mod server;
+mod tx;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 6 {
+        eprintln!(
+            "usage: {} <backupdir> <archivedir> <dbdir> (lsn|txnum) <n> [logdir...]",
+            args[0]
+        );
+        process::exit(1);
+    }
+    let n = args[5].parse().unwrap_or_else(|_| {
+        eprintln!("invalid number: {}", args[5]);
+        process::exit(1);
+    });
+    let target = match args[4].as_str() {
+        "lsn" => RecoveryTarget::Lsn(n),
+        "txnum" => RecoveryTarget::TxNum(n),
+        _ => {
+            eprintln!("unknown recovery target: {}", args[4]);
+            process::exit(1);
+        }
+    };
+    let mut log_dirs = vec![args[2].as_str()];
+    log_dirs.extend(args[6..].iter().map(|s| s.as_str()));
+    match SimpleDB::restore(&args[1], &log_dirs, &args[3], target) {
+        Ok(()) => println!("restored {} to {:?}", args[3], target),
+        Err(e) => {
+            eprintln!("restore failed: {:?}", e);
+            process::exit(1);
+        }
+    }
+}
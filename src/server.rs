@@ -1 +1,3 @@
+pub mod dbconfig;
+pub mod dbconfigtest;
 pub mod simpledb;
@@ -0,0 +1,174 @@
+use std::{
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+use crate::{file::durability::Durability, tx::concurrency::locktable::LockPolicy};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DbConfig {
+    block_size: usize,
+    buffer_size: usize,
+    log_file: String,
+    durability: Durability,
+    lock_policy: LockPolicy,
+    mvcc: bool,
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        DbConfig::new()
+    }
+}
+
+impl DbConfig {
+    pub const CONFIG_FILE: &'static str = "simpledb.conf";
+    const BLOCK_SIZE: usize = 400;
+    const BUFFER_SIZE: usize = 8;
+    const LOG_FILE: &'static str = "simpledb.log";
+    const DURABILITY: Durability = Durability::Fsync;
+    const LOCK_POLICY: LockPolicy = LockPolicy::DeadlockDetection;
+    const MVCC: bool = false;
+
+    pub fn new() -> DbConfig {
+        DbConfig {
+            block_size: DbConfig::BLOCK_SIZE,
+            buffer_size: DbConfig::BUFFER_SIZE,
+            log_file: DbConfig::LOG_FILE.to_string(),
+            durability: DbConfig::DURABILITY,
+            lock_policy: DbConfig::LOCK_POLICY,
+            mvcc: DbConfig::MVCC,
+        }
+    }
+
+    pub fn load(path: &str) -> Result<DbConfig, Error> {
+        DbConfig::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_dir(dirname: &str) -> Result<DbConfig, Error> {
+        let path = Path::new(dirname).join(DbConfig::CONFIG_FILE);
+        if !path.exists() {
+            return Ok(DbConfig::new());
+        }
+        DbConfig::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> Result<DbConfig, Error> {
+        let mut config = DbConfig::new();
+        for line in text.lines() {
+            let line = match line.find('#') {
+                Some(pos) => &line[..pos],
+                None => line,
+            }
+            .trim();
+            if line.is_empty() || line.starts_with('[') {
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim().trim_matches('"')),
+                None => return Err(invalid(&format!("expected key = value: {}", line))),
+            };
+            match key {
+                "block_size" => config.block_size = parse_size(key, value)?,
+                "buffer_size" => config.buffer_size = parse_size(key, value)?,
+                "log_file" => config.log_file = value.to_string(),
+                "durability" => {
+                    config.durability = match value {
+                        "fsync" => Durability::Fsync,
+                        "nosync" => Durability::NoSync,
+                        _ => return Err(invalid(&format!("unknown durability: {}", value))),
+                    }
+                }
+                "lock_policy" => {
+                    config.lock_policy = match value {
+                        "deadlock_detection" => LockPolicy::DeadlockDetection,
+                        "wait_die" => LockPolicy::WaitDie,
+                        "wound_wait" => LockPolicy::WoundWait,
+                        _ => return Err(invalid(&format!("unknown lock policy: {}", value))),
+                    }
+                }
+                "mvcc" => {
+                    config.mvcc = match value {
+                        "true" => true,
+                        "false" => false,
+                        _ => return Err(invalid(&format!("invalid mvcc: {}", value))),
+                    }
+                }
+                _ => return Err(invalid(&format!("unknown key: {}", key))),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    pub fn log_file(&self) -> &str {
+        &self.log_file
+    }
+
+    pub fn durability(&self) -> Durability {
+        self.durability
+    }
+
+    pub fn lock_policy(&self) -> LockPolicy {
+        self.lock_policy
+    }
+
+    pub fn mvcc(&self) -> bool {
+        self.mvcc
+    }
+
+    #[allow(dead_code)]
+    pub fn with_block_size(mut self, block_size: usize) -> DbConfig {
+        self.block_size = block_size;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> DbConfig {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_log_file(mut self, log_file: &str) -> DbConfig {
+        self.log_file = log_file.to_string();
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_durability(mut self, durability: Durability) -> DbConfig {
+        self.durability = durability;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_lock_policy(mut self, lock_policy: LockPolicy) -> DbConfig {
+        self.lock_policy = lock_policy;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_mvcc(mut self, mvcc: bool) -> DbConfig {
+        self.mvcc = mvcc;
+        self
+    }
+}
+
+fn parse_size(key: &str, value: &str) -> Result<usize, Error> {
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(invalid(&format!("invalid {}: {}", key, value))),
+    }
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
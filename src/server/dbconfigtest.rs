@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::ErrorKind, path::Path};
+
+    use crate::{
+        file::durability::Durability,
+        server::{dbconfig::DbConfig, simpledb::SimpleDB},
+        tx::concurrency::locktable::LockPolicy,
+    };
+
+    #[test]
+    fn dbconfigtest() {
+        let text = "# simpledb settings\n\
+                    [storage]\n\
+                    block_size = 200\n\
+                    buffer_size = 4 # small pool\n\
+                    log_file = \"test.log\"\n\
+                    durability = \"nosync\"\n\
+                    lock_policy = wound_wait\n\
+                    mvcc = true\n";
+        let config = DbConfig::parse(text).unwrap();
+        assert_eq!(200, config.block_size());
+        assert_eq!(4, config.buffer_size());
+        assert_eq!("test.log", config.log_file());
+        assert_eq!(Durability::NoSync, config.durability());
+        assert_eq!(LockPolicy::WoundWait, config.lock_policy());
+        assert!(config.mvcc());
+
+        assert_eq!(DbConfig::new(), DbConfig::parse("").unwrap());
+        assert!(DbConfig::parse("block_size = 0").is_err());
+        assert!(DbConfig::parse("page_size = 400").is_err());
+        assert!(DbConfig::parse("block_size").is_err());
+        assert!(DbConfig::parse("lock_policy = timeout").is_err());
+        assert!(DbConfig::parse("mvcc = yes").is_err());
+    }
+
+    #[test]
+    fn blocksizetest() {
+        let config = "block_size = 200\nbuffer_size = 4\ndurability = nosync\n";
+        let mut db =
+            SimpleDB::with_config("blocksizetest", &DbConfig::parse(config).unwrap()).unwrap();
+        assert_eq!(200, db.file_mgr().block_size());
+        assert_eq!(4, db.buffer_mgr().lock().unwrap().available());
+        drop(db);
+
+        fs::write(
+            Path::new("blocksizetest").join(DbConfig::CONFIG_FILE),
+            config,
+        )
+        .unwrap();
+        let db = SimpleDB::new("blocksizetest").unwrap();
+        assert_eq!(200, db.file_mgr().block_size());
+        drop(db);
+
+        let e = SimpleDB::with_params("blocksizetest", 400, 8, Durability::NoSync)
+            .err()
+            .unwrap();
+        assert_eq!(ErrorKind::InvalidInput, e.kind());
+
+        fs::remove_dir_all("blocksizetest").unwrap();
+    }
+
+    #[test]
+    fn formatversiontest() {
+        let db = SimpleDB::new("formatversiontest").unwrap();
+        drop(db);
+
+        let info = Path::new("formatversiontest").join("simpledb.info");
+        fs::write(&info, "block_size = 400\n").unwrap();
+        let e = SimpleDB::with_params("formatversiontest", 400, 8, Durability::NoSync)
+            .err()
+            .unwrap();
+        assert_eq!(ErrorKind::InvalidData, e.kind());
+
+        fs::remove_file(&info).unwrap();
+        let e = SimpleDB::with_params("formatversiontest", 400, 8, Durability::NoSync)
+            .err()
+            .unwrap();
+        assert_eq!(ErrorKind::InvalidData, e.kind());
+
+        fs::remove_dir_all("formatversiontest").unwrap();
+    }
+}
@@ -1,47 +1,91 @@
-use std::io::Error;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use crate::buffer::backgroundwriter::BackgroundWriter;
 use crate::buffer::buffermgr::BufferMgr;
+use crate::buffer::replacementpolicy::ReplacementPolicy;
+use crate::file::durability::Durability;
 use crate::file::filemgr::FileMgr;
 use crate::index::planner::indexupdateplanner::IndexUpdatePlanner;
 use crate::log::logmgr::LogMgr;
 use crate::metadata::metadatamgr::MetadataMgr;
 use crate::opt::heuristicqueryplanner::HeuristicQueryPlanner;
 use crate::plan::planner::Planner;
-use crate::tx::transaction::{Transaction, TransactionError};
+use crate::tx::concurrency::locktable::LockTable;
+use crate::tx::mvcc::versionmgr::VersionMgr;
+use crate::tx::recovery::checkpointer::Checkpointer;
+use crate::tx::recovery::recoverymgr::RecoveryMgr;
+use crate::tx::recovery::recoverytarget::RecoveryTarget;
+use crate::tx::transaction::{advance_tx_num, Transaction, TransactionError};
+
+use super::dbconfig::DbConfig;
 
 pub struct SimpleDB {
     fm: Arc<FileMgr>,
     lm: Arc<Mutex<LogMgr>>,
     bm: Arc<Mutex<BufferMgr>>,
+    lt: Arc<LockTable>,
+    vm: Arc<VersionMgr>,
+    checkpointer: Option<Checkpointer>,
+    writer: Option<BackgroundWriter>,
     mdm: Option<Arc<Mutex<MetadataMgr>>>,
     planner: Option<Arc<Mutex<Planner>>>,
 }
 
 impl SimpleDB {
-    const BLOCK_SIZE: usize = 400;
-    const BUFFER_SIZE: usize = 8;
-    const LOG_FILE: &'static str = "simpledb.log";
-
+    #[allow(dead_code)]
     pub fn with_params(
         dirname: &str,
         blocksize: usize,
         buffsize: usize,
+        durability: Durability,
     ) -> Result<SimpleDB, Error> {
-        let fm = Arc::new(FileMgr::new(dirname, blocksize)?);
-        let lm = Arc::new(Mutex::new(LogMgr::new(fm.clone(), SimpleDB::LOG_FILE)?));
-        let bm = Arc::new(Mutex::new(BufferMgr::new(fm.clone(), lm.clone(), buffsize)));
+        let config = DbConfig::new()
+            .with_block_size(blocksize)
+            .with_buffer_size(buffsize)
+            .with_durability(durability);
+        SimpleDB::open(dirname, &config)
+    }
+
+    pub fn open(dirname: &str, config: &DbConfig) -> Result<SimpleDB, Error> {
+        let fm = Arc::new(FileMgr::new(
+            dirname,
+            config.block_size(),
+            config.durability(),
+        )?);
+        advance_tx_num(fm.reserved_tx_num());
+        let lm = Arc::new(Mutex::new(LogMgr::new(fm.clone(), config.log_file())?));
+        let bm = Arc::new(Mutex::new(BufferMgr::new(
+            fm.clone(),
+            lm.clone(),
+            config.buffer_size(),
+        )));
+        let lt = Arc::new(LockTable::new(config.lock_policy()));
+        let vm = Arc::new(VersionMgr::new(config.mvcc()));
         Ok(SimpleDB {
             fm,
             lm,
             bm,
+            lt,
+            vm,
+            checkpointer: None,
+            writer: None,
             mdm: None,
             planner: None,
         })
     }
 
+    #[allow(dead_code)]
     pub fn new(dirname: &str) -> Result<SimpleDB, TransactionError> {
-        let mut sd = SimpleDB::with_params(dirname, SimpleDB::BLOCK_SIZE, SimpleDB::BUFFER_SIZE)?;
+        let config = DbConfig::from_dir(dirname)?;
+        SimpleDB::with_config(dirname, &config)
+    }
+
+    pub fn with_config(dirname: &str, config: &DbConfig) -> Result<SimpleDB, TransactionError> {
+        let mut sd = SimpleDB::open(dirname, config)?;
         let tx = Arc::new(Mutex::new(sd.new_tx()?));
         let isnew = sd.fm.is_new();
         if isnew {
@@ -60,8 +104,103 @@ impl SimpleDB {
         Ok(sd)
     }
 
+    #[allow(dead_code)]
+    pub fn restore(
+        backup_dir: &str,
+        log_dirs: &[&str],
+        dirname: &str,
+        target: RecoveryTarget,
+    ) -> Result<(), TransactionError> {
+        if Path::new(dirname).exists() {
+            return Err(Error::from(ErrorKind::AlreadyExists).into());
+        }
+        let config = DbConfig::from_dir(backup_dir)?;
+        fs::create_dir_all(dirname)?;
+        let prefix = format!("{}.", config.log_file());
+        for dir in [backup_dir].iter().chain(log_dirs) {
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let name = entry.file_name();
+                let is_log = name.to_string_lossy().starts_with(&prefix);
+                if !entry.file_type()?.is_file() || (*dir != backup_dir && !is_log) {
+                    continue;
+                }
+                let dest = Path::new(dirname).join(&name);
+                if dest.exists() && fs::metadata(&dest)?.len() > entry.metadata()?.len() {
+                    continue;
+                }
+                fs::copy(entry.path(), dest)?;
+            }
+        }
+        let db = SimpleDB::open(dirname, &config)?;
+        let mut tx = db.new_tx()?;
+        tx.recover_to(target)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn with_replacement_policy(self, policy: ReplacementPolicy) -> SimpleDB {
+        self.bm.lock().unwrap().set_replacement_policy(policy);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_checkpoint_interval(mut self, interval: Duration) -> SimpleDB {
+        self.checkpointer = Some(Checkpointer::new(
+            self.lm.clone(),
+            self.bm.clone(),
+            self.vm.clone(),
+            interval,
+        ));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_background_writer(mut self, interval: Duration) -> SimpleDB {
+        self.writer = Some(BackgroundWriter::new(self.bm.clone(), interval));
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_group_commit_wait(self, max_wait: Duration) -> SimpleDB {
+        self.lm.lock().unwrap().set_max_wait(max_wait);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn checkpoint(&self) -> Result<(), Error> {
+        RecoveryMgr::checkpoint(&self.lm, &self.bm, &self.vm)
+    }
+
+    #[allow(dead_code)]
+    pub fn log_size(&self) -> Result<usize, Error> {
+        self.lm.lock().unwrap().size()
+    }
+
+    #[allow(dead_code)]
+    pub fn oldest_needed_lsn(&self) -> usize {
+        RecoveryMgr::oldest_needed_lsn(&self.lm.lock().unwrap(), &self.vm)
+    }
+
     pub fn new_tx(&self) -> Result<Transaction, Error> {
-        Transaction::new(self.fm.clone(), self.lm.clone(), self.bm.clone())
+        Transaction::new(
+            self.fm.clone(),
+            self.lm.clone(),
+            self.bm.clone(),
+            self.lt.clone(),
+            self.vm.clone(),
+        )
+    }
+
+    pub fn new_read_only_tx(&self) -> Result<Transaction, Error> {
+        Transaction::read_only(
+            self.fm.clone(),
+            self.lm.clone(),
+            self.bm.clone(),
+            self.lt.clone(),
+            self.vm.clone(),
+        )
     }
 
     #[allow(dead_code)]
@@ -87,4 +226,14 @@ impl SimpleDB {
     pub fn buffer_mgr(&mut self) -> Arc<Mutex<BufferMgr>> {
         self.bm.clone()
     }
+
+    #[allow(dead_code)]
+    pub fn lock_table(&self) -> Arc<LockTable> {
+        self.lt.clone()
+    }
+
+    #[allow(dead_code)]
+    pub fn version_mgr(&self) -> Arc<VersionMgr> {
+        self.vm.clone()
+    }
 }
@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, time::Duration};
 
 use tonic::transport::Server;
 
@@ -12,7 +12,7 @@ use crate::{
             result_set_server::ResultSetServer, statement_server::StatementServer,
         },
     },
-    server::simpledb::SimpleDB,
+    server::{dbconfig::DbConfig, simpledb::SimpleDB},
 };
 
 mod api;
@@ -31,6 +31,10 @@ mod record;
 mod server;
 mod tx;
 
+const CHECKPOINT_INTERVAL: u64 = 60;
+const GROUP_COMMIT_WAIT: u64 = 2;
+const BACKGROUND_WRITER_INTERVAL: u64 = 200;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
@@ -39,7 +43,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     } else {
         &args[1]
     };
-    let db = SimpleDB::new(dirname).unwrap();
+    let config = if args.len() > 2 {
+        DbConfig::load(&args[2])?
+    } else {
+        DbConfig::from_dir(dirname)?
+    };
+    let db = SimpleDB::with_config(dirname, &config)
+        .unwrap()
+        .with_checkpoint_interval(Duration::from_secs(CHECKPOINT_INTERVAL))
+        .with_group_commit_wait(Duration::from_millis(GROUP_COMMIT_WAIT))
+        .with_background_writer(Duration::from_millis(BACKGROUND_WRITER_INTERVAL));
 
     let addr = "[::1]:1099".parse().unwrap();
 
@@ -1,6 +1,10 @@
 pub mod bufferlist;
 pub mod concurrency;
 pub mod concurrencytest;
+pub mod isolationlevel;
+pub mod isolationtest;
+pub mod mvcc;
 pub mod recovery;
+pub mod savepointtest;
 pub mod transaction;
 pub mod txtest;
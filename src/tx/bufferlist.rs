@@ -32,7 +32,7 @@ impl BufferList {
     }
 
     pub(in crate::tx) fn pin(&mut self, blk: &BlockId) -> Result<(), AbortError> {
-        let idx = self.bm.lock().unwrap().pin(blk)?;
+        let idx = BufferMgr::pin(&self.bm, blk)?;
         self.buffers.insert(blk.clone(), idx);
         self.pins.push(blk.clone());
         Ok(())
@@ -1,2 +1,3 @@
 pub mod concurrencymgr;
 pub mod locktable;
+pub mod waitsforgraph;
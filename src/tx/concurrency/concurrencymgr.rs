@@ -1,26 +1,61 @@
-use once_cell::sync::Lazy;
-use std::{collections::HashMap, sync::Mutex};
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    buffer::buffermgr::AbortError, file::blockid::BlockId, tx::concurrency::locktable::LockTable,
+    buffer::buffermgr::AbortError,
+    file::blockid::BlockId,
+    tx::{
+        concurrency::locktable::LockTable, isolationlevel::IsolationLevel, transaction::Transaction,
+    },
 };
 
-static LOCKTBL: Lazy<Mutex<LockTable>> = Lazy::new(|| Mutex::new(LockTable::new()));
-
 pub struct ConcurrencyMgr {
+    txnum: usize,
+    locktbl: Arc<LockTable>,
     locks: HashMap<BlockId, String>,
+    isolation: IsolationLevel,
 }
 
 impl ConcurrencyMgr {
-    pub fn new() -> ConcurrencyMgr {
+    pub fn new(txnum: usize, locktbl: Arc<LockTable>) -> ConcurrencyMgr {
         ConcurrencyMgr {
+            txnum,
+            locktbl,
             locks: HashMap::new(),
+            isolation: IsolationLevel::Serializable,
         }
     }
 
+    pub fn isolation_level(&self) -> IsolationLevel {
+        self.isolation
+    }
+
+    pub fn set_isolation_level(&mut self, isolation: IsolationLevel) {
+        self.isolation = isolation;
+    }
+
     pub fn s_lock(&mut self, blk: &BlockId) -> Result<(), AbortError> {
+        if self.isolation == IsolationLevel::ReadUncommitted {
+            return Ok(());
+        }
+        self.acquire_s_lock(blk)
+    }
+
+    pub fn end_read(&mut self, blk: &BlockId) {
+        let release = match self.isolation {
+            IsolationLevel::ReadUncommitted => false,
+            IsolationLevel::ReadCommitted => true,
+            IsolationLevel::RepeatableRead => blk.number() == Transaction::END_OF_FILE,
+            IsolationLevel::Serializable => false,
+        };
+        if release && self.locks.get(blk).map(|l| l == "S").unwrap_or(false) {
+            self.locktbl.unlock(blk, self.txnum);
+            self.locks.remove(blk);
+        }
+    }
+
+    fn acquire_s_lock(&mut self, blk: &BlockId) -> Result<(), AbortError> {
         if !self.locks.contains_key(blk) {
-            LOCKTBL.lock().unwrap().s_lock(blk)?;
+            self.locktbl.s_lock(blk, self.txnum)?;
             self.locks.insert(blk.clone(), "S".to_string());
         }
         Ok(())
@@ -28,16 +63,22 @@ impl ConcurrencyMgr {
 
     pub fn x_lock(&mut self, blk: &BlockId) -> Result<(), AbortError> {
         if !self.has_x_lock(blk) {
-            self.s_lock(blk)?;
+            self.acquire_s_lock(blk)?;
+            self.locktbl.x_lock(blk, self.txnum)?;
             self.locks.insert(blk.clone(), "X".to_string());
         }
         Ok(())
     }
 
+    pub fn check_wounded(&self) -> Result<(), AbortError> {
+        self.locktbl.check_wounded(self.txnum)
+    }
+
     pub fn release(&mut self) {
         for blk in self.locks.keys() {
-            LOCKTBL.lock().unwrap().unlock(blk);
+            self.locktbl.unlock(blk, self.txnum);
         }
+        self.locktbl.finish(self.txnum);
         self.locks.clear();
     }
 
@@ -50,3 +91,9 @@ impl ConcurrencyMgr {
         false
     }
 }
+
+impl Drop for ConcurrencyMgr {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
-    collections::HashMap,
-    thread::{current, park_timeout},
+    collections::{HashMap, HashSet},
+    sync::{Condvar, Mutex, MutexGuard},
     time::{Duration, SystemTime},
 };
 
@@ -9,52 +9,222 @@ use crate::{
     file::blockid::BlockId,
 };
 
+use super::waitsforgraph::WaitsForGraph;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LockPolicy {
+    DeadlockDetection,
+    WaitDie,
+    WoundWait,
+}
+
 pub struct LockTable {
-    locks: HashMap<BlockId, i32>,
+    state: Mutex<LockState>,
+    released: Condvar,
+    policy: LockPolicy,
     max_time: u128,
 }
 
+struct LockState {
+    locks: HashMap<BlockId, Lock>,
+    waiters: HashMap<usize, BlockId>,
+    waits_for: WaitsForGraph,
+    victims: HashMap<usize, usize>,
+}
+
+#[derive(Default)]
+struct Lock {
+    sharers: HashSet<usize>,
+    exclusive: Option<usize>,
+}
+
 impl LockTable {
     const MAX_TIME: u128 = 10000;
 
-    pub fn new() -> LockTable {
+    pub fn new(policy: LockPolicy) -> LockTable {
         LockTable {
-            locks: HashMap::new(),
+            state: Mutex::new(LockState {
+                locks: HashMap::new(),
+                waiters: HashMap::new(),
+                waits_for: WaitsForGraph::new(),
+                victims: HashMap::new(),
+            }),
+            released: Condvar::new(),
+            policy,
             max_time: LockTable::MAX_TIME,
         }
     }
 
-    pub fn s_lock(&mut self, blk: &BlockId) -> Result<(), AbortError> {
+    pub fn s_lock(&self, blk: &BlockId, txnum: usize) -> Result<(), AbortError> {
+        let timestamp = SystemTime::now();
+        loop {
+            let mut state = self.state.lock().unwrap();
+            state.check_victim(txnum)?;
+            let holders = state.x_lock_holders(blk, txnum);
+            if holders.is_empty() {
+                state.waits_for.remove_waiter(txnum);
+                state.lock_entry(blk).sharers.insert(txnum);
+                return Ok(());
+            }
+            self.check_wait(&mut state, timestamp, txnum, holders)?;
+            self.wait(state, blk, txnum);
+        }
+    }
+
+    pub fn x_lock(&self, blk: &BlockId, txnum: usize) -> Result<(), AbortError> {
         let timestamp = SystemTime::now();
-        while self.has_x_lock(blk) && !waiting_too_long(timestamp, self.max_time)? {
-            park_timeout(Duration::from_millis(self.max_time as u64));
+        loop {
+            let mut state = self.state.lock().unwrap();
+            state.check_victim(txnum)?;
+            let mut holders = state.x_lock_holders(blk, txnum);
+            holders.extend(state.other_s_lock_holders(blk, txnum));
+            if holders.is_empty() {
+                state.waits_for.remove_waiter(txnum);
+                state.lock_entry(blk).exclusive = Some(txnum);
+                return Ok(());
+            }
+            self.check_wait(&mut state, timestamp, txnum, holders)?;
+            self.wait(state, blk, txnum);
+        }
+    }
+
+    pub(in crate::tx::concurrency) fn unlock(&self, blk: &BlockId, txnum: usize) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(lock) = state.locks.get_mut(blk) {
+            lock.sharers.remove(&txnum);
+            if lock.exclusive == Some(txnum) {
+                lock.exclusive = None;
+            }
+            if lock.sharers.is_empty() && lock.exclusive.is_none() {
+                state.locks.remove(blk);
+            }
         }
-        if self.has_x_lock(blk) {
+        let waiting: Vec<usize> = state
+            .waiters
+            .iter()
+            .filter(|(_, b)| *b == blk)
+            .map(|(t, _)| *t)
+            .collect();
+        for waiter in waiting {
+            state.waits_for.remove_edge(waiter, txnum);
+            state.waiters.remove(&waiter);
+        }
+        self.released.notify_all();
+    }
+
+    #[allow(dead_code)]
+    pub fn policy(&self) -> LockPolicy {
+        self.policy
+    }
+
+    pub(in crate::tx::concurrency) fn check_wounded(&self, txnum: usize) -> Result<(), AbortError> {
+        self.state.lock().unwrap().check_victim(txnum)
+    }
+
+    pub(in crate::tx::concurrency) fn finish(&self, txnum: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.victims.remove(&txnum);
+        state.waits_for.remove_waiter(txnum);
+    }
+
+    fn check_wait(
+        &self,
+        state: &mut LockState,
+        timestamp: SystemTime,
+        txnum: usize,
+        holders: HashSet<usize>,
+    ) -> Result<(), AbortError> {
+        if waiting_too_long(timestamp, self.max_time)? {
+            state.waits_for.remove_waiter(txnum);
             return Err(AbortError::General);
         }
-        let val = self.get_lock_val(blk);
-        self.locks.insert(blk.clone(), val + 1);
+        match self.policy {
+            LockPolicy::DeadlockDetection => {
+                let victims = state.victims.len();
+                state.detect_deadlock(txnum, holders)?;
+                if state.victims.len() > victims {
+                    self.released.notify_all();
+                }
+                Ok(())
+            }
+            LockPolicy::WaitDie => {
+                if let Some(older) = holders.into_iter().filter(|h| *h < txnum).min() {
+                    return Err(AbortError::Deadlock(older));
+                }
+                Ok(())
+            }
+            LockPolicy::WoundWait => {
+                let victims = state.victims.len();
+                for younger in holders.into_iter().filter(|h| *h > txnum) {
+                    state.wound(younger, txnum);
+                }
+                if state.victims.len() > victims {
+                    self.released.notify_all();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn wait(&self, mut state: MutexGuard<LockState>, blk: &BlockId, txnum: usize) {
+        state.waiters.insert(txnum, blk.clone());
+        let timeout = Duration::from_millis(self.max_time as u64);
+        let mut state = self.released.wait_timeout(state, timeout).unwrap().0;
+        state.waiters.remove(&txnum);
+    }
+}
+
+impl LockState {
+    fn check_victim(&mut self, txnum: usize) -> Result<(), AbortError> {
+        if let Some(conflicting) = self.victims.remove(&txnum) {
+            self.waits_for.remove_waiter(txnum);
+            return Err(AbortError::Deadlock(conflicting));
+        }
         Ok(())
     }
 
-    pub(in crate::tx::concurrency) fn unlock(&mut self, blk: &BlockId) {
-        let val = self.get_lock_val(blk);
-        if val > 1 {
-            self.locks.insert(blk.clone(), val - 1);
-        } else {
-            self.locks.remove(blk);
-            current().unpark();
+    fn detect_deadlock(&mut self, txnum: usize, holders: HashSet<usize>) -> Result<(), AbortError> {
+        self.waits_for.set_waits_for(txnum, holders);
+        if let Some(cycle) = self.waits_for.find_cycle(txnum) {
+            let pos = (0..cycle.len()).max_by_key(|i| cycle[*i]).unwrap();
+            let victim = cycle[pos];
+            let conflicting = cycle[(pos + 1) % cycle.len()];
+            if victim == txnum {
+                self.waits_for.remove_waiter(txnum);
+                return Err(AbortError::Deadlock(conflicting));
+            }
+            self.wound(victim, conflicting);
         }
+        Ok(())
     }
 
-    fn has_x_lock(&self, blk: &BlockId) -> bool {
-        self.get_lock_val(blk) < 0
+    fn wound(&mut self, victim: usize, conflicting: usize) {
+        self.waits_for.remove_waiter(victim);
+        self.victims.insert(victim, conflicting);
+        self.waiters.remove(&victim);
+    }
+
+    fn lock_entry(&mut self, blk: &BlockId) -> &mut Lock {
+        self.locks.entry(blk.clone()).or_default()
+    }
+
+    fn x_lock_holders(&self, blk: &BlockId, txnum: usize) -> HashSet<usize> {
+        let mut holders = HashSet::new();
+        if let Some(lock) = self.locks.get(blk) {
+            if let Some(x) = lock.exclusive {
+                if x != txnum {
+                    holders.insert(x);
+                }
+            }
+        }
+        holders
     }
 
-    fn get_lock_val(&self, blk: &BlockId) -> i32 {
-        if let Some(ival) = self.locks.get(blk) {
-            return *ival;
+    fn other_s_lock_holders(&self, blk: &BlockId, txnum: usize) -> HashSet<usize> {
+        let mut holders = HashSet::new();
+        if let Some(lock) = self.locks.get(blk) {
+            holders.extend(lock.sharers.iter().filter(|t| **t != txnum));
         }
-        0
+        holders
     }
 }
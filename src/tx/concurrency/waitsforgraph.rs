@@ -0,0 +1,63 @@
+use std::collections::{HashMap, HashSet};
+
+pub struct WaitsForGraph {
+    edges: HashMap<usize, HashSet<usize>>,
+}
+
+impl WaitsForGraph {
+    pub fn new() -> WaitsForGraph {
+        WaitsForGraph {
+            edges: HashMap::new(),
+        }
+    }
+
+    pub fn set_waits_for(&mut self, waiter: usize, holders: HashSet<usize>) {
+        self.edges.insert(waiter, holders);
+    }
+
+    pub fn remove_edge(&mut self, waiter: usize, holder: usize) {
+        if let Some(holders) = self.edges.get_mut(&waiter) {
+            holders.remove(&holder);
+            if holders.is_empty() {
+                self.edges.remove(&waiter);
+            }
+        }
+    }
+
+    pub fn remove_waiter(&mut self, waiter: usize) {
+        self.edges.remove(&waiter);
+    }
+
+    pub fn find_cycle(&self, start: usize) -> Option<Vec<usize>> {
+        let mut path = vec![start];
+        let mut visited = HashSet::new();
+        if self.search(start, start, &mut path, &mut visited) {
+            return Some(path);
+        }
+        None
+    }
+
+    fn search(
+        &self,
+        start: usize,
+        current: usize,
+        path: &mut Vec<usize>,
+        visited: &mut HashSet<usize>,
+    ) -> bool {
+        if let Some(holders) = self.edges.get(&current) {
+            for holder in holders {
+                if *holder == start {
+                    return true;
+                }
+                if visited.insert(*holder) {
+                    path.push(*holder);
+                    if self.search(start, *holder, path, visited) {
+                        return true;
+                    }
+                    path.pop();
+                }
+            }
+        }
+        false
+    }
+}
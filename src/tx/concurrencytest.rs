@@ -4,38 +4,50 @@ mod tests {
         fs,
         sync::{Arc, Mutex},
         thread,
-        time::Duration,
+        time::{Duration, SystemTime},
     };
 
     use crate::{
-        buffer::buffermgr::BufferMgr,
-        file::{blockid::BlockId, filemgr::FileMgr},
+        buffer::buffermgr::{AbortError, BufferMgr},
+        file::{blockid::BlockId, durability::Durability, filemgr::FileMgr},
         log::logmgr::LogMgr,
-        server::simpledb::SimpleDB,
-        tx::transaction::Transaction,
+        server::{dbconfig::DbConfig, simpledb::SimpleDB},
+        tx::{
+            concurrency::locktable::{LockPolicy, LockTable},
+            mvcc::versionmgr::VersionMgr,
+            transaction::{Transaction, TransactionError},
+        },
     };
 
     #[test]
     fn concurrencytest() {
-        let mut db = SimpleDB::with_params("concurrencytest", 400, 8).unwrap();
+        let mut db = SimpleDB::with_params("concurrencytest", 400, 8, Durability::NoSync).unwrap();
         let fm = db.file_mgr();
         let lm = db.log_mgr();
         let bm = db.buffer_mgr();
+        let lt = db.lock_table();
+        let vm = db.version_mgr();
 
         let fm_a = fm.clone();
         let lm_a = lm.clone();
         let bm_a = bm.clone();
-        let handler_a = thread::spawn(move || run_a(fm_a, lm_a, bm_a));
+        let lt_a = lt.clone();
+        let vm_a = vm.clone();
+        let handler_a = thread::spawn(move || run_a(fm_a, lm_a, bm_a, lt_a, vm_a));
 
         let fm_b = fm.clone();
         let lm_b = lm.clone();
         let bm_b = bm.clone();
-        let handler_b = thread::spawn(move || run_b(fm_b, lm_b, bm_b));
+        let lt_b = lt.clone();
+        let vm_b = vm.clone();
+        let handler_b = thread::spawn(move || run_b(fm_b, lm_b, bm_b, lt_b, vm_b));
 
         let fm_c = fm.clone();
         let lm_c = lm.clone();
         let bm_c = bm.clone();
-        let handler_c = thread::spawn(move || run_c(fm_c, lm_c, bm_c));
+        let lt_c = lt.clone();
+        let vm_c = vm.clone();
+        let handler_c = thread::spawn(move || run_c(fm_c, lm_c, bm_c, lt_c, vm_c));
 
         handler_a.join().unwrap();
         handler_b.join().unwrap();
@@ -44,8 +56,14 @@ mod tests {
         fs::remove_dir_all("concurrencytest").unwrap();
     }
 
-    fn run_a(fm: Arc<FileMgr>, lm: Arc<Mutex<LogMgr>>, bm: Arc<Mutex<BufferMgr>>) {
-        let mut tx_a = Transaction::new(fm, lm, bm).unwrap();
+    fn run_a(
+        fm: Arc<FileMgr>,
+        lm: Arc<Mutex<LogMgr>>,
+        bm: Arc<Mutex<BufferMgr>>,
+        lt: Arc<LockTable>,
+        vm: Arc<VersionMgr>,
+    ) {
+        let mut tx_a = Transaction::new(fm, lm, bm, lt, vm).unwrap();
         let blk1 = BlockId::new("testfile", 1);
         let blk2 = BlockId::new("testfile", 2);
         tx_a.pin(&blk1).unwrap();
@@ -60,8 +78,14 @@ mod tests {
         tx_a.commit().unwrap();
     }
 
-    fn run_b(fm: Arc<FileMgr>, lm: Arc<Mutex<LogMgr>>, bm: Arc<Mutex<BufferMgr>>) {
-        let mut tx_b = Transaction::new(fm, lm, bm).unwrap();
+    fn run_b(
+        fm: Arc<FileMgr>,
+        lm: Arc<Mutex<LogMgr>>,
+        bm: Arc<Mutex<BufferMgr>>,
+        lt: Arc<LockTable>,
+        vm: Arc<VersionMgr>,
+    ) {
+        let mut tx_b = Transaction::new(fm, lm, bm, lt, vm).unwrap();
         let blk1 = BlockId::new("testfile", 1);
         let blk2 = BlockId::new("testfile", 2);
         tx_b.pin(&blk1).unwrap();
@@ -76,8 +100,14 @@ mod tests {
         tx_b.commit().unwrap();
     }
 
-    fn run_c(fm: Arc<FileMgr>, lm: Arc<Mutex<LogMgr>>, bm: Arc<Mutex<BufferMgr>>) {
-        let mut tx_c = Transaction::new(fm, lm, bm).unwrap();
+    fn run_c(
+        fm: Arc<FileMgr>,
+        lm: Arc<Mutex<LogMgr>>,
+        bm: Arc<Mutex<BufferMgr>>,
+        lt: Arc<LockTable>,
+        vm: Arc<VersionMgr>,
+    ) {
+        let mut tx_c = Transaction::new(fm, lm, bm, lt, vm).unwrap();
         let blk1 = BlockId::new("testfile", 1);
         let blk2 = BlockId::new("testfile", 2);
         tx_c.pin(&blk1).unwrap();
@@ -92,4 +122,334 @@ mod tests {
 
         tx_c.commit().unwrap();
     }
+
+    #[test]
+    fn xlocktest() {
+        let db = SimpleDB::with_params("xlocktest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.pin(&blk).unwrap();
+        tx_a.set_int(&blk, 0, 1, false).unwrap();
+
+        let mut tx_b = db.new_tx().unwrap();
+        let blk_b = blk.clone();
+        let events_b = events.clone();
+        let handler_b = thread::spawn(move || {
+            tx_b.pin(&blk_b).unwrap();
+            tx_b.set_int(&blk_b, 0, 2, false).unwrap();
+            events_b.lock().unwrap().push("b wrote");
+            tx_b.commit().unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        events.lock().unwrap().push("a committed");
+        tx_a.commit().unwrap();
+        handler_b.join().unwrap();
+
+        assert_eq!(vec!["a committed", "b wrote"], *events.lock().unwrap());
+        let mut tx_c = db.new_tx().unwrap();
+        tx_c.pin(&blk).unwrap();
+        assert_eq!(2, tx_c.get_int(&blk, 0).unwrap());
+        tx_c.commit().unwrap();
+
+        fs::remove_dir_all("xlocktest").unwrap();
+    }
+
+    #[test]
+    fn slockwaitstest() {
+        let db = SimpleDB::with_params("slockwaitstest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.pin(&blk).unwrap();
+        tx_a.set_int(&blk, 0, 1, false).unwrap();
+
+        let mut tx_b = db.new_tx().unwrap();
+        let blk_b = blk.clone();
+        let events_b = events.clone();
+        let handler_b = thread::spawn(move || {
+            tx_b.pin(&blk_b).unwrap();
+            let val = tx_b.get_int(&blk_b, 0).unwrap();
+            events_b.lock().unwrap().push("b read");
+            tx_b.commit().unwrap();
+            val
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        tx_a.set_int(&blk, 0, 3, false).unwrap();
+        events.lock().unwrap().push("a committed");
+        tx_a.commit().unwrap();
+
+        assert_eq!(3, handler_b.join().unwrap());
+        assert_eq!(vec!["a committed", "b read"], *events.lock().unwrap());
+
+        fs::remove_dir_all("slockwaitstest").unwrap();
+    }
+
+    #[test]
+    fn lockwaketest() {
+        let db = SimpleDB::with_params("lockwaketest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.pin(&blk).unwrap();
+        tx_a.set_int(&blk, 0, 5, false).unwrap();
+
+        let mut tx_b = db.new_tx().unwrap();
+        let blk_b = blk.clone();
+        let handler_b = thread::spawn(move || {
+            let timestamp = SystemTime::now();
+            tx_b.pin(&blk_b).unwrap();
+            tx_b.set_int(&blk_b, 0, 6, false).unwrap();
+            tx_b.commit().unwrap();
+            timestamp.elapsed().unwrap()
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        tx_a.commit().unwrap();
+
+        assert!(handler_b.join().unwrap() < Duration::from_secs(1));
+
+        fs::remove_dir_all("lockwaketest").unwrap();
+    }
+
+    #[test]
+    fn upgradetest() {
+        let db = SimpleDB::with_params("upgradetest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.pin(&blk).unwrap();
+        tx_a.get_int(&blk, 0).unwrap();
+        tx_a.set_int(&blk, 0, 1, false).unwrap();
+        tx_a.commit().unwrap();
+
+        let mut tx_b = db.new_tx().unwrap();
+        tx_b.pin(&blk).unwrap();
+        tx_b.get_int(&blk, 0).unwrap();
+
+        let mut tx_c = db.new_tx().unwrap();
+        let blk_c = blk.clone();
+        let events_c = events.clone();
+        let handler_c = thread::spawn(move || {
+            tx_c.pin(&blk_c).unwrap();
+            tx_c.get_int(&blk_c, 0).unwrap();
+            tx_c.set_int(&blk_c, 0, 2, false).unwrap();
+            events_c.lock().unwrap().push("c upgraded");
+            tx_c.commit().unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        events.lock().unwrap().push("b committed");
+        tx_b.commit().unwrap();
+        handler_c.join().unwrap();
+
+        assert_eq!(vec!["b committed", "c upgraded"], *events.lock().unwrap());
+
+        fs::remove_dir_all("upgradetest").unwrap();
+    }
+
+    #[test]
+    fn deadlocktest() {
+        let db = SimpleDB::with_params("deadlocktest", 400, 8, Durability::NoSync).unwrap();
+        let blk1 = BlockId::new("testfile", 1);
+        let blk2 = BlockId::new("testfile", 2);
+        let timestamp = SystemTime::now();
+
+        let mut tx_a = db.new_tx().unwrap();
+        let mut tx_b = db.new_tx().unwrap();
+        let txnum_a = tx_a.tx_number();
+        tx_a.pin(&blk1).unwrap();
+        tx_a.pin(&blk2).unwrap();
+        tx_b.pin(&blk1).unwrap();
+        tx_b.pin(&blk2).unwrap();
+        tx_a.set_int(&blk1, 0, 1, true).unwrap();
+        tx_b.set_int(&blk2, 0, 2, true).unwrap();
+
+        let blk2_a = blk2.clone();
+        let handler_a = thread::spawn(move || {
+            tx_a.set_int(&blk2_a, 0, 1, true).unwrap();
+            tx_a.commit().unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        match tx_b.set_int(&blk1, 0, 2, true) {
+            Err(TransactionError::Abort(AbortError::Deadlock(txnum))) => {
+                assert_eq!(txnum_a, txnum)
+            }
+            _ => panic!("expected a deadlock"),
+        }
+        tx_b.rollback().unwrap();
+        handler_a.join().unwrap();
+
+        let mut tx_c = db.new_tx().unwrap();
+        tx_c.pin(&blk1).unwrap();
+        tx_c.pin(&blk2).unwrap();
+        assert_eq!(1, tx_c.get_int(&blk1, 0).unwrap());
+        assert_eq!(1, tx_c.get_int(&blk2, 0).unwrap());
+        tx_c.commit().unwrap();
+        assert!(SystemTime::now().duration_since(timestamp).unwrap() < Duration::from_secs(5));
+
+        fs::remove_dir_all("deadlocktest").unwrap();
+    }
+
+    #[test]
+    fn deadlockvictimtest() {
+        let db = SimpleDB::with_params("deadlockvictimtest", 400, 8, Durability::NoSync).unwrap();
+        let blk1 = BlockId::new("testfile", 1);
+        let blk2 = BlockId::new("testfile", 2);
+        let timestamp = SystemTime::now();
+
+        let mut tx_a = db.new_tx().unwrap();
+        let mut tx_b = db.new_tx().unwrap();
+        let txnum_a = tx_a.tx_number();
+        tx_a.pin(&blk1).unwrap();
+        tx_a.pin(&blk2).unwrap();
+        tx_b.pin(&blk1).unwrap();
+        tx_b.pin(&blk2).unwrap();
+        tx_a.get_int(&blk1, 0).unwrap();
+        tx_b.get_int(&blk2, 0).unwrap();
+
+        let blk1_b = blk1.clone();
+        let handler_b = thread::spawn(move || {
+            let result = tx_b.set_int(&blk1_b, 0, 2, true);
+            tx_b.rollback().unwrap();
+            result
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        tx_a.set_int(&blk2, 0, 1, true).unwrap();
+        match handler_b.join().unwrap() {
+            Err(TransactionError::Abort(AbortError::Deadlock(txnum))) => {
+                assert_eq!(txnum_a, txnum)
+            }
+            _ => panic!("expected a deadlock"),
+        }
+        tx_a.commit().unwrap();
+        assert!(SystemTime::now().duration_since(timestamp).unwrap() < Duration::from_secs(5));
+
+        fs::remove_dir_all("deadlockvictimtest").unwrap();
+    }
+
+    #[test]
+    fn waitdietest() {
+        let config = DbConfig::new()
+            .with_durability(Durability::NoSync)
+            .with_lock_policy(LockPolicy::WaitDie);
+        let db = SimpleDB::open("waitdietest", &config).unwrap();
+        assert_eq!(LockPolicy::WaitDie, db.lock_table().policy());
+        let blk1 = BlockId::new("testfile", 1);
+        let blk2 = BlockId::new("testfile", 2);
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        let mut tx_b = db.new_tx().unwrap();
+        let txnum_a = tx_a.tx_number();
+        tx_a.pin(&blk1).unwrap();
+        tx_a.pin(&blk2).unwrap();
+        tx_b.pin(&blk1).unwrap();
+        tx_b.pin(&blk2).unwrap();
+        tx_a.set_int(&blk1, 0, 1, true).unwrap();
+        tx_b.set_int(&blk2, 0, 2, true).unwrap();
+
+        match tx_b.set_int(&blk1, 0, 2, true) {
+            Err(TransactionError::Abort(AbortError::Deadlock(txnum))) => {
+                assert_eq!(txnum_a, txnum)
+            }
+            _ => panic!("expected the younger transaction to die"),
+        }
+
+        let events_b = events.clone();
+        let handler_b = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            events_b.lock().unwrap().push("b rolled back");
+            tx_b.rollback().unwrap();
+        });
+        tx_a.set_int(&blk2, 0, 1, true).unwrap();
+        events.lock().unwrap().push("a wrote");
+        tx_a.commit().unwrap();
+        handler_b.join().unwrap();
+
+        assert_eq!(vec!["b rolled back", "a wrote"], *events.lock().unwrap());
+
+        fs::remove_dir_all("waitdietest").unwrap();
+    }
+
+    #[test]
+    fn woundwaittest() {
+        let config = DbConfig::new()
+            .with_durability(Durability::NoSync)
+            .with_lock_policy(LockPolicy::WoundWait);
+        let db = SimpleDB::open("woundwaittest", &config).unwrap();
+        let blk1 = BlockId::new("testfile", 1);
+        let blk2 = BlockId::new("testfile", 2);
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        let mut tx_b = db.new_tx().unwrap();
+        let txnum_a = tx_a.tx_number();
+        tx_a.pin(&blk1).unwrap();
+        tx_b.pin(&blk1).unwrap();
+        tx_b.pin(&blk2).unwrap();
+        tx_b.set_int(&blk1, 0, 2, true).unwrap();
+
+        let events_a = events.clone();
+        let handler_a = thread::spawn(move || {
+            tx_a.set_int(&blk1, 0, 1, true).unwrap();
+            events_a.lock().unwrap().push("a wrote");
+            tx_a.commit().unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        match tx_b.set_int(&blk2, 0, 2, true) {
+            Err(TransactionError::Abort(AbortError::Deadlock(txnum))) => {
+                assert_eq!(txnum_a, txnum)
+            }
+            _ => panic!("expected the younger transaction to be wounded"),
+        }
+        events.lock().unwrap().push("b rolled back");
+        tx_b.rollback().unwrap();
+        handler_a.join().unwrap();
+
+        assert_eq!(vec!["b rolled back", "a wrote"], *events.lock().unwrap());
+
+        fs::remove_dir_all("woundwaittest").unwrap();
+    }
+
+    #[test]
+    fn woundcommittest() {
+        let config = DbConfig::new()
+            .with_durability(Durability::NoSync)
+            .with_lock_policy(LockPolicy::WoundWait);
+        let db = SimpleDB::open("woundcommittest", &config).unwrap();
+        let blk = BlockId::new("testfile", 1);
+
+        let mut tx_a = db.new_tx().unwrap();
+        let mut tx_b = db.new_tx().unwrap();
+        let txnum_a = tx_a.tx_number();
+        tx_a.pin(&blk).unwrap();
+        tx_b.pin(&blk).unwrap();
+        tx_b.set_int(&blk, 0, 2, true).unwrap();
+
+        let handler_a = thread::spawn(move || {
+            tx_a.set_int(&blk, 0, 1, true).unwrap();
+            tx_a.commit().unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        match tx_b.commit() {
+            Err(TransactionError::Abort(AbortError::Deadlock(txnum))) => {
+                assert_eq!(txnum_a, txnum)
+            }
+            _ => panic!("expected the wounded transaction not to commit"),
+        }
+        tx_b.rollback().unwrap();
+        handler_a.join().unwrap();
+
+        fs::remove_dir_all("woundcommittest").unwrap();
+    }
 }
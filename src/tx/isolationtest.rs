@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use crate::{
+        file::{blockid::BlockId, durability::Durability},
+        server::simpledb::SimpleDB,
+        tx::isolationlevel::IsolationLevel,
+    };
+
+    #[test]
+    fn readuncommittedtest() {
+        let db = SimpleDB::with_params("readuncommittedtest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+
+        let mut tx_b = db.new_tx().unwrap();
+        tx_b.pin(&blk).unwrap();
+        tx_b.set_int(&blk, 0, 7, true).unwrap();
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.set_isolation_level(IsolationLevel::ReadUncommitted);
+        tx_a.pin(&blk).unwrap();
+        assert_eq!(7, tx_a.get_int(&blk, 0).unwrap());
+
+        tx_b.rollback().unwrap();
+        assert_eq!(0, tx_a.get_int(&blk, 0).unwrap());
+        tx_a.commit().unwrap();
+
+        fs::remove_dir_all("readuncommittedtest").unwrap();
+    }
+
+    #[test]
+    fn readcommittedtest() {
+        let db = SimpleDB::with_params("readcommittedtest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.set_isolation_level(IsolationLevel::ReadCommitted);
+        tx_a.pin(&blk).unwrap();
+        assert_eq!(0, tx_a.get_int(&blk, 0).unwrap());
+
+        let mut tx_b = db.new_tx().unwrap();
+        tx_b.pin(&blk).unwrap();
+        tx_b.set_int(&blk, 0, 5, true).unwrap();
+
+        let blk_a = blk.clone();
+        let events_a = events.clone();
+        let handler_a = thread::spawn(move || {
+            let val = tx_a.get_int(&blk_a, 0).unwrap();
+            events_a.lock().unwrap().push("a read");
+            tx_a.commit().unwrap();
+            val
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        events.lock().unwrap().push("b committed");
+        tx_b.commit().unwrap();
+
+        assert_eq!(5, handler_a.join().unwrap());
+        assert_eq!(vec!["b committed", "a read"], *events.lock().unwrap());
+
+        fs::remove_dir_all("readcommittedtest").unwrap();
+    }
+
+    #[test]
+    fn repeatablereadtest() {
+        let db = SimpleDB::with_params("repeatablereadtest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 0);
+
+        let mut tx = db.new_tx().unwrap();
+        tx.append("testfile").unwrap();
+        tx.commit().unwrap();
+
+        let mut tx_a = db.new_tx().unwrap();
+        tx_a.set_isolation_level(IsolationLevel::RepeatableRead);
+        tx_a.pin(&blk).unwrap();
+        tx_a.get_int(&blk, 0).unwrap();
+        assert_eq!(1, tx_a.size("testfile").unwrap());
+
+        let mut tx_b = db.new_tx().unwrap();
+        tx_b.append("testfile").unwrap();
+        tx_b.commit().unwrap();
+
+        assert_eq!(2, tx_a.size("testfile").unwrap());
+        tx_a.commit().unwrap();
+
+        fs::remove_dir_all("repeatablereadtest").unwrap();
+    }
+
+    #[test]
+    fn serializabletest() {
+        let db = SimpleDB::with_params("serializabletest", 400, 8, Durability::NoSync).unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let mut tx_a = db.new_tx().unwrap();
+        assert_eq!(IsolationLevel::Serializable, tx_a.isolation_level());
+        assert_eq!(0, tx_a.size("testfile").unwrap());
+
+        let mut tx_b = db.new_tx().unwrap();
+        let events_b = events.clone();
+        let handler_b = thread::spawn(move || {
+            tx_b.append("testfile").unwrap();
+            events_b.lock().unwrap().push("b appended");
+            tx_b.commit().unwrap();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(0, tx_a.size("testfile").unwrap());
+        events.lock().unwrap().push("a committed");
+        tx_a.commit().unwrap();
+        handler_b.join().unwrap();
+
+        assert_eq!(vec!["a committed", "b appended"], *events.lock().unwrap());
+
+        fs::remove_dir_all("serializabletest").unwrap();
+    }
+
+    #[test]
+    fn setisolationleveltest() {
+        let db = SimpleDB::new("setisolationleveltest").unwrap();
+        let planner = db.planner().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let cmds = [
+            (
+                "set transaction isolation level read uncommitted",
+                IsolationLevel::ReadUncommitted,
+            ),
+            (
+                "set transaction isolation level read committed",
+                IsolationLevel::ReadCommitted,
+            ),
+            (
+                "set transaction isolation level repeatable read",
+                IsolationLevel::RepeatableRead,
+            ),
+            (
+                "set transaction isolation level serializable",
+                IsolationLevel::Serializable,
+            ),
+        ];
+        for (cmd, level) in cmds {
+            planner
+                .lock()
+                .unwrap()
+                .execute_update(cmd, tx.clone())
+                .unwrap();
+            assert_eq!(level, tx.lock().unwrap().isolation_level());
+        }
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("setisolationleveltest").unwrap();
+    }
+}
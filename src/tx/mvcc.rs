@@ -0,0 +1,3 @@
+pub mod mvcctest;
+pub mod snapshot;
+pub mod versionmgr;
@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        api::{
+            connection::ConnectionControl,
+            driver::{DriverControl, SQLError},
+            embedded::embeddeddriver::EmbeddedDriver,
+            resultset::ResultSetControl,
+            statement::StatementControl,
+        },
+        file::durability::Durability,
+        plan::plan::PlanError,
+        query::{scan::ScanControl, updatescan::UpdateScanControl},
+        record::{layout::Layout, schema::Schema, tablescan::TableScan},
+        server::{dbconfig::DbConfig, simpledb::SimpleDB},
+        tx::transaction::TransactionError,
+    };
+
+    #[test]
+    fn mvcctest() {
+        let db = SimpleDB::open("mvcctest", &mvcc_config()).unwrap();
+
+        let mut sch = Schema::new();
+        sch.add_int_field("A");
+        sch.add_text_field("B");
+        let layout = Layout::new(Arc::new(sch));
+        let long = "x".repeat(300);
+
+        let tx1 = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts = TableScan::new(tx1.clone(), "T", layout.clone()).unwrap();
+        ts.insert().unwrap();
+        ts.set_int("A", 1).unwrap();
+        ts.set_string("B", &long).unwrap();
+        ts.close().unwrap();
+        tx1.lock().unwrap().commit().unwrap();
+
+        let tx2 = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts2 = TableScan::new(tx2.clone(), "T", layout.clone()).unwrap();
+        assert!(ts2.next().unwrap());
+        let rid = ts2.get_rid();
+        ts2.set_int("A", 2).unwrap();
+        ts2.set_string("B", "two").unwrap();
+        assert!(rid == ts2.get_rid());
+        assert!(!ts2.next().unwrap());
+
+        let tx3 = Arc::new(Mutex::new(db.new_read_only_tx().unwrap()));
+        let mut ts3 = TableScan::new(tx3.clone(), "T", layout.clone()).unwrap();
+        assert!(ts3.next().unwrap());
+        assert_eq!(1, ts3.get_int("A").unwrap());
+        assert_eq!(long, ts3.get_string("B").unwrap());
+        assert!(!ts3.next().unwrap());
+
+        ts2.close().unwrap();
+        tx2.lock().unwrap().commit().unwrap();
+        ts3.before_first().unwrap();
+        assert!(ts3.next().unwrap());
+        assert_eq!(1, ts3.get_int("A").unwrap());
+        assert_eq!(long, ts3.get_string("B").unwrap());
+        assert!(!ts3.next().unwrap());
+
+        let tx4 = Arc::new(Mutex::new(db.new_read_only_tx().unwrap()));
+        let mut ts4 = TableScan::new(tx4.clone(), "T", layout.clone()).unwrap();
+        assert!(ts4.next().unwrap());
+        assert_eq!(2, ts4.get_int("A").unwrap());
+        assert_eq!("two", ts4.get_string("B").unwrap());
+        assert!(matches!(
+            ts4.set_int("A", 3),
+            Err(TransactionError::ReadOnly)
+        ));
+        assert!(matches!(ts4.insert(), Err(TransactionError::ReadOnly)));
+
+        ts3.close().unwrap();
+        tx3.lock().unwrap().commit().unwrap();
+        ts4.close().unwrap();
+        tx4.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("mvcctest").unwrap();
+    }
+
+    #[test]
+    fn mvccscantest() {
+        let db = SimpleDB::open("mvccscantest", &mvcc_config()).unwrap();
+
+        let mut sch = Schema::new();
+        sch.add_int_field("A");
+        sch.add_string_field("B", 9);
+        let layout = Layout::new(Arc::new(sch));
+
+        let reader = Arc::new(Mutex::new(db.new_read_only_tx().unwrap()));
+        let mut ts = TableScan::new(reader.clone(), "T", layout.clone()).unwrap();
+        ts.before_first().unwrap();
+        assert!(!ts.next().unwrap());
+        ts.close().unwrap();
+        reader.lock().unwrap().commit().unwrap();
+
+        let tx1 = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts = TableScan::new(tx1.clone(), "T", layout.clone()).unwrap();
+        for i in 0..20 {
+            ts.insert().unwrap();
+            ts.set_int("A", i).unwrap();
+            ts.set_string("B", &format!("rec{}", i)).unwrap();
+        }
+        ts.close().unwrap();
+        tx1.lock().unwrap().commit().unwrap();
+
+        let tx2 = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts2 = TableScan::new(tx2.clone(), "T", layout.clone()).unwrap();
+        while ts2.next().unwrap() {
+            let a = ts2.get_int("A").unwrap();
+            if a % 2 == 0 {
+                ts2.delete().unwrap();
+            } else {
+                ts2.set_int("A", a * 100).unwrap();
+            }
+        }
+        for i in 20..40 {
+            ts2.insert().unwrap();
+            ts2.set_int("A", i).unwrap();
+            ts2.set_string("B", &format!("rec{}", i)).unwrap();
+        }
+
+        let reader = Arc::new(Mutex::new(db.new_read_only_tx().unwrap()));
+        let mut ts = TableScan::new(reader.clone(), "T", layout.clone()).unwrap();
+        let mut expected = 0;
+        while ts.next().unwrap() {
+            assert_eq!(expected, ts.get_int("A").unwrap());
+            assert_eq!(format!("rec{}", expected), ts.get_string("B").unwrap());
+            expected += 1;
+        }
+        assert_eq!(20, expected);
+
+        ts2.close().unwrap();
+        tx2.lock().unwrap().rollback().unwrap();
+
+        ts.before_first().unwrap();
+        let mut count = 0;
+        while ts.next().unwrap() {
+            assert_eq!(count, ts.get_int("A").unwrap());
+            count += 1;
+        }
+        assert_eq!(20, count);
+        ts.close().unwrap();
+        reader.lock().unwrap().commit().unwrap();
+
+        let tx3 = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let size = tx3.lock().unwrap().size("T.tbl").unwrap();
+        let mut ts3 = TableScan::new(tx3.clone(), "T", layout.clone()).unwrap();
+        while ts3.next().unwrap() {
+            ts3.delete().unwrap();
+        }
+        ts3.close().unwrap();
+        tx3.lock().unwrap().commit().unwrap();
+
+        let tx4 = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let mut ts4 = TableScan::new(tx4.clone(), "T", layout.clone()).unwrap();
+        for i in 0..20 {
+            ts4.insert().unwrap();
+            ts4.set_int("A", i).unwrap();
+            ts4.set_string("B", &format!("rec{}", i)).unwrap();
+        }
+        ts4.close().unwrap();
+        assert_eq!(size, tx4.lock().unwrap().size("T.tbl").unwrap());
+        tx4.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("mvccscantest").unwrap();
+    }
+
+    #[test]
+    fn mvccconnectiontest() {
+        let d = EmbeddedDriver::with_config(mvcc_config());
+        let mut conn = d.connect("mvccconnectiontest").unwrap();
+        let mut stmt = conn.create_statement();
+        stmt.execute_update("create table T(A int)").unwrap();
+        stmt.execute_update("insert into T(A) values (1)").unwrap();
+
+        conn.set_read_only(true).unwrap();
+        let mut rs = stmt.execute_query("select A from T").unwrap();
+        assert!(rs.next().unwrap());
+        assert_eq!(1, rs.get_int("a").unwrap());
+        rs.close().unwrap();
+        let result = stmt.execute_update("insert into T(A) values (2)");
+        assert!(matches!(
+            result,
+            Err(SQLError::Plan(PlanError::Transaction(
+                TransactionError::ReadOnly
+            )))
+        ));
+        conn.commit().unwrap();
+
+        conn.set_read_only(false).unwrap();
+        stmt.execute_update("insert into T(A) values (2)").unwrap();
+        conn.close().unwrap();
+
+        fs::remove_dir_all("mvccconnectiontest").unwrap();
+    }
+
+    fn mvcc_config() -> DbConfig {
+        DbConfig::new()
+            .with_durability(Durability::NoSync)
+            .with_mvcc(true)
+    }
+}
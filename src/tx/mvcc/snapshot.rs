@@ -0,0 +1,17 @@
+use std::collections::HashSet;
+
+#[derive(Clone)]
+pub struct Snapshot {
+    xmax: usize,
+    active: HashSet<usize>,
+}
+
+impl Snapshot {
+    pub fn new(xmax: usize, active: HashSet<usize>) -> Snapshot {
+        Snapshot { xmax, active }
+    }
+
+    pub fn sees(&self, txnum: usize) -> bool {
+        txnum < self.xmax && !self.active.contains(&txnum)
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use super::snapshot::Snapshot;
+
+pub struct VersionMgr {
+    enabled: bool,
+    state: Mutex<VersionState>,
+}
+
+struct VersionState {
+    pending: HashSet<usize>,
+    active: HashSet<usize>,
+    start_lsns: HashMap<usize, usize>,
+    snapshots: HashMap<usize, Snapshot>,
+}
+
+impl VersionMgr {
+    pub fn new(enabled: bool) -> VersionMgr {
+        VersionMgr {
+            enabled,
+            state: Mutex::new(VersionState {
+                pending: HashSet::new(),
+                active: HashSet::new(),
+                start_lsns: HashMap::new(),
+                snapshots: HashMap::new(),
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn begin<F>(&self, next_tx_num: F) -> usize
+    where
+        F: FnOnce() -> usize,
+    {
+        let mut state = self.state.lock().unwrap();
+        let txnum = next_tx_num();
+        state.pending.insert(txnum);
+        txnum
+    }
+
+    pub fn start(&self, txnum: usize, start_lsn: usize, read_only: bool) -> Option<Snapshot> {
+        let mut state = self.state.lock().unwrap();
+        state.pending.remove(&txnum);
+        let mut snapshot = None;
+        if self.enabled && read_only {
+            let active = state.active.union(&state.pending).copied().collect();
+            let s = Snapshot::new(txnum, active);
+            state.snapshots.insert(txnum, s.clone());
+            snapshot = Some(s);
+        }
+        state.active.insert(txnum);
+        state.start_lsns.insert(txnum, start_lsn);
+        snapshot
+    }
+
+    pub fn is_active(&self, txnum: usize) -> bool {
+        let state = self.state.lock().unwrap();
+        state.pending.contains(&txnum) || state.active.contains(&txnum)
+    }
+
+    pub fn oldest_start_lsn(&self) -> Option<usize> {
+        let state = self.state.lock().unwrap();
+        state.start_lsns.values().min().copied()
+    }
+
+    pub fn with_active_txs<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce(&[usize]) -> T,
+    {
+        let state = self.state.lock().unwrap();
+        let mut txnums: Vec<usize> = state.active.iter().copied().collect();
+        txnums.sort_unstable();
+        f(&txnums)
+    }
+
+    pub fn is_obsolete(&self, txnum: usize) -> bool {
+        let state = self.state.lock().unwrap();
+        !state.pending.contains(&txnum)
+            && !state.active.contains(&txnum)
+            && state.snapshots.values().all(|s| s.sees(txnum))
+    }
+
+    pub fn end(&self, txnum: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.pending.remove(&txnum);
+        state.active.remove(&txnum);
+        state.start_lsns.remove(&txnum);
+        state.snapshots.remove(&txnum);
+    }
+}
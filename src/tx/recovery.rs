@@ -1,9 +1,18 @@
+pub mod begincheckpointrecord;
+pub mod checkpointer;
 pub mod checkpointrecord;
 pub mod commitrecord;
+pub mod endcheckpointrecord;
 pub mod logrecord;
+pub mod pageimagerecord;
 pub mod recoverymgr;
+pub mod recoverytarget;
 pub mod recoverytest;
 pub mod rollbackrecord;
+pub mod savepointrecord;
+pub mod setbytesrecord;
 pub mod setintrecord;
 pub mod setstringrecord;
+pub mod setvaluerecord;
 pub mod startrecord;
+pub mod value;
@@ -0,0 +1,66 @@
+use std::{
+    io::Error,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    file::page::Page,
+    log::logmgr::LogMgr,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+use super::logrecord::{LogRecord, Op};
+
+pub struct BeginCheckPointRecord {
+    txnums: Vec<usize>,
+}
+
+impl LogRecord for BeginCheckPointRecord {
+    fn op(&self) -> Op {
+        Op::BeginCheckPoint
+    }
+
+    fn tx_number(&self) -> Option<usize> {
+        None
+    }
+
+    fn undo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+
+    fn redo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+
+    fn active_txs(&self) -> Vec<usize> {
+        self.txnums.clone()
+    }
+}
+
+impl BeginCheckPointRecord {
+    pub fn new(p: Page) -> BeginCheckPointRecord {
+        let bytes = 4;
+        let npos = bytes;
+        let n = p.get_int(npos) as usize;
+        let txnums = (0..n)
+            .map(|i| p.get_int(npos + (i + 1) * bytes) as usize)
+            .collect();
+        BeginCheckPointRecord { txnums }
+    }
+
+    pub fn write_to_log(lm: &Arc<Mutex<LogMgr>>, txnums: &[usize]) -> Result<usize, Error> {
+        let bytes = 4;
+        let npos = bytes;
+        let mut rec = Vec::with_capacity(npos + (txnums.len() + 1) * bytes);
+        rec.resize(rec.capacity(), 0);
+        let mut p = Page::with_vec(rec);
+        p.set_int(0, Op::BeginCheckPoint as i32);
+        p.set_int(npos, txnums.len() as i32);
+        for (i, txnum) in txnums.iter().enumerate() {
+            p.set_int(npos + (i + 1) * bytes, *txnum as i32);
+        }
+        let mut lm = lm.lock().unwrap();
+        lm.clear_page_images();
+        lm.append(p.contents())
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{buffer::buffermgr::BufferMgr, log::logmgr::LogMgr, tx::mvcc::versionmgr::VersionMgr};
+
+use super::recoverymgr::RecoveryMgr;
+
+pub struct Checkpointer {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Checkpointer {
+    pub fn new(
+        lm: Arc<Mutex<LogMgr>>,
+        bm: Arc<Mutex<BufferMgr>>,
+        vm: Arc<VersionMgr>,
+        interval: Duration,
+    ) -> Checkpointer {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let stop_c = stop.clone();
+        let handle = thread::spawn(move || loop {
+            let (lock, cvar) = &*stop_c;
+            let stopped = lock.lock().unwrap();
+            let (stopped, _) = cvar
+                .wait_timeout_while(stopped, interval, |stopped| !*stopped)
+                .unwrap();
+            if *stopped {
+                break;
+            }
+            drop(stopped);
+            if RecoveryMgr::checkpoint(&lm, &bm, &vm).is_err() {
+                break;
+            }
+        });
+        Checkpointer {
+            stop,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl Drop for Checkpointer {
+    fn drop(&mut self) {
+        let (lock, cvar) = &*self.stop;
+        *lock.lock().unwrap() = true;
+        cvar.notify_all();
+        if let Some(handle) = self.handle.take() {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -25,6 +25,10 @@ impl LogRecord for CheckPointRecord {
     fn undo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
         Ok(())
     }
+
+    fn redo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
 }
 
 impl CheckPointRecord {
@@ -38,6 +42,8 @@ impl CheckPointRecord {
         rec.resize(rec.capacity(), 0);
         let mut p = Page::with_vec(rec);
         p.set_int(0, Op::CheckPoint as i32);
-        lm.lock().unwrap().append(p.contents())
+        let mut lm = lm.lock().unwrap();
+        lm.clear_page_images();
+        lm.append(p.contents())
     }
 }
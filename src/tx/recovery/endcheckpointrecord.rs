@@ -0,0 +1,47 @@
+use std::{
+    io::Error,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    file::page::Page,
+    log::logmgr::LogMgr,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+use super::logrecord::{LogRecord, Op};
+
+pub struct EndCheckPointRecord {}
+
+impl LogRecord for EndCheckPointRecord {
+    fn op(&self) -> Op {
+        Op::EndCheckPoint
+    }
+
+    fn tx_number(&self) -> Option<usize> {
+        None
+    }
+
+    fn undo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+
+    fn redo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+}
+
+impl EndCheckPointRecord {
+    pub fn new() -> EndCheckPointRecord {
+        EndCheckPointRecord {}
+    }
+
+    pub fn write_to_log(lm: &Arc<Mutex<LogMgr>>) -> Result<usize, Error> {
+        let bytes = 4;
+        let mut rec = Vec::with_capacity(bytes);
+        rec.resize(rec.capacity(), 0);
+        let mut p = Page::with_vec(rec);
+        p.set_int(0, Op::EndCheckPoint as i32);
+        lm.lock().unwrap().append(p.contents())
+    }
+}
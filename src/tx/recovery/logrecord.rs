@@ -1,11 +1,14 @@
 use crate::{
-    file::page::Page,
+    file::{blockid::BlockId, page::Page},
     tx::transaction::{Transaction, TransactionError},
 };
 
 use super::{
-    checkpointrecord::CheckPointRecord, commitrecord::CommitRecord, rollbackrecord::RollbackRecord,
-    setintrecord::SetIntRecord, setstringrecord::SetStringRecord, startrecord::StartRecord,
+    begincheckpointrecord::BeginCheckPointRecord, checkpointrecord::CheckPointRecord,
+    commitrecord::CommitRecord, endcheckpointrecord::EndCheckPointRecord,
+    pageimagerecord::PageImageRecord, rollbackrecord::RollbackRecord,
+    savepointrecord::SavepointRecord, setbytesrecord::SetBytesRecord, setintrecord::SetIntRecord,
+    setstringrecord::SetStringRecord, setvaluerecord::SetValueRecord, startrecord::StartRecord,
 };
 
 #[derive(Eq, PartialEq)]
@@ -16,12 +19,31 @@ pub enum Op {
     Rollback = 3,
     SetInt = 4,
     SetString = 5,
+    BeginCheckPoint = 6,
+    EndCheckPoint = 7,
+    Savepoint = 8,
+    PageImage = 9,
+    SetValue = 10,
+    SetBytes = 11,
 }
 
 pub trait LogRecord {
     fn op(&self) -> Op;
     fn tx_number(&self) -> Option<usize>;
     fn undo(&self, tx: &mut Transaction) -> Result<(), TransactionError>;
+    fn redo(&self, tx: &mut Transaction) -> Result<(), TransactionError>;
+
+    fn active_txs(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn savepoint_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn imaged_block(&self) -> Option<&BlockId> {
+        None
+    }
 }
 
 pub fn create_log_record(bytes: Vec<u8>) -> Result<Box<dyn LogRecord>, TransactionError> {
@@ -33,6 +55,15 @@ pub fn create_log_record(bytes: Vec<u8>) -> Result<Box<dyn LogRecord>, Transacti
         x if x == Op::Rollback as i32 => Ok(Box::new(RollbackRecord::new(p))),
         x if x == Op::SetInt as i32 => Ok(Box::new(SetIntRecord::new(p)?)),
         x if x == Op::SetString as i32 => Ok(Box::new(SetStringRecord::new(p)?)),
+        x if x == Op::BeginCheckPoint as i32 => Ok(Box::new(BeginCheckPointRecord::new(p))),
+        x if x == Op::EndCheckPoint as i32 => Ok(Box::new(EndCheckPointRecord::new())),
+        x if x == Op::Savepoint as i32 => Ok(Box::new(SavepointRecord::new(p)?)),
+        x if x == Op::PageImage as i32 => Ok(Box::new(PageImageRecord::new(p)?)),
+        x if x == Op::SetValue as i32 => match SetValueRecord::new(p)? {
+            Some(rec) => Ok(Box::new(rec)),
+            None => Err(TransactionError::General),
+        },
+        x if x == Op::SetBytes as i32 => Ok(Box::new(SetBytesRecord::new(p)?)),
         _ => Err(TransactionError::General),
     }
 }
@@ -0,0 +1,102 @@
+use std::{
+    io::Error,
+    string::FromUtf8Error,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    file::{blockid::BlockId, page::Page},
+    log::logmgr::LogMgr,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+use super::logrecord::{LogRecord, Op};
+
+pub struct PageImageRecord {
+    txnum: usize,
+    blk: BlockId,
+    offset: usize,
+    image: Vec<u8>,
+}
+
+impl LogRecord for PageImageRecord {
+    fn op(&self) -> Op {
+        Op::PageImage
+    }
+
+    fn tx_number(&self) -> Option<usize> {
+        Some(self.txnum)
+    }
+
+    fn undo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+
+    fn redo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_page(&self.blk, self.offset, &self.image)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+
+    fn imaged_block(&self) -> Option<&BlockId> {
+        Some(&self.blk)
+    }
+}
+
+impl PageImageRecord {
+    pub fn new(p: Page) -> Result<PageImageRecord, FromUtf8Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let txnum = p.get_int(tpos) as usize;
+        let fpos = tpos + bytes;
+        let filename = p.get_string(fpos)?;
+        let bpos = fpos + Page::bytes_length(filename.len());
+        let blknum = p.get_int(bpos);
+        let blk = BlockId::new(&filename, blknum);
+        let opos = bpos + bytes;
+        let offset = p.get_int(opos) as usize;
+        let ipos = opos + bytes;
+        let image = p.get_bytes(ipos).to_vec();
+        Ok(PageImageRecord {
+            txnum,
+            blk,
+            offset,
+            image,
+        })
+    }
+
+    pub fn write_to_log(
+        lm: &Arc<Mutex<LogMgr>>,
+        txnum: usize,
+        blk: BlockId,
+        image: &[u8],
+    ) -> Result<Option<usize>, Error> {
+        let mut lm = lm.lock().unwrap();
+        if !lm.needs_page_image(&blk) {
+            return Ok(None);
+        }
+        let bytes = 4;
+        let tpos = bytes;
+        let fpos = tpos + bytes;
+        let bpos = fpos + Page::bytes_length(blk.file_name().len());
+        let opos = bpos + bytes;
+        let ipos = opos + bytes;
+        let fragment_size = image.len() - 2 * bytes - (ipos + bytes);
+        let mut lsn = 0;
+        for (i, fragment) in image.chunks(fragment_size).enumerate() {
+            let reclen = ipos + bytes + fragment.len();
+            let mut rec = Vec::with_capacity(reclen);
+            rec.resize(rec.capacity(), 0);
+            let mut p = Page::with_vec(rec);
+            p.set_int(0, Op::PageImage as i32);
+            p.set_int(tpos, txnum as i32);
+            p.set_string(fpos, blk.file_name());
+            p.set_int(bpos, blk.number());
+            p.set_int(opos, (i * fragment_size) as i32);
+            p.set_bytes(ipos, fragment);
+            lsn = lm.append(p.contents())?;
+        }
+        Ok(Some(lsn))
+    }
+}
@@ -7,11 +7,15 @@ use std::{
 use crate::{
     buffer::{buffer::Buffer, buffermgr::BufferMgr},
     log::logmgr::LogMgr,
+    tx::mvcc::versionmgr::VersionMgr,
 };
 
 use super::{
-    commitrecord::CommitRecord, setintrecord::SetIntRecord, setstringrecord::SetStringRecord,
-    startrecord::StartRecord,
+    begincheckpointrecord::BeginCheckPointRecord, commitrecord::CommitRecord,
+    endcheckpointrecord::EndCheckPointRecord, pageimagerecord::PageImageRecord,
+    savepointrecord::SavepointRecord, setbytesrecord::SetBytesRecord, setintrecord::SetIntRecord,
+    setstringrecord::SetStringRecord, setvaluerecord::SetValueRecord, startrecord::StartRecord,
+    value::Value,
 };
 
 #[derive(Debug)]
@@ -35,38 +39,120 @@ impl From<FromUtf8Error> for RecoveryError {
 
 pub struct RecoveryMgr {
     lm: Arc<Mutex<LogMgr>>,
-    bm: Arc<Mutex<BufferMgr>>,
     txnum: usize,
+    start_lsn: usize,
 }
 
 impl RecoveryMgr {
-    pub fn new(
-        txnum: usize,
-        lm: Arc<Mutex<LogMgr>>,
-        bm: Arc<Mutex<BufferMgr>>,
-    ) -> Result<RecoveryMgr, Error> {
-        StartRecord::write_to_log(&lm, txnum)?;
-        Ok(RecoveryMgr { lm, bm, txnum })
+    pub fn new(txnum: usize, lm: Arc<Mutex<LogMgr>>) -> Result<RecoveryMgr, Error> {
+        let start_lsn = StartRecord::write_to_log(&lm, txnum)?;
+        Ok(RecoveryMgr {
+            lm,
+            txnum,
+            start_lsn,
+        })
     }
 
     pub fn commit(&self) -> Result<(), Error> {
-        self.bm.lock().unwrap().flush_all(self.txnum)?;
         let lsn = CommitRecord::write_to_log(&self.lm, self.txnum)?;
-        self.lm.lock().unwrap().flush(lsn)?;
+        LogMgr::group_flush(&self.lm, lsn)?;
         Ok(())
     }
 
+    pub fn savepoint(&self, name: &str) -> Result<(), Error> {
+        SavepointRecord::write_to_log(&self.lm, self.txnum, name)?;
+        Ok(())
+    }
+
+    pub fn checkpoint(
+        lm: &Arc<Mutex<LogMgr>>,
+        bm: &Arc<Mutex<BufferMgr>>,
+        vm: &VersionMgr,
+    ) -> Result<(), Error> {
+        let mut bm = bm.lock().unwrap();
+        let begin_lsn =
+            vm.with_active_txs(|txnums| BeginCheckPointRecord::write_to_log(lm, txnums))?;
+        bm.flush_modified()?;
+        bm.sync()?;
+        let lsn = EndCheckPointRecord::write_to_log(lm)?;
+        lm.lock().unwrap().flush(lsn)?;
+        RecoveryMgr::truncate_log(lm, vm, begin_lsn)
+    }
+
+    pub fn truncate_log(
+        lm: &Arc<Mutex<LogMgr>>,
+        vm: &VersionMgr,
+        checkpoint_lsn: usize,
+    ) -> Result<(), Error> {
+        let mut lm = lm.lock().unwrap();
+        lm.set_checkpoint_lsn(checkpoint_lsn);
+        let oldest = RecoveryMgr::oldest_needed_lsn(&lm, vm);
+        lm.truncate(oldest)
+    }
+
+    pub fn oldest_needed_lsn(lm: &LogMgr, vm: &VersionMgr) -> usize {
+        let lsn = lm.checkpoint_lsn().unwrap_or_else(|| lm.first_lsn());
+        match vm.oldest_start_lsn() {
+            Some(start_lsn) => lsn.min(start_lsn),
+            None => lsn,
+        }
+    }
+
+    pub fn start_lsn(&self) -> usize {
+        self.start_lsn
+    }
+
+    pub fn log_page_image(&mut self, buff: &mut Buffer) -> Result<Option<usize>, Error> {
+        if let Some(blk) = buff.block().clone() {
+            return PageImageRecord::write_to_log(
+                &self.lm,
+                self.txnum,
+                blk,
+                buff.contents().contents(),
+            );
+        }
+        Ok(None)
+    }
+
     pub fn set_int(
         &mut self,
         buff: &mut Buffer,
         offset: usize,
-        _: i32,
+        newval: i32,
     ) -> Result<usize, RecoveryError> {
         let oldval = buff.contents().get_int(offset);
         let blk = buff.block();
         if let Some(blk) = blk {
-            let lsn =
-                SetIntRecord::write_to_log(&self.lm, self.txnum, blk.clone(), offset, oldval)?;
+            let lsn = SetIntRecord::write_to_log(
+                &self.lm,
+                self.txnum,
+                blk.clone(),
+                offset,
+                oldval,
+                newval,
+            )?;
+            return Ok(lsn);
+        }
+        Err(RecoveryError::General)
+    }
+
+    pub fn set_value(
+        &mut self,
+        buff: &mut Buffer,
+        offset: usize,
+        newval: Value,
+    ) -> Result<usize, RecoveryError> {
+        let oldval = newval.read(buff.contents(), offset);
+        let blk = buff.block();
+        if let Some(blk) = blk {
+            let lsn = SetValueRecord::write_to_log(
+                &self.lm,
+                self.txnum,
+                blk.clone(),
+                offset,
+                oldval,
+                newval,
+            )?;
             return Ok(lsn);
         }
         Err(RecoveryError::General)
@@ -76,13 +162,43 @@ impl RecoveryMgr {
         &mut self,
         buff: &mut Buffer,
         offset: usize,
-        _: &str,
+        newval: &str,
     ) -> Result<usize, RecoveryError> {
         let oldval = buff.contents().get_string(offset)?;
         let blk = buff.block();
         if let Some(blk) = blk {
-            let lsn =
-                SetStringRecord::write_to_log(&self.lm, self.txnum, blk.clone(), offset, &oldval)?;
+            let lsn = SetStringRecord::write_to_log(
+                &self.lm,
+                self.txnum,
+                blk.clone(),
+                offset,
+                &oldval,
+                newval,
+            )?;
+            return Ok(lsn);
+        }
+        Err(RecoveryError::General)
+    }
+
+    pub fn set_bytes(
+        &mut self,
+        buff: &mut Buffer,
+        offset: usize,
+        newval: &[u8],
+    ) -> Result<usize, RecoveryError> {
+        let oldval = buff.contents().get_raw(offset, newval.len()).to_vec();
+        let blocksize = buff.contents().contents().len();
+        let blk = buff.block();
+        if let Some(blk) = blk {
+            let lsn = SetBytesRecord::write_to_log(
+                &self.lm,
+                self.txnum,
+                blk.clone(),
+                offset,
+                &oldval,
+                newval,
+                blocksize,
+            )?;
             return Ok(lsn);
         }
         Err(RecoveryError::General)
@@ -1,19 +1,30 @@
 #[cfg(test)]
 mod tests {
     use std::{
-        fs,
+        fs::{self, OpenOptions},
+        io::{Seek, SeekFrom, Write},
+        path::Path,
         sync::{Arc, Mutex},
+        thread,
+        time::Duration,
     };
 
     use crate::{
         buffer::buffermgr::BufferMgr,
-        file::{blockid::BlockId, filemgr::FileMgr, page::Page},
-        server::simpledb::SimpleDB,
+        file::{blockid::BlockId, durability::Durability, filemgr::FileMgr, page::Page},
+        plan::{plan::PlanControl, planner::Planner},
+        query::scan::ScanControl,
+        server::{dbconfig::DbConfig, simpledb::SimpleDB},
+        tx::recovery::{
+            logrecord::{create_log_record, Op},
+            recoverytarget::RecoveryTarget,
+        },
+        tx::transaction::Transaction,
     };
 
     #[test]
     fn recoverytest() {
-        let mut db = SimpleDB::with_params("recoverytest", 400, 8).unwrap();
+        let mut db = SimpleDB::with_params("recoverytest", 400, 8, Durability::NoSync).unwrap();
         let fm = db.file_mgr();
         let bm = db.buffer_mgr();
         let blk0 = BlockId::new("testfile", 0);
@@ -21,7 +32,7 @@ mod tests {
 
         for _ in 0..2 {
             if fm.length("testfile").unwrap() == 0 {
-                initialize(&db, fm.clone(), &blk0, &blk1);
+                initialize(&db, fm.clone(), bm.clone(), &blk0, &blk1);
                 modify(&db, bm.clone(), &blk0, &blk1);
             } else {
                 recover(&db, fm.clone(), &blk0, &blk1);
@@ -30,9 +41,17 @@ mod tests {
         fs::remove_dir_all("recoverytest").unwrap();
     }
 
-    fn initialize(db: &SimpleDB, fm: Arc<FileMgr>, blk0: &BlockId, blk1: &BlockId) {
+    fn initialize(
+        db: &SimpleDB,
+        fm: Arc<FileMgr>,
+        bm: Arc<Mutex<BufferMgr>>,
+        blk0: &BlockId,
+        blk1: &BlockId,
+    ) {
         let mut tx1 = db.new_tx().unwrap();
         let mut tx2 = db.new_tx().unwrap();
+        let txnum1 = tx1.tx_number();
+        let txnum2 = tx2.tx_number();
         tx1.pin(blk0).unwrap();
         tx2.pin(blk1).unwrap();
         let bytes = 4;
@@ -46,6 +65,8 @@ mod tests {
         tx2.set_string(blk1, 30, "def", false).unwrap();
         tx1.commit().unwrap();
         tx2.commit().unwrap();
+        bm.lock().unwrap().flush_all(txnum1).unwrap();
+        bm.lock().unwrap().flush_all(txnum2).unwrap();
         assert_values(
             fm,
             blk0,
@@ -58,6 +79,8 @@ mod tests {
     fn modify(db: &SimpleDB, bm: Arc<Mutex<BufferMgr>>, blk0: &BlockId, blk1: &BlockId) {
         let mut tx3 = db.new_tx().unwrap();
         let mut tx4 = db.new_tx().unwrap();
+        let txnum3 = tx3.tx_number();
+        let txnum4 = tx4.tx_number();
         tx3.pin(blk0).unwrap();
         tx4.pin(blk1).unwrap();
         let mut pos = 0;
@@ -69,8 +92,8 @@ mod tests {
         }
         tx3.set_string(blk0, 30, "uvw", true).unwrap();
         tx4.set_string(blk1, 30, "xyz", true).unwrap();
-        bm.lock().unwrap().flush_all(3).unwrap();
-        bm.lock().unwrap().flush_all(4).unwrap();
+        bm.lock().unwrap().flush_all(txnum3).unwrap();
+        bm.lock().unwrap().flush_all(txnum4).unwrap();
 
         tx3.rollback().unwrap();
     }
@@ -87,6 +110,391 @@ mod tests {
         );
     }
 
+    #[test]
+    fn redotest() {
+        let blk0 = BlockId::new("testfile", 0);
+        let blk1 = BlockId::new("testfile", 1);
+        {
+            let db = SimpleDB::with_params("redotest", 400, 8, Durability::NoSync).unwrap();
+            let mut tx1 = db.new_tx().unwrap();
+            tx1.append("testfile").unwrap();
+            tx1.append("testfile").unwrap();
+            tx1.commit().unwrap();
+
+            let mut tx2 = db.new_tx().unwrap();
+            let mut tx3 = db.new_tx().unwrap();
+            tx2.pin(&blk0).unwrap();
+            tx3.pin(&blk1).unwrap();
+            let mut pos = 0;
+            let bytes = 4;
+            for _ in 0..6 {
+                tx2.set_int(&blk0, pos, pos as i32, true).unwrap();
+                tx3.set_int(&blk1, pos, pos as i32 + 100, true).unwrap();
+                pos += bytes;
+            }
+            tx2.set_string(&blk0, 30, "abc", true).unwrap();
+            tx3.set_string(&blk1, 30, "xyz", true).unwrap();
+            tx2.commit().unwrap();
+
+            let mut tx4 = db.new_tx().unwrap();
+            tx4.pin(&blk1).unwrap();
+            tx3.rollback().unwrap();
+            tx4.set_string(&blk1, 30, "def", true).unwrap();
+            tx4.commit().unwrap();
+        }
+
+        let db = SimpleDB::with_params("redotest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+        let mut tx = db.new_tx().unwrap();
+        tx.recover().unwrap();
+        assert_values(
+            fm,
+            &blk0,
+            &blk1,
+            [[0, 0], [4, 0], [8, 0], [12, 0], [16, 0], [20, 0]],
+            ["abc", "def"],
+        );
+        tx.commit().unwrap();
+
+        fs::remove_dir_all("redotest").unwrap();
+    }
+
+    #[test]
+    fn tornpagetest() {
+        let blk = BlockId::new("testfile", 0);
+        {
+            let mut db = SimpleDB::with_params("tornpagetest", 400, 8, Durability::NoSync).unwrap();
+            let mut tx1 = db.new_tx().unwrap();
+            tx1.append("testfile").unwrap();
+            tx1.pin(&blk).unwrap();
+            tx1.set_int(&blk, 80, 1, true).unwrap();
+            tx1.set_string(&blk, 40, "one", true).unwrap();
+            tx1.commit().unwrap();
+            db.checkpoint().unwrap();
+
+            let mut tx2 = db.new_tx().unwrap();
+            tx2.pin(&blk).unwrap();
+            tx2.set_int(&blk, 80, 2, true).unwrap();
+            tx2.set_string(&blk, 40, "two", true).unwrap();
+            tx2.commit().unwrap();
+            db.buffer_mgr()
+                .lock()
+                .unwrap()
+                .flush_all(tx2.tx_number())
+                .unwrap();
+        }
+
+        let mut f = OpenOptions::new()
+            .write(true)
+            .open("tornpagetest/testfile")
+            .unwrap();
+        f.seek(SeekFrom::Start(200)).unwrap();
+        f.write_all(&[0xff; 204]).unwrap();
+        drop(f);
+
+        let db = SimpleDB::with_params("tornpagetest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+        let mut p = Page::new(fm.page_size());
+        assert!(fm.read(&blk, &mut p).is_err());
+        let mut tx = db.new_tx().unwrap();
+        tx.recover().unwrap();
+        fm.read(&blk, &mut p).unwrap();
+        assert_eq!(2, p.get_int(80));
+        assert_eq!("two", p.get_string(40).unwrap());
+        tx.commit().unwrap();
+
+        fs::remove_dir_all("tornpagetest").unwrap();
+    }
+
+    #[test]
+    fn checkpointtest() {
+        let blk0 = BlockId::new("testfile", 0);
+        let blk1 = BlockId::new("testfile", 1);
+        let blk2 = BlockId::new("testfile", 2);
+        {
+            let db = SimpleDB::with_params("checkpointtest", 400, 8, Durability::NoSync).unwrap();
+            let fm = db.file_mgr();
+            let mut tx0 = db.new_tx().unwrap();
+            for _ in 0..3 {
+                tx0.append("testfile").unwrap();
+            }
+            tx0.commit().unwrap();
+
+            let mut tx1 = db.new_tx().unwrap();
+            tx1.pin(&blk0).unwrap();
+            tx1.set_int(&blk0, 0, 1, true).unwrap();
+            tx1.set_string(&blk0, 30, "one", true).unwrap();
+            tx1.commit().unwrap();
+
+            let mut tx2 = db.new_tx().unwrap();
+            tx2.pin(&blk1).unwrap();
+            tx2.set_int(&blk1, 0, 2, true).unwrap();
+
+            db.checkpoint().unwrap();
+
+            tx2.set_string(&blk1, 30, "two", true).unwrap();
+            let mut tx3 = db.new_tx().unwrap();
+            tx3.pin(&blk2).unwrap();
+            tx3.set_int(&blk2, 0, 3, true).unwrap();
+            tx3.commit().unwrap();
+
+            let mut p = Page::new(fm.page_size());
+            fm.read(&blk0, &mut p).unwrap();
+            assert_eq!(1, p.get_int(0));
+            p.set_int(0, 99);
+            fm.write(&blk0, &mut p).unwrap();
+        }
+
+        let db = SimpleDB::with_params("checkpointtest", 400, 8, Durability::NoSync).unwrap();
+        let fm = db.file_mgr();
+        let mut tx = db.new_tx().unwrap();
+        tx.recover().unwrap();
+        let mut p0 = Page::new(fm.page_size());
+        let mut p1 = Page::new(fm.page_size());
+        let mut p2 = Page::new(fm.page_size());
+        fm.read(&blk0, &mut p0).unwrap();
+        fm.read(&blk1, &mut p1).unwrap();
+        fm.read(&blk2, &mut p2).unwrap();
+        assert_eq!(99, p0.get_int(0));
+        assert_eq!("one", p0.get_string(30).unwrap());
+        assert_eq!(0, p1.get_int(0));
+        assert_eq!("", p1.get_string(30).unwrap());
+        assert_eq!(3, p2.get_int(0));
+        tx.commit().unwrap();
+
+        fs::remove_dir_all("checkpointtest").unwrap();
+    }
+
+    #[test]
+    fn periodiccheckpointtest() {
+        let mut db = SimpleDB::with_params("periodiccheckpointtest", 400, 8, Durability::NoSync)
+            .unwrap()
+            .with_checkpoint_interval(Duration::from_millis(10));
+        let fm = db.file_mgr();
+        let lm = db.log_mgr();
+        let blk = BlockId::new("testfile", 0);
+
+        let mut tx = db.new_tx().unwrap();
+        tx.append("testfile").unwrap();
+        tx.pin(&blk).unwrap();
+        tx.set_int(&blk, 0, 7, true).unwrap();
+        tx.commit().unwrap();
+
+        thread::sleep(Duration::from_millis(100));
+        let mut p = Page::new(fm.page_size());
+        fm.read(&blk, &mut p).unwrap();
+        assert_eq!(7, p.get_int(0));
+        let iter = lm.lock().unwrap().iterator().unwrap();
+        assert!(iter
+            .map(|bytes| create_log_record(bytes).unwrap())
+            .any(|rec| rec.op() == Op::EndCheckPoint));
+
+        drop(db);
+        fs::remove_dir_all("periodiccheckpointtest").unwrap();
+    }
+
+    #[test]
+    fn pointintimerecoverytest() {
+        let mut db = SimpleDB::new("pitrtest").unwrap();
+        let lm = db.log_mgr();
+        lm.lock().unwrap().set_segment_size(2);
+        lm.lock().unwrap().set_archive_dir("pitrtest_archive");
+        let planner = db.planner().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let cmd = "create table T(A int, B varchar(9))";
+        planner
+            .lock()
+            .unwrap()
+            .execute_update(cmd, tx.clone())
+            .unwrap();
+        insert_records(&planner, tx.clone(), 0, 10);
+        tx.lock().unwrap().commit().unwrap();
+
+        db.checkpoint().unwrap();
+        copy_dir("pitrtest", "pitrtest_backup");
+        let first_lsn = lm.lock().unwrap().last_lsn();
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        insert_records(&planner, tx.clone(), 10, 20);
+        let good_txnum = tx.lock().unwrap().tx_number();
+        tx.lock().unwrap().commit().unwrap();
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        planner
+            .lock()
+            .unwrap()
+            .execute_update("delete from T", tx.clone())
+            .unwrap();
+        tx.lock().unwrap().commit().unwrap();
+        db.checkpoint().unwrap();
+        assert!(fs::read_dir("pitrtest_archive").unwrap().count() > 0);
+        drop(planner);
+        drop(db);
+
+        let log_dirs = ["pitrtest_archive", "pitrtest"];
+        SimpleDB::restore(
+            "pitrtest_backup",
+            &log_dirs,
+            "pitrtest_restored1",
+            RecoveryTarget::TxNum(good_txnum),
+        )
+        .unwrap();
+        assert_eq!(20, count_records("pitrtest_restored1"));
+
+        SimpleDB::restore(
+            "pitrtest_backup",
+            &log_dirs,
+            "pitrtest_restored2",
+            RecoveryTarget::Lsn(first_lsn),
+        )
+        .unwrap();
+        assert_eq!(10, count_records("pitrtest_restored2"));
+        assert_eq!(0, count_records("pitrtest"));
+
+        for dir in [
+            "pitrtest",
+            "pitrtest_archive",
+            "pitrtest_backup",
+            "pitrtest_restored1",
+            "pitrtest_restored2",
+        ] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn midblockrestoretest() {
+        let config = DbConfig::new().with_block_size(4096);
+        let db = SimpleDB::with_config("midblocktest", &config).unwrap();
+        fs::write(
+            Path::new("midblocktest").join(DbConfig::CONFIG_FILE),
+            "block_size = 4096\n",
+        )
+        .unwrap();
+        let planner = db.planner().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let cmd = "create table T(A int, B varchar(9))";
+        planner
+            .lock()
+            .unwrap()
+            .execute_update(cmd, tx.clone())
+            .unwrap();
+        insert_records(&planner, tx.clone(), 0, 3);
+        tx.lock().unwrap().commit().unwrap();
+        db.checkpoint().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        insert_records(&planner, tx.clone(), 3, 4);
+        tx.lock().unwrap().commit().unwrap();
+        copy_dir("midblocktest", "midblocktest_backup");
+
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        insert_records(&planner, tx.clone(), 4, 5);
+        let good_txnum = tx.lock().unwrap().tx_number();
+        tx.lock().unwrap().commit().unwrap();
+        drop(planner);
+        drop(db);
+
+        let prefix = format!("{}.", DbConfig::new().log_file());
+        for entry in fs::read_dir("midblocktest_backup").unwrap() {
+            let entry = entry.unwrap();
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                let live = Path::new("midblocktest").join(entry.file_name());
+                let live_len = fs::metadata(live).unwrap().len();
+                assert_eq!(entry.metadata().unwrap().len(), live_len);
+            }
+        }
+
+        SimpleDB::restore(
+            "midblocktest_backup",
+            &["midblocktest"],
+            "midblocktest_restored",
+            RecoveryTarget::TxNum(good_txnum),
+        )
+        .unwrap();
+        assert_eq!(5, count_records("midblocktest_restored"));
+
+        for dir in [
+            "midblocktest",
+            "midblocktest_backup",
+            "midblocktest_restored",
+        ] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn txnumtest() {
+        let db = SimpleDB::with_params("txnumtest", 400, 8, Durability::NoSync).unwrap();
+        let mut tx = db.new_tx().unwrap();
+        let txnum = tx.tx_number();
+        assert!(db.file_mgr().reserved_tx_num() > txnum);
+        tx.commit().unwrap();
+        drop(db);
+
+        let fm = FileMgr::new("txnumtest", 400, Durability::NoSync).unwrap();
+        assert!(fm.reserved_tx_num() > txnum);
+        fm.reserve_tx_num(fm.reserved_tx_num() + 5).unwrap();
+        let reserved = fm.reserved_tx_num();
+        drop(fm);
+
+        let db = SimpleDB::with_params("txnumtest", 400, 8, Durability::NoSync).unwrap();
+        assert_eq!(reserved, db.file_mgr().reserved_tx_num());
+        let tx = db.new_tx().unwrap();
+        assert!(tx.tx_number() > reserved);
+        db.checkpoint().unwrap();
+        let pinned = db.oldest_needed_lsn();
+        drop(tx);
+        assert!(db.oldest_needed_lsn() > pinned);
+        drop(db);
+
+        fs::remove_dir_all("txnumtest").unwrap();
+    }
+
+    fn copy_dir(src: &str, dst: &str) {
+        fs::create_dir_all(dst).unwrap();
+        for entry in fs::read_dir(src).unwrap() {
+            let entry = entry.unwrap();
+            fs::copy(entry.path(), Path::new(dst).join(entry.file_name())).unwrap();
+        }
+    }
+
+    fn insert_records(
+        planner: &Arc<Mutex<Planner>>,
+        tx: Arc<Mutex<Transaction>>,
+        start: i32,
+        end: i32,
+    ) {
+        for i in start..end {
+            let cmd = format!("insert into T(A, B) values({0}, 'rec{0}')", i);
+            planner
+                .lock()
+                .unwrap()
+                .execute_update(&cmd, tx.clone())
+                .unwrap();
+        }
+    }
+
+    fn count_records(dirname: &str) -> usize {
+        let db = SimpleDB::new(dirname).unwrap();
+        let planner = db.planner().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let p = planner
+            .lock()
+            .unwrap()
+            .create_query_plan("select A, B from T", tx.clone())
+            .unwrap();
+        let mut s = p.open().unwrap();
+        let mut count = 0;
+        while s.next().unwrap() {
+            let a = s.get_int("a").unwrap();
+            assert_eq!(format!("rec{}", a), s.get_string("b").unwrap());
+            count += 1;
+        }
+        s.close().unwrap();
+        tx.lock().unwrap().commit().unwrap();
+        count
+    }
+
     fn assert_values(
         fm: Arc<FileMgr>,
         blk0: &BlockId,
@@ -94,8 +502,8 @@ mod tests {
         e1: [[i32; 2]; 6],
         e2: [&str; 2],
     ) {
-        let mut p0 = Page::new(fm.block_size());
-        let mut p1 = Page::new(fm.block_size());
+        let mut p0 = Page::new(fm.page_size());
+        let mut p1 = Page::new(fm.page_size());
         fm.read(blk0, &mut p0).unwrap();
         fm.read(blk1, &mut p1).unwrap();
         let mut pos = 0;
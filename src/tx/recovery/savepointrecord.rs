@@ -0,0 +1,65 @@
+use std::{
+    io::Error,
+    string::FromUtf8Error,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    file::page::Page,
+    log::logmgr::LogMgr,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+use super::logrecord::{LogRecord, Op};
+
+pub struct SavepointRecord {
+    txnum: usize,
+    name: String,
+}
+
+impl LogRecord for SavepointRecord {
+    fn op(&self) -> Op {
+        Op::Savepoint
+    }
+
+    fn tx_number(&self) -> Option<usize> {
+        Some(self.txnum)
+    }
+
+    fn undo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+
+    fn redo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
+
+    fn savepoint_name(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+}
+
+impl SavepointRecord {
+    pub fn new(p: Page) -> Result<SavepointRecord, FromUtf8Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let txnum = p.get_int(tpos) as usize;
+        let npos = tpos + bytes;
+        let name = p.get_string(npos)?;
+        Ok(SavepointRecord { txnum, name })
+    }
+
+    pub fn write_to_log(lm: &Arc<Mutex<LogMgr>>, txnum: usize, name: &str) -> Result<usize, Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let npos = tpos + bytes;
+        let reclen = npos + Page::bytes_length(name.len());
+        let mut rec = Vec::with_capacity(reclen);
+        rec.resize(rec.capacity(), 0);
+        let mut p = Page::with_vec(rec);
+        p.set_int(0, Op::Savepoint as i32);
+        p.set_int(tpos, txnum as i32);
+        p.set_string(npos, name);
+        lm.lock().unwrap().append(p.contents())
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    io::Error,
+    string::FromUtf8Error,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    file::{blockid::BlockId, page::Page},
+    log::logmgr::LogMgr,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+use super::logrecord::{LogRecord, Op};
+
+pub struct SetBytesRecord {
+    txnum: usize,
+    offset: usize,
+    oldval: Vec<u8>,
+    newval: Vec<u8>,
+    blk: BlockId,
+}
+
+impl LogRecord for SetBytesRecord {
+    fn op(&self) -> Op {
+        Op::SetBytes
+    }
+
+    fn tx_number(&self) -> Option<usize> {
+        Some(self.txnum)
+    }
+
+    fn undo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_bytes(&self.blk, self.offset, &self.oldval, true)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+
+    fn redo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_bytes(&self.blk, self.offset, &self.newval, false)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+}
+
+impl SetBytesRecord {
+    pub fn new(p: Page) -> Result<SetBytesRecord, FromUtf8Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let txnum = p.get_int(tpos) as usize;
+        let fpos = tpos + bytes;
+        let filename = p.get_string(fpos)?;
+        let bpos = fpos + Page::bytes_length(filename.len());
+        let blknum = p.get_int(bpos);
+        let blk = BlockId::new(&filename, blknum);
+        let opos = bpos + bytes;
+        let offset = p.get_int(opos) as usize;
+        let vpos = opos + bytes;
+        let oldval = p.get_bytes(vpos).to_vec();
+        let npos = vpos + Page::bytes_length(oldval.len());
+        let newval = p.get_bytes(npos).to_vec();
+        Ok(SetBytesRecord {
+            txnum,
+            offset,
+            oldval,
+            newval,
+            blk,
+        })
+    }
+
+    pub fn write_to_log(
+        lm: &Arc<Mutex<LogMgr>>,
+        txnum: usize,
+        blk: BlockId,
+        offset: usize,
+        oldval: &[u8],
+        newval: &[u8],
+        blocksize: usize,
+    ) -> Result<usize, Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let fpos = tpos + bytes;
+        let bpos = fpos + Page::bytes_length(blk.file_name().len());
+        let opos = bpos + bytes;
+        let vpos = opos + bytes;
+        let fragment_size = (blocksize - 2 * bytes - (vpos + 2 * bytes)) / 2;
+        let mut lm = lm.lock().unwrap();
+        let mut lsn = 0;
+        for (i, (oldfrag, newfrag)) in oldval
+            .chunks(fragment_size)
+            .zip(newval.chunks(fragment_size))
+            .enumerate()
+        {
+            let npos = vpos + Page::bytes_length(oldfrag.len());
+            let reclen = npos + Page::bytes_length(newfrag.len());
+            let mut rec = Vec::with_capacity(reclen);
+            rec.resize(rec.capacity(), 0);
+            let mut p = Page::with_vec(rec);
+            p.set_int(0, Op::SetBytes as i32);
+            p.set_int(tpos, txnum as i32);
+            p.set_string(fpos, blk.file_name());
+            p.set_int(bpos, blk.number());
+            p.set_int(opos, (offset + i * fragment_size) as i32);
+            p.set_bytes(vpos, oldfrag);
+            p.set_bytes(npos, newfrag);
+            lsn = lm.append(p.contents())?;
+        }
+        Ok(lsn)
+    }
+}
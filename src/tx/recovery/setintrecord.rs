@@ -15,7 +15,8 @@ use super::logrecord::{LogRecord, Op};
 pub struct SetIntRecord {
     txnum: usize,
     offset: usize,
-    val: i32,
+    oldval: i32,
+    newval: i32,
     blk: BlockId,
 }
 
@@ -30,7 +31,14 @@ impl LogRecord for SetIntRecord {
 
     fn undo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
         tx.pin(&self.blk)?;
-        tx.set_int(&self.blk, self.offset, self.val, false)?;
+        tx.set_int(&self.blk, self.offset, self.oldval, true)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+
+    fn redo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_int(&self.blk, self.offset, self.newval, false)?;
         tx.unpin(&self.blk)?;
         Ok(())
     }
@@ -43,17 +51,20 @@ impl SetIntRecord {
         let txnum = p.get_int(tpos) as usize;
         let fpos = tpos + bytes;
         let filename = p.get_string(fpos)?;
-        let bpos = fpos + Page::max_length(filename.len());
+        let bpos = fpos + Page::bytes_length(filename.len());
         let blknum = p.get_int(bpos);
         let blk = BlockId::new(&filename, blknum);
         let opos = bpos + bytes;
         let offset = p.get_int(opos) as usize;
         let vpos = opos + bytes;
-        let val = p.get_int(vpos);
+        let oldval = p.get_int(vpos);
+        let npos = vpos + bytes;
+        let newval = p.get_int(npos);
         Ok(SetIntRecord {
             txnum,
             offset,
-            val,
+            oldval,
+            newval,
             blk,
         })
     }
@@ -63,15 +74,17 @@ impl SetIntRecord {
         txnum: usize,
         blk: BlockId,
         offset: usize,
-        val: i32,
+        oldval: i32,
+        newval: i32,
     ) -> Result<usize, Error> {
         let bytes = 4;
         let tpos = bytes;
         let fpos = tpos + bytes;
-        let bpos = fpos + Page::max_length(blk.file_name().len());
+        let bpos = fpos + Page::bytes_length(blk.file_name().len());
         let opos = bpos + bytes;
         let vpos = opos + bytes;
-        let mut rec = Vec::with_capacity(vpos + bytes);
+        let npos = vpos + bytes;
+        let mut rec = Vec::with_capacity(npos + bytes);
         rec.resize(rec.capacity(), 0);
         let mut p = Page::with_vec(rec);
         p.set_int(0, Op::SetInt as i32);
@@ -79,7 +92,8 @@ impl SetIntRecord {
         p.set_string(fpos, blk.file_name());
         p.set_int(bpos, blk.number());
         p.set_int(opos, offset as i32);
-        p.set_int(vpos, val);
+        p.set_int(vpos, oldval);
+        p.set_int(npos, newval);
         lm.lock().unwrap().append(p.contents())
     }
 }
@@ -15,7 +15,8 @@ use super::logrecord::{LogRecord, Op};
 pub struct SetStringRecord {
     txnum: usize,
     offset: usize,
-    val: String,
+    oldval: String,
+    newval: String,
     blk: BlockId,
 }
 
@@ -30,7 +31,14 @@ impl LogRecord for SetStringRecord {
 
     fn undo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
         tx.pin(&self.blk)?;
-        tx.set_string(&self.blk, self.offset, &self.val, false)?;
+        tx.set_string(&self.blk, self.offset, &self.oldval, true)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+
+    fn redo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_string(&self.blk, self.offset, &self.newval, false)?;
         tx.unpin(&self.blk)?;
         Ok(())
     }
@@ -43,17 +51,20 @@ impl SetStringRecord {
         let txnum = p.get_int(tpos) as usize;
         let fpos = tpos + bytes;
         let filename = p.get_string(fpos)?;
-        let bpos = fpos + Page::max_length(filename.len());
+        let bpos = fpos + Page::bytes_length(filename.len());
         let blknum = p.get_int(bpos);
         let blk = BlockId::new(&filename, blknum);
         let opos = bpos + bytes;
         let offset = p.get_int(opos) as usize;
         let vpos = opos + bytes;
-        let val = p.get_string(vpos)?;
+        let oldval = p.get_string(vpos)?;
+        let npos = vpos + Page::bytes_length(oldval.len());
+        let newval = p.get_string(npos)?;
         Ok(SetStringRecord {
             txnum,
             offset,
-            val,
+            oldval,
+            newval,
             blk,
         })
     }
@@ -63,15 +74,17 @@ impl SetStringRecord {
         txnum: usize,
         blk: BlockId,
         offset: usize,
-        val: &str,
+        oldval: &str,
+        newval: &str,
     ) -> Result<usize, Error> {
         let bytes = 4;
         let tpos = bytes;
         let fpos = tpos + bytes;
-        let bpos = fpos + Page::max_length(blk.file_name().len());
+        let bpos = fpos + Page::bytes_length(blk.file_name().len());
         let opos = bpos + bytes;
         let vpos = opos + bytes;
-        let reclen = vpos + Page::max_length(val.len());
+        let npos = vpos + Page::bytes_length(oldval.len());
+        let reclen = npos + Page::bytes_length(newval.len());
         let mut rec = Vec::with_capacity(reclen);
         rec.resize(rec.capacity(), 0);
         let mut p = Page::with_vec(rec);
@@ -80,7 +93,8 @@ impl SetStringRecord {
         p.set_string(fpos, blk.file_name());
         p.set_int(bpos, blk.number());
         p.set_int(opos, offset as i32);
-        p.set_string(vpos, val);
+        p.set_string(vpos, oldval);
+        p.set_string(npos, newval);
         lm.lock().unwrap().append(p.contents())
     }
 }
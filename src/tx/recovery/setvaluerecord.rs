@@ -0,0 +1,108 @@
+use std::{
+    io::Error,
+    string::FromUtf8Error,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    file::{blockid::BlockId, page::Page},
+    log::logmgr::LogMgr,
+    tx::transaction::{Transaction, TransactionError},
+};
+
+use super::{
+    logrecord::{LogRecord, Op},
+    value::Value,
+};
+
+pub struct SetValueRecord {
+    txnum: usize,
+    offset: usize,
+    oldval: Value,
+    newval: Value,
+    blk: BlockId,
+}
+
+impl LogRecord for SetValueRecord {
+    fn op(&self) -> Op {
+        Op::SetValue
+    }
+
+    fn tx_number(&self) -> Option<usize> {
+        Some(self.txnum)
+    }
+
+    fn undo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_value(&self.blk, self.offset, self.oldval, true)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+
+    fn redo(&self, tx: &mut Transaction) -> Result<(), TransactionError> {
+        tx.pin(&self.blk)?;
+        tx.set_value(&self.blk, self.offset, self.newval, false)?;
+        tx.unpin(&self.blk)?;
+        Ok(())
+    }
+}
+
+impl SetValueRecord {
+    pub fn new(p: Page) -> Result<Option<SetValueRecord>, FromUtf8Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let txnum = p.get_int(tpos) as usize;
+        let fpos = tpos + bytes;
+        let filename = p.get_string(fpos)?;
+        let bpos = fpos + Page::bytes_length(filename.len());
+        let blknum = p.get_int(bpos);
+        let blk = BlockId::new(&filename, blknum);
+        let opos = bpos + bytes;
+        let offset = p.get_int(opos) as usize;
+        let kpos = opos + bytes;
+        let Some(kind) = Value::with_tag(p.get_int(kpos)) else {
+            return Ok(None);
+        };
+        let vpos = kpos + bytes;
+        let oldval = kind.read(&p, vpos);
+        let npos = vpos + kind.size();
+        let newval = kind.read(&p, npos);
+        Ok(Some(SetValueRecord {
+            txnum,
+            offset,
+            oldval,
+            newval,
+            blk,
+        }))
+    }
+
+    pub fn write_to_log(
+        lm: &Arc<Mutex<LogMgr>>,
+        txnum: usize,
+        blk: BlockId,
+        offset: usize,
+        oldval: Value,
+        newval: Value,
+    ) -> Result<usize, Error> {
+        let bytes = 4;
+        let tpos = bytes;
+        let fpos = tpos + bytes;
+        let bpos = fpos + Page::bytes_length(blk.file_name().len());
+        let opos = bpos + bytes;
+        let kpos = opos + bytes;
+        let vpos = kpos + bytes;
+        let npos = vpos + newval.size();
+        let mut rec = Vec::with_capacity(npos + newval.size());
+        rec.resize(rec.capacity(), 0);
+        let mut p = Page::with_vec(rec);
+        p.set_int(0, Op::SetValue as i32);
+        p.set_int(tpos, txnum as i32);
+        p.set_string(fpos, blk.file_name());
+        p.set_int(bpos, blk.number());
+        p.set_int(opos, offset as i32);
+        p.set_int(kpos, newval.tag());
+        oldval.write(&mut p, vpos);
+        newval.write(&mut p, npos);
+        lm.lock().unwrap().append(p.contents())
+    }
+}
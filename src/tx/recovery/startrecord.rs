@@ -27,6 +27,10 @@ impl LogRecord for StartRecord {
     fn undo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
         Ok(())
     }
+
+    fn redo(&self, _: &mut Transaction) -> Result<(), TransactionError> {
+        Ok(())
+    }
 }
 
 impl StartRecord {
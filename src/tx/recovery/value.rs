@@ -0,0 +1,54 @@
+use crate::file::page::Page;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    Long(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+impl Value {
+    const LONG: i32 = 0;
+    const DOUBLE: i32 = 1;
+    const BOOL: i32 = 2;
+
+    pub fn tag(&self) -> i32 {
+        match self {
+            Value::Long(_) => Value::LONG,
+            Value::Double(_) => Value::DOUBLE,
+            Value::Bool(_) => Value::BOOL,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            Value::Long(_) | Value::Double(_) => 8,
+            Value::Bool(_) => 1,
+        }
+    }
+
+    pub fn with_tag(tag: i32) -> Option<Value> {
+        match tag {
+            Value::LONG => Some(Value::Long(0)),
+            Value::DOUBLE => Some(Value::Double(0.0)),
+            Value::BOOL => Some(Value::Bool(false)),
+            _ => None,
+        }
+    }
+
+    pub fn read(&self, p: &Page, offset: usize) -> Value {
+        match self {
+            Value::Long(_) => Value::Long(p.get_long(offset)),
+            Value::Double(_) => Value::Double(p.get_double(offset)),
+            Value::Bool(_) => Value::Bool(p.get_bool(offset)),
+        }
+    }
+
+    pub fn write(&self, p: &mut Page, offset: usize) {
+        match self {
+            Value::Long(n) => p.set_long(offset, *n),
+            Value::Double(d) => p.set_double(offset, *d),
+            Value::Bool(b) => p.set_bool(offset, *b),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::{
+        file::{blockid::BlockId, durability::Durability},
+        plan::plan::PlanControl,
+        query::scan::ScanControl,
+        server::simpledb::SimpleDB,
+        tx::transaction::{Transaction, TransactionError},
+    };
+
+    #[test]
+    fn savepointtest() {
+        let db = SimpleDB::with_params("savepointtest", 400, 8, Durability::NoSync).unwrap();
+        let blk = BlockId::new("testfile", 1);
+
+        let mut tx = db.new_tx().unwrap();
+        tx.pin(&blk).unwrap();
+        tx.set_int(&blk, 80, 1, true).unwrap();
+        tx.set_string(&blk, 40, "one", true).unwrap();
+        tx.savepoint("s1").unwrap();
+        tx.set_int(&blk, 80, 2, true).unwrap();
+        tx.set_string(&blk, 40, "two", true).unwrap();
+        tx.savepoint("s2").unwrap();
+        tx.set_int(&blk, 80, 3, true).unwrap();
+
+        tx.rollback_to_savepoint("s2").unwrap();
+        assert_eq!(2, tx.get_int(&blk, 80).unwrap());
+        assert_eq!("two", tx.get_string(&blk, 40).unwrap());
+
+        tx.rollback_to_savepoint("s1").unwrap();
+        assert_eq!(1, tx.get_int(&blk, 80).unwrap());
+        assert_eq!("one", tx.get_string(&blk, 40).unwrap());
+        assert!(matches!(
+            tx.rollback_to_savepoint("s2"),
+            Err(TransactionError::NoSuchSavepoint(_))
+        ));
+
+        tx.set_int(&blk, 80, 4, true).unwrap();
+        tx.rollback_to_savepoint("s1").unwrap();
+        assert_eq!(1, tx.get_int(&blk, 80).unwrap());
+
+        tx.release_savepoint("s1").unwrap();
+        assert!(matches!(
+            tx.rollback_to_savepoint("s1"),
+            Err(TransactionError::NoSuchSavepoint(_))
+        ));
+        tx.commit().unwrap();
+
+        let mut tx = db.new_tx().unwrap();
+        tx.pin(&blk).unwrap();
+        assert_eq!(1, tx.get_int(&blk, 80).unwrap());
+        tx.savepoint("s1").unwrap();
+        tx.set_int(&blk, 80, 5, true).unwrap();
+        tx.rollback().unwrap();
+
+        let mut tx = db.new_tx().unwrap();
+        tx.pin(&blk).unwrap();
+        assert_eq!(1, tx.get_int(&blk, 80).unwrap());
+        assert_eq!("one", tx.get_string(&blk, 40).unwrap());
+        tx.commit().unwrap();
+
+        fs::remove_dir_all("savepointtest").unwrap();
+    }
+
+    #[test]
+    fn savepointundotest() {
+        let db = SimpleDB::with_params("savepointundotest", 400, 8, Durability::NoSync).unwrap();
+        let undone = Arc::new(Mutex::new(Vec::new()));
+        let hook = |name: &'static str| {
+            let undone = undone.clone();
+            Box::new(move || undone.lock().unwrap().push(name))
+        };
+
+        let mut tx = db.new_tx().unwrap();
+        tx.on_undo(hook("a"));
+        tx.savepoint("s1").unwrap();
+        tx.on_undo(hook("b"));
+        tx.rollback_to_savepoint("s1").unwrap();
+        assert_eq!(vec!["b"], *undone.lock().unwrap());
+
+        tx.on_undo(hook("c"));
+        tx.rollback().unwrap();
+        assert_eq!(vec!["b", "a", "c"], *undone.lock().unwrap());
+
+        fs::remove_dir_all("savepointundotest").unwrap();
+    }
+
+    #[test]
+    fn savepointsqltest() {
+        let db = SimpleDB::new("savepointsqltest").unwrap();
+        let planner = db.planner().unwrap();
+        let tx = Arc::new(Mutex::new(db.new_tx().unwrap()));
+        let cmds = [
+            "create table T(A int, B varchar(9))",
+            "insert into T(A, B) values(1, 'one')",
+            "savepoint s1",
+            "insert into T(A, B) values(2, 'two')",
+            "savepoint s2",
+            "insert into T(A, B) values(3, 'three')",
+            "rollback to savepoint s2",
+            "release savepoint s2",
+            "insert into T(A, B) values(4, 'four')",
+        ];
+        for cmd in cmds {
+            planner
+                .lock()
+                .unwrap()
+                .execute_update(cmd, tx.clone())
+                .unwrap();
+        }
+        assert!(planner
+            .lock()
+            .unwrap()
+            .execute_update("rollback to savepoint s2", tx.clone())
+            .is_err());
+        assert_eq!(vec![1, 2, 4], select_a(&db, tx.clone()));
+
+        planner
+            .lock()
+            .unwrap()
+            .execute_update("rollback to savepoint s1", tx.clone())
+            .unwrap();
+        assert_eq!(vec![1], select_a(&db, tx.clone()));
+        tx.lock().unwrap().commit().unwrap();
+
+        fs::remove_dir_all("savepointsqltest").unwrap();
+    }
+
+    fn select_a(db: &SimpleDB, tx: Arc<Mutex<Transaction>>) -> Vec<i32> {
+        let planner = db.planner().unwrap();
+        let p = planner
+            .lock()
+            .unwrap()
+            .create_query_plan("select A from T", tx)
+            .unwrap();
+        let mut s = p.open().unwrap();
+        let mut vals = Vec::new();
+        while s.next().unwrap() {
+            vals.push(s.get_int("a").unwrap());
+        }
+        s.close().unwrap();
+        vals
+    }
+}
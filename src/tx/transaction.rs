@@ -1,4 +1,5 @@
 use std::{
+    collections::{HashMap, HashSet},
     io::Error,
     string::FromUtf8Error,
     sync::{
@@ -8,27 +9,38 @@ use std::{
 };
 
 use crate::{
-    buffer::buffermgr::{AbortError, BufferMgr},
-    file::{blockid::BlockId, filemgr::FileMgr},
+    buffer::{
+        buffer::Buffer,
+        buffermgr::{AbortError, BufferMgr},
+        bufferstats::BufferStats,
+    },
+    file::{blockid::BlockId, corruptpageerror::CorruptPageError, filemgr::FileMgr, page::Page},
     log::logmgr::LogMgr,
     tx::recovery::rollbackrecord::RollbackRecord,
 };
 
 use super::{
     bufferlist::BufferList,
-    concurrency::concurrencymgr::ConcurrencyMgr,
+    concurrency::{concurrencymgr::ConcurrencyMgr, locktable::LockTable},
+    isolationlevel::IsolationLevel,
+    mvcc::{snapshot::Snapshot, versionmgr::VersionMgr},
     recovery::{
         checkpointrecord::CheckPointRecord,
         logrecord::{create_log_record, Op},
         recoverymgr::{RecoveryError, RecoveryMgr},
+        recoverytarget::RecoveryTarget,
+        value::Value,
     },
 };
 
 static NEXT_TX_NUM: AtomicUsize = AtomicUsize::new(0);
 
-fn next_tx_num() -> usize {
-    NEXT_TX_NUM.fetch_add(1, Ordering::SeqCst);
-    NEXT_TX_NUM.load(Ordering::SeqCst)
+pub(in crate::tx) fn next_tx_num() -> usize {
+    NEXT_TX_NUM.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+pub fn advance_tx_num(txnum: usize) {
+    NEXT_TX_NUM.fetch_max(txnum, Ordering::SeqCst);
 }
 
 #[derive(Debug)]
@@ -37,6 +49,11 @@ pub enum TransactionError {
     Recovery(RecoveryError),
     Utf8(FromUtf8Error),
     IO(Error),
+    ReadOnly,
+    NoSuchSavepoint(String),
+    StringTooLong(String),
+    IndexKeyTooLong(String),
+    TypeMismatch(String),
     General,
 }
 
@@ -72,19 +89,57 @@ pub struct Transaction {
     txnum: usize,
     mybuffers: BufferList,
     lm: Arc<Mutex<LogMgr>>,
+    vm: Arc<VersionMgr>,
+    read_only: bool,
+    snapshot: Option<Snapshot>,
+    savepoints: Vec<(String, usize)>,
+    undo_hooks: Vec<Box<dyn Fn() + Send>>,
 }
 
 impl Transaction {
-    const END_OF_FILE: i32 = -1;
+    pub(in crate::tx) const END_OF_FILE: i32 = -1;
 
     pub fn new(
         fm: Arc<FileMgr>,
         lm: Arc<Mutex<LogMgr>>,
         bm: Arc<Mutex<BufferMgr>>,
+        locktbl: Arc<LockTable>,
+        vm: Arc<VersionMgr>,
+    ) -> Result<Transaction, Error> {
+        Transaction::with_mode(fm, lm, bm, locktbl, vm, false)
+    }
+
+    pub fn read_only(
+        fm: Arc<FileMgr>,
+        lm: Arc<Mutex<LogMgr>>,
+        bm: Arc<Mutex<BufferMgr>>,
+        locktbl: Arc<LockTable>,
+        vm: Arc<VersionMgr>,
     ) -> Result<Transaction, Error> {
-        let txnum = next_tx_num();
-        let recovery_mgr = RecoveryMgr::new(txnum, lm.clone(), bm.clone())?;
-        let concur_mgr = ConcurrencyMgr::new();
+        Transaction::with_mode(fm, lm, bm, locktbl, vm, true)
+    }
+
+    fn with_mode(
+        fm: Arc<FileMgr>,
+        lm: Arc<Mutex<LogMgr>>,
+        bm: Arc<Mutex<BufferMgr>>,
+        locktbl: Arc<LockTable>,
+        vm: Arc<VersionMgr>,
+        read_only: bool,
+    ) -> Result<Transaction, Error> {
+        let txnum = vm.begin(next_tx_num);
+        let recovery_mgr = match fm
+            .reserve_tx_num(txnum)
+            .and_then(|_| RecoveryMgr::new(txnum, lm.clone()))
+        {
+            Ok(recovery_mgr) => recovery_mgr,
+            Err(e) => {
+                vm.end(txnum);
+                return Err(e);
+            }
+        };
+        let snapshot = vm.start(txnum, recovery_mgr.start_lsn(), read_only);
+        let concur_mgr = ConcurrencyMgr::new(txnum, locktbl);
         let mybuffers = BufferList::new(bm.clone());
         Ok(Transaction {
             recovery_mgr,
@@ -94,11 +149,19 @@ impl Transaction {
             txnum,
             mybuffers,
             lm,
+            vm,
+            read_only,
+            snapshot,
+            savepoints: Vec::new(),
+            undo_hooks: Vec::new(),
         })
     }
 
-    pub fn commit(&mut self) -> Result<(), Error> {
+    pub fn commit(&mut self) -> Result<(), TransactionError> {
+        self.concur_mgr.check_wounded()?;
         self.recovery_mgr.commit()?;
+        self.undo_hooks.clear();
+        self.vm.end(self.txnum);
         println!("transaction {} committed", self.txnum);
         self.concur_mgr.release();
         self.mybuffers.unpin_all();
@@ -106,22 +169,60 @@ impl Transaction {
     }
 
     pub fn rollback(&mut self) -> Result<(), TransactionError> {
-        self.do_rollback()?;
-        self.bm.lock().unwrap().flush_all(self.txnum)?;
+        self.do_rollback(None)?;
+        self.undo_hooks.drain(..).for_each(|hook| hook());
         let lsn = RollbackRecord::write_to_log(&self.lm, self.txnum)?;
         self.lm.lock().unwrap().flush(lsn)?;
+        self.vm.end(self.txnum);
         println!("transaction {} rolled back", self.txnum);
         self.concur_mgr.release();
         self.mybuffers.unpin_all();
         Ok(())
     }
 
+    pub fn savepoint(&mut self, name: &str) -> Result<(), TransactionError> {
+        self.savepoints.retain(|(s, _)| s != name);
+        self.recovery_mgr.savepoint(name)?;
+        self.savepoints
+            .push((name.to_string(), self.undo_hooks.len()));
+        Ok(())
+    }
+
+    pub fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), TransactionError> {
+        let pos = self.savepoint_position(name)?;
+        self.do_rollback(Some(name))?;
+        let hooks = self.savepoints[pos].1;
+        self.undo_hooks.drain(hooks..).for_each(|hook| hook());
+        self.savepoints.truncate(pos + 1);
+        Ok(())
+    }
+
+    pub fn release_savepoint(&mut self, name: &str) -> Result<(), TransactionError> {
+        let pos = self.savepoint_position(name)?;
+        self.savepoints.truncate(pos);
+        Ok(())
+    }
+
     pub fn recover(&mut self) -> Result<(), TransactionError> {
         self.bm.lock().unwrap().flush_all(self.txnum)?;
         self.do_recover()?;
         self.bm.lock().unwrap().flush_all(self.txnum)?;
+        self.fm.sync_all()?;
+        let lsn = CheckPointRecord::write_to_log(&self.lm)?;
+        self.lm.lock().unwrap().flush(lsn)?;
+        RecoveryMgr::truncate_log(&self.lm, &self.vm, lsn)?;
+        Ok(())
+    }
+
+    pub fn recover_to(&mut self, target: RecoveryTarget) -> Result<(), TransactionError> {
+        self.bm.lock().unwrap().flush_all(self.txnum)?;
+        self.do_recover_to(target)?;
+        self.bm.lock().unwrap().flush_all(self.txnum)?;
+        self.fm.sync_all()?;
+        self.lm.lock().unwrap().reset()?;
         let lsn = CheckPointRecord::write_to_log(&self.lm)?;
         self.lm.lock().unwrap().flush(lsn)?;
+        RecoveryMgr::truncate_log(&self.lm, &self.vm, lsn)?;
         Ok(())
     }
 
@@ -134,35 +235,162 @@ impl Transaction {
     }
 
     pub fn get_int(&mut self, blk: &BlockId, offset: usize) -> Result<i32, TransactionError> {
-        self.concur_mgr.s_lock(blk)?;
+        self.read(blk, |p| Ok(p.get_int(offset)))
+    }
+
+    pub fn get_long(&mut self, blk: &BlockId, offset: usize) -> Result<i64, TransactionError> {
+        self.read(blk, |p| Ok(p.get_long(offset)))
+    }
+
+    pub fn get_double(&mut self, blk: &BlockId, offset: usize) -> Result<f64, TransactionError> {
+        self.read(blk, |p| Ok(p.get_double(offset)))
+    }
+
+    pub fn get_bool(&mut self, blk: &BlockId, offset: usize) -> Result<bool, TransactionError> {
+        self.read(blk, |p| Ok(p.get_bool(offset)))
+    }
+
+    pub fn get_string(&mut self, blk: &BlockId, offset: usize) -> Result<String, TransactionError> {
+        self.read(blk, |p| Ok(p.get_string(offset)?))
+    }
+
+    pub fn get_bytes(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        len: usize,
+    ) -> Result<Vec<u8>, TransactionError> {
+        self.read(blk, |p| Ok(p.get_raw(offset, len).to_vec()))
+    }
+
+    pub fn set_int(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: i32,
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.write(
+            blk,
+            ok_to_log,
+            |rm, buff| rm.set_int(buff, offset, val),
+            |p| p.set_int(offset, val),
+        )
+    }
+
+    pub fn set_long(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: i64,
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.set_value(blk, offset, Value::Long(val), ok_to_log)
+    }
+
+    pub fn set_double(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: f64,
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.set_value(blk, offset, Value::Double(val), ok_to_log)
+    }
+
+    pub fn set_bool(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: bool,
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.set_value(blk, offset, Value::Bool(val), ok_to_log)
+    }
+
+    pub fn set_string(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: &str,
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.write(
+            blk,
+            ok_to_log,
+            |rm, buff| rm.set_string(buff, offset, val),
+            |p| p.set_string(offset, val),
+        )
+    }
+
+    pub fn set_bytes(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: &[u8],
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.write(
+            blk,
+            ok_to_log,
+            |rm, buff| rm.set_bytes(buff, offset, val),
+            |p| p.set_raw(offset, val),
+        )
+    }
+
+    pub(in crate::tx) fn set_value(
+        &mut self,
+        blk: &BlockId,
+        offset: usize,
+        val: Value,
+        ok_to_log: bool,
+    ) -> Result<(), TransactionError> {
+        self.write(
+            blk,
+            ok_to_log,
+            |rm, buff| rm.set_value(buff, offset, val),
+            |p| val.write(p, offset),
+        )
+    }
+
+    pub fn peek_int(&mut self, blk: &BlockId, offset: usize) -> Result<i32, TransactionError> {
         let idx = self.mybuffers.get_index(blk);
         if let Some(idx) = idx {
             let mut bm = self.bm.lock().unwrap();
-            let buff = bm.buffer(idx);
-            return Ok(buff.contents().get_int(offset));
+            return Ok(bm.buffer(idx).contents().get_int(offset));
         }
         Err(TransactionError::General)
     }
 
-    pub fn get_string(&mut self, blk: &BlockId, offset: usize) -> Result<String, TransactionError> {
-        self.concur_mgr.s_lock(blk)?;
+    fn read<T>(
+        &mut self,
+        blk: &BlockId,
+        get: impl FnOnce(&Page) -> Result<T, TransactionError>,
+    ) -> Result<T, TransactionError> {
+        if self.snapshot.is_none() {
+            self.concur_mgr.s_lock(blk)?;
+        }
         let idx = self.mybuffers.get_index(blk);
         if let Some(idx) = idx {
             let mut bm = self.bm.lock().unwrap();
             let buff = bm.buffer(idx);
-            let s = buff.contents().get_string(offset)?;
-            return Ok(s);
+            let val = get(buff.contents())?;
+            self.concur_mgr.end_read(blk);
+            return Ok(val);
         }
         Err(TransactionError::General)
     }
 
-    pub fn set_int(
+    fn write(
         &mut self,
         blk: &BlockId,
-        offset: usize,
-        val: i32,
         ok_to_log: bool,
+        log: impl FnOnce(&mut RecoveryMgr, &mut Buffer) -> Result<usize, RecoveryError>,
+        put: impl FnOnce(&mut Page),
     ) -> Result<(), TransactionError> {
+        if self.read_only {
+            return Err(TransactionError::ReadOnly);
+        }
         self.concur_mgr.x_lock(blk)?;
         let idx = self.mybuffers.get_index(blk);
         if let Some(idx) = idx {
@@ -170,35 +398,31 @@ impl Transaction {
             let buff = bm.buffer(idx);
             let mut lsn = None;
             if ok_to_log {
-                lsn = Some(self.recovery_mgr.set_int(buff, offset, val)?);
+                lsn = Some(log(&mut self.recovery_mgr, buff)?);
+            }
+            put(buff.contents());
+            if ok_to_log {
+                lsn = self.recovery_mgr.log_page_image(buff)?.or(lsn);
             }
-            let p = buff.contents();
-            p.set_int(offset, val);
             buff.set_modified(self.txnum, lsn);
             return Ok(());
         }
         Err(TransactionError::General)
     }
 
-    pub fn set_string(
+    pub(in crate::tx) fn set_page(
         &mut self,
         blk: &BlockId,
         offset: usize,
-        val: &str,
-        ok_to_log: bool,
+        image: &[u8],
     ) -> Result<(), TransactionError> {
         self.concur_mgr.x_lock(blk)?;
         let idx = self.mybuffers.get_index(blk);
         if let Some(idx) = idx {
             let mut bm = self.bm.lock().unwrap();
             let buff = bm.buffer(idx);
-            let mut lsn = None;
-            if ok_to_log {
-                lsn = Some(self.recovery_mgr.set_string(buff, offset, val)?);
-            }
-            let p = buff.contents();
-            p.set_string(offset, val);
-            buff.set_modified(self.txnum, lsn);
+            buff.contents().contents()[offset..offset + image.len()].copy_from_slice(image);
+            buff.set_modified(self.txnum, None);
             return Ok(());
         }
         Err(TransactionError::General)
@@ -206,25 +430,79 @@ impl Transaction {
 
     pub fn size(&mut self, filename: &str) -> Result<usize, TransactionError> {
         let dummyblk = BlockId::new(filename, Transaction::END_OF_FILE);
-        self.concur_mgr.s_lock(&dummyblk)?;
-        Ok(self.fm.length(filename)?)
+        if self.snapshot.is_none() {
+            self.concur_mgr.s_lock(&dummyblk)?;
+        }
+        let size = self.fm.length(filename)?;
+        self.concur_mgr.end_read(&dummyblk);
+        Ok(size)
     }
 
     pub fn append(&mut self, filename: &str) -> Result<BlockId, TransactionError> {
+        if self.read_only {
+            return Err(TransactionError::ReadOnly);
+        }
         let dummyblk = BlockId::new(filename, Transaction::END_OF_FILE);
         self.concur_mgr.x_lock(&dummyblk)?;
         Ok(self.fm.append(filename)?)
     }
 
-    pub fn block_size(&self) -> usize {
-        self.fm.block_size()
+    pub fn tx_number(&self) -> usize {
+        self.txnum
+    }
+
+    pub fn is_versioned(&self) -> bool {
+        self.vm.is_enabled()
+    }
+
+    pub fn has_snapshot(&self) -> bool {
+        self.snapshot.is_some()
+    }
+
+    pub fn sees(&self, txnum: usize) -> bool {
+        self.snapshot.as_ref().is_none_or(|s| s.sees(txnum))
+    }
+
+    pub fn is_obsolete(&self, txnum: usize) -> bool {
+        self.vm.is_obsolete(txnum)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn isolation_level(&self) -> IsolationLevel {
+        self.concur_mgr.isolation_level()
+    }
+
+    pub fn set_isolation_level(&mut self, isolation: IsolationLevel) {
+        self.concur_mgr.set_isolation_level(isolation);
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.fm.page_size()
+    }
+
+    pub fn on_undo(&mut self, hook: Box<dyn Fn() + Send>) {
+        self.undo_hooks.push(hook);
     }
 
     pub fn available_buffs(&self) -> usize {
         self.bm.lock().unwrap().available()
     }
 
-    fn do_rollback(&mut self) -> Result<(), TransactionError> {
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.bm.lock().unwrap().stats()
+    }
+
+    fn savepoint_position(&self, name: &str) -> Result<usize, TransactionError> {
+        self.savepoints
+            .iter()
+            .position(|(s, _)| s == name)
+            .ok_or_else(|| TransactionError::NoSuchSavepoint(name.to_string()))
+    }
+
+    fn do_rollback(&mut self, savepoint: Option<&str>) -> Result<(), TransactionError> {
         let mut recs = Vec::new();
         for bytes in self.lm.lock().unwrap().iterator()? {
             let rec = create_log_record(bytes)?;
@@ -233,6 +511,9 @@ impl Transaction {
                     if rec.op() == Op::Start {
                         break;
                     }
+                    if savepoint.is_some() && rec.savepoint_name() == savepoint {
+                        break;
+                    }
                     recs.push(rec);
                 }
             }
@@ -245,24 +526,143 @@ impl Transaction {
     }
 
     fn do_recover(&mut self) -> Result<(), TransactionError> {
-        let mut finished_txs = Vec::new();
+        let mut finished_txs = HashSet::new();
+        let mut started_txs = HashSet::new();
+        let mut end_seen = false;
+        let mut pending: Option<HashSet<usize>> = None;
+        let mut redo_len = None;
         let mut recs = Vec::new();
         for bytes in self.lm.lock().unwrap().iterator()? {
+            if pending.as_ref().map(|p| p.is_empty()).unwrap_or(false) {
+                break;
+            }
             let rec = create_log_record(bytes)?;
-            if rec.op() == Op::CheckPoint {
-                return Ok(());
+            match rec.op() {
+                Op::CheckPoint => break,
+                Op::EndCheckPoint => end_seen = true,
+                Op::BeginCheckPoint if end_seen && pending.is_none() => {
+                    redo_len = Some(recs.len());
+                    pending = Some(
+                        rec.active_txs()
+                            .into_iter()
+                            .filter(|t| !finished_txs.contains(t) && !started_txs.contains(t))
+                            .collect(),
+                    );
+                }
+                _ => {}
             }
             if let Some(tx_number) = rec.tx_number() {
                 if rec.op() == Op::Commit || rec.op() == Op::Rollback {
-                    finished_txs.push(tx_number);
-                } else if !finished_txs.contains(&tx_number) {
-                    recs.push(rec);
+                    finished_txs.insert(tx_number);
+                } else if rec.op() == Op::Start {
+                    started_txs.insert(tx_number);
+                }
+                if let Some(pending) = &mut pending {
+                    if !pending.contains(&tx_number) {
+                        continue;
+                    }
+                    if rec.op() == Op::Start {
+                        pending.remove(&tx_number);
+                    }
                 }
             }
+            recs.push(rec);
+        }
+        let redo_len = redo_len.unwrap_or(recs.len());
+        for rec in recs[..redo_len].iter() {
+            if let Some(blk) = rec.imaged_block() {
+                self.repair_page(blk)?;
+            }
+        }
+        for rec in recs[..redo_len].iter().rev() {
+            rec.redo(self)?;
         }
         for rec in recs.iter() {
-            rec.undo(self)?;
+            if let Some(tx_number) = rec.tx_number() {
+                if !finished_txs.contains(&tx_number) {
+                    rec.undo(self)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn repair_page(&self, blk: &BlockId) -> Result<(), TransactionError> {
+        let mut p = Page::new(self.fm.page_size());
+        match self.fm.read(blk, &mut p) {
+            Err(e) if e.get_ref().map(|e| e.is::<CorruptPageError>()) == Some(true) => {
+                self.fm.write(blk, &mut Page::new(self.fm.page_size()))?;
+                Ok(())
+            }
+            result => Ok(result?),
+        }
+    }
+
+    fn do_recover_to(&mut self, target: RecoveryTarget) -> Result<(), TransactionError> {
+        let mut recs = Vec::new();
+        {
+            let mut lm = self.lm.lock().unwrap();
+            let mut lsn = lm.last_lsn();
+            for bytes in lm.iterator()? {
+                recs.push((lsn, create_log_record(bytes)?));
+                lsn -= 1;
+            }
+        }
+        recs.reverse();
+        if let Some(txnum) = recs.iter().filter_map(|(_, rec)| rec.tx_number()).max() {
+            advance_tx_num(txnum);
+        }
+        let target_lsn = match target {
+            RecoveryTarget::Lsn(lsn) => lsn,
+            RecoveryTarget::TxNum(txnum) => recs
+                .iter()
+                .rev()
+                .find(|(_, rec)| rec.op() == Op::Commit && rec.tx_number() == Some(txnum))
+                .map(|(lsn, _)| *lsn)
+                .ok_or(TransactionError::General)?,
+        };
+        recs.retain(|(lsn, _)| *lsn <= target_lsn);
+
+        let mut starts = HashMap::new();
+        let mut unfinished = HashSet::new();
+        let mut owners = Vec::with_capacity(recs.len());
+        for (lsn, rec) in recs.iter() {
+            let owner = rec.tx_number().map(|txnum| {
+                if rec.op() == Op::Start {
+                    starts.insert(txnum, *lsn);
+                }
+                (txnum, starts.get(&txnum).copied().unwrap_or(0))
+            });
+            match (rec.op(), owner) {
+                (Op::Start, Some(owner)) => {
+                    unfinished.insert(owner);
+                }
+                (Op::Commit, Some(owner)) | (Op::Rollback, Some(owner)) => {
+                    unfinished.remove(&owner);
+                }
+                (Op::CheckPoint, _) => unfinished.clear(),
+                _ => {}
+            }
+            rec.redo(self)?;
+            owners.push(owner);
+        }
+        for ((_, rec), owner) in recs.iter().zip(owners).rev() {
+            if let Some(owner) = owner {
+                if unfinished.contains(&owner) {
+                    rec.undo(self)?;
+                }
+            }
         }
         Ok(())
     }
 }
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if self.vm.is_active(self.txnum) && self.rollback().is_err() {
+            self.vm.end(self.txnum);
+            self.concur_mgr.release();
+            self.mybuffers.unpin_all();
+        }
+    }
+}
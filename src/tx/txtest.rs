@@ -2,24 +2,32 @@
 mod tests {
     use std::fs;
 
-    use crate::{file::blockid::BlockId, server::simpledb::SimpleDB, tx::transaction::Transaction};
+    use crate::{
+        file::{blockid::BlockId, durability::Durability},
+        server::simpledb::SimpleDB,
+        tx::transaction::Transaction,
+    };
 
     #[test]
     fn txtest() {
-        let mut db = SimpleDB::with_params("txtest", 400, 8).unwrap();
+        let mut db = SimpleDB::with_params("txtest", 400, 8, Durability::NoSync).unwrap();
         let fm = db.file_mgr();
         let lm = db.log_mgr();
 
         let bm = db.buffer_mgr();
+        let lt = db.lock_table();
+        let vm = db.version_mgr();
 
-        let mut tx1 = Transaction::new(fm.clone(), lm.clone(), bm.clone()).unwrap();
+        let mut tx1 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
         let blk = BlockId::new("testfile", 1);
         tx1.pin(&blk).unwrap();
         tx1.set_int(&blk, 80, 1, false).unwrap();
         tx1.set_string(&blk, 40, "one", false).unwrap();
         tx1.commit().unwrap();
 
-        let mut tx2 = Transaction::new(fm.clone(), lm.clone(), bm.clone()).unwrap();
+        let mut tx2 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
         tx2.pin(&blk).unwrap();
         let ival = tx2.get_int(&blk, 80).unwrap();
         let sval = tx2.get_string(&blk, 40).unwrap();
@@ -30,7 +38,8 @@ mod tests {
         tx2.set_int(&blk, 80, newival, true).unwrap();
         tx2.set_string(&blk, 40, &newsval, true).unwrap();
         tx2.commit().unwrap();
-        let mut tx3 = Transaction::new(fm.clone(), lm.clone(), bm.clone()).unwrap();
+        let mut tx3 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
         tx3.pin(&blk).unwrap();
         assert_eq!(2, tx3.get_int(&blk, 80).unwrap());
         assert_eq!("one!", tx3.get_string(&blk, 40).unwrap());
@@ -38,7 +47,8 @@ mod tests {
         assert_eq!(9999, tx3.get_int(&blk, 80).unwrap());
         tx3.rollback().unwrap();
 
-        let mut tx4 = Transaction::new(fm.clone(), lm.clone(), bm.clone()).unwrap();
+        let mut tx4 =
+            Transaction::new(fm.clone(), lm.clone(), bm.clone(), lt.clone(), vm.clone()).unwrap();
         tx4.pin(&blk).unwrap();
         assert_eq!(2, tx4.get_int(&blk, 80).unwrap());
         tx4.commit().unwrap();